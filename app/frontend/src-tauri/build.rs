@@ -1,7 +1,38 @@
+use std::path::Path;
+
+/// FNV-1a 64; only needs to be stable between this build script and itself.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
 fn main() {
     let build_id = std::env::var("VITE_BUILD_ID")
         .or_else(|_| std::env::var("VITE_BUILD"))
         .unwrap_or_else(|_| "UNKNOWN_BUILD".to_string());
     println!("cargo:rustc-env=BUILD_ID={}", build_id);
+    // Sidecar binaries may carry the target triple in their name (see src/sidecar.rs).
+    println!("cargo:rustc-env=TARGET_TRIPLE={}", std::env::var("TARGET").unwrap_or_default());
+
+    // Expected frontend bundle: build-id.txt is written by vite (see vite.config.ts) next to index.html,
+    // whose hashed asset names make its own hash a fingerprint of the whole bundle.
+    let dist = Path::new("../dist");
+    let build_id_file = dist.join("build-id.txt");
+    let index_html = dist.join("index.html");
+    println!("cargo:rerun-if-changed={}", build_id_file.display());
+    println!("cargo:rerun-if-changed={}", index_html.display());
+    let frontend_build_id = std::fs::read_to_string(&build_id_file)
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|_| build_id.clone());
+    let bundle_hash = std::fs::read(&index_html)
+        .map(|b| format!("{:016x}", fnv1a(&b)))
+        .unwrap_or_else(|_| "none".to_string());
+    println!("cargo:rustc-env=FRONTEND_BUILD_ID={}", frontend_build_id);
+    println!("cargo:rustc-env=FRONTEND_BUNDLE_HASH={}", bundle_hash);
+
     tauri_build::build()
 }
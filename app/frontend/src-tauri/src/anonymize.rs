@@ -0,0 +1,166 @@
+// Anonymization pass for logs that leave the machine (diagnostics bundle): user names in paths,
+// email addresses and document file names are replaced with placeholders.
+
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+const DOCUMENT_EXTENSIONS: &str = "docx?|pdf|pptx?|xlsx?|odt|ods|odp|rtf|txt|md|epub|csv";
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AnonymizeCounts {
+  pub user_paths: usize,
+  pub emails: usize,
+  pub documents: usize,
+}
+
+impl AnonymizeCounts {
+  pub fn total(&self) -> usize {
+    self.user_paths + self.emails + self.documents
+  }
+}
+
+struct Patterns {
+  user_path: Regex,
+  email: Regex,
+  quoted_document: Regex,
+  path_document: Regex,
+  document: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+  static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+  PATTERNS.get_or_init(|| Patterns {
+    // C:\Users\<name>\, /home/<name>/, /Users/<name>/
+    user_path: Regex::new(r#"(?i)([\\/](?:users|home)[\\/])[^\\/\s"':]+"#).unwrap(),
+    email: Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap(),
+    // Quoted titles may contain spaces: "My Thesis Draft.docx"
+    quoted_document: Regex::new(&format!(r#"(["'])[^"'\r\n]+\.((?i:{}))(["'])"#, DOCUMENT_EXTENSIONS)).unwrap(),
+    // Last path segment may contain spaces: C:\Users\x\Documents\My Thesis.docx
+    path_document: Regex::new(&format!(r#"([\\/])[^\\/\r\n"'<>:|]+?\.((?i:{}))\b"#, DOCUMENT_EXTENSIONS)).unwrap(),
+    document: Regex::new(&format!(r#"[^\\/\s"':<>]+\.((?i:{}))\b"#, DOCUMENT_EXTENSIONS)).unwrap(),
+  })
+}
+
+/// The current user's name (USERNAME on Windows, USER elsewhere), if it is long enough to replace safely.
+fn current_user() -> Option<String> {
+  std::env::var("USERNAME")
+    .or_else(|_| std::env::var("USER"))
+    .ok()
+    .filter(|u| u.len() >= 3)
+}
+
+/// Anonymize `text`, returning the result and how many replacements of each kind were made.
+pub fn anonymize(text: &str) -> (String, AnonymizeCounts) {
+  anonymize_for(text, current_user())
+}
+
+fn anonymize_for(text: &str, user: Option<String>) -> (String, AnonymizeCounts) {
+  let p = patterns();
+  let mut counts = AnonymizeCounts {
+    user_paths: p.user_path.find_iter(text).count(),
+    ..Default::default()
+  };
+  let mut out = p.user_path.replace_all(text, "${1}<user>").into_owned();
+  // Paths outside the profile dir can still carry the user name (e.g. D:\alice\notes).
+  if let Some(user) = user {
+    let needle = format!(r"(?i)([\\/]){}([\\/])", regex::escape(&user));
+    if let Ok(re) = Regex::new(&needle) {
+      counts.user_paths += re.find_iter(&out).count();
+      out = re.replace_all(&out, "${1}<user>${2}").into_owned();
+    }
+  }
+
+  counts.emails = p.email.find_iter(&out).count();
+  out = p.email.replace_all(&out, "<email>").into_owned();
+
+  counts.documents = p.quoted_document.find_iter(&out).count();
+  out = p.quoted_document.replace_all(&out, "${1}<document>.${2}${3}").into_owned();
+  counts.documents += p.path_document.find_iter(&out).count();
+  out = p.path_document.replace_all(&out, "${1}<document>.${2}").into_owned();
+  // Placeholders never match here: '<' and '>' are excluded from the name class.
+  counts.documents += p.document.find_iter(&out).count();
+  out = p.document.replace_all(&out, "<document>.${1}").into_owned();
+
+  (out, counts)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn run(text: &str) -> (String, AnonymizeCounts) {
+    anonymize_for(text, None)
+  }
+
+  #[test]
+  fn windows_user_paths() {
+    let (out, counts) = run(r"loading C:\Users\alice\AppData\Local\AI_Mentor\settings.json");
+    assert_eq!(out, r"loading C:\Users\<user>\AppData\Local\AI_Mentor\settings.json");
+    assert_eq!(counts.user_paths, 1);
+    // Forward slashes and other casing are profile paths too.
+    let (out, _) = run("c:/users/Bob.Smith/Desktop");
+    assert_eq!(out, "c:/users/<user>/Desktop");
+  }
+
+  #[test]
+  fn unix_user_paths() {
+    let (out, counts) = run("/home/bob/.config/app and /Users/carol/Library/Logs");
+    assert_eq!(out, "/home/<user>/.config/app and /Users/<user>/Library/Logs");
+    assert_eq!(counts.user_paths, 2);
+  }
+
+  #[test]
+  fn user_name_outside_profile_dir() {
+    let (out, counts) = anonymize_for(r"D:\alice\notes\ and /mnt/ALICE/data", Some("alice".to_string()));
+    assert_eq!(out, r"D:\<user>\notes\ and /mnt/<user>/data");
+    assert_eq!(counts.user_paths, 2);
+    // Only whole path segments: a longer name containing it is left alone.
+    let (out, counts) = anonymize_for(r"D:\alice2\notes", Some("alice".to_string()));
+    assert_eq!(out, r"D:\alice2\notes");
+    assert_eq!(counts.user_paths, 0);
+  }
+
+  #[test]
+  fn emails() {
+    let (out, counts) = run("from a.b+tag@example.co.uk to x_y@mail.io, not me@localhost");
+    assert_eq!(out, "from <email> to <email>, not me@localhost");
+    assert_eq!(counts.emails, 2);
+  }
+
+  #[test]
+  fn quoted_titles_keep_quotes_and_extension() {
+    let (out, counts) = run(r#"opened "My Thesis Draft.docx" and 'Budget 2024.XLSX'"#);
+    assert_eq!(out, r#"opened "<document>.docx" and '<document>.XLSX'"#);
+    assert_eq!(counts.documents, 2);
+  }
+
+  #[test]
+  fn titles_with_spaces_in_paths() {
+    let (out, counts) = run(r"indexing C:\Users\alice\Documents\My Thesis.pdf done");
+    assert_eq!(out, r"indexing C:\Users\<user>\Documents\<document>.pdf done");
+    assert_eq!(counts.user_paths, 1);
+    assert_eq!(counts.documents, 1);
+    let (out, _) = run("/home/bob/Class Notes/week 1.md");
+    assert_eq!(out, "/home/<user>/Class Notes/<document>.md");
+  }
+
+  #[test]
+  fn bare_document_names() {
+    let (out, counts) = run("attached report.pdf, slides.pptx; kept app.log and main.rs");
+    assert_eq!(out, "attached <document>.pdf, <document>.pptx; kept app.log and main.rs");
+    assert_eq!(counts.documents, 2);
+  }
+
+  #[test]
+  fn counts_add_up() {
+    let text = "C:\\Users\\alice\\x.txt sent by alice@example.com with \"Plan A.docx\"";
+    let (out, counts) = run(text);
+    assert_eq!(out, "C:\\Users\\<user>\\<document>.txt sent by <email> with \"<document>.docx\"");
+    assert_eq!((counts.user_paths, counts.emails, counts.documents), (1, 1, 2));
+    assert_eq!(counts.total(), 4);
+    let (out, counts) = run("nothing to hide here");
+    assert_eq!(out, "nothing to hide here");
+    assert_eq!(counts.total(), 0);
+  }
+}
@@ -0,0 +1,219 @@
+// Native file attachments: files are streamed to the backend in CHUNK_BYTES pieces so the webview
+// never holds a whole file. Protocol, one upload id per file (random hex):
+//   PUT {api}/api/v1/sessions/<session>/attachments/<upload_id>
+//     Content-Range: bytes <start>-<end>/<total>, Content-Type: <detected mime>,
+//     X-File-Name: <percent-encoded UTF-8 name>
+// The backend (routes/api_v1/attachments.py) answers each chunk with 2xx; the response to the last
+// chunk is returned as-is.
+// Progress is emitted as `attachment-progress` to the session's chat window (or the main window).
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+use tokio::io::AsyncReadExt;
+
+use crate::error::AppError;
+use crate::settings::SettingsState;
+
+pub const ATTACHMENT_PROGRESS_EVENT: &str = "attachment-progress";
+pub const DEFAULT_ATTACHMENT_MAX_BYTES: u64 = 100 * 1024 * 1024;
+const CHUNK_BYTES: usize = 1024 * 1024;
+const CHUNK_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentProgress {
+  pub upload_id: String,
+  pub path: PathBuf,
+  pub sent: u64,
+  pub total: u64,
+  pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentResult {
+  pub upload_id: String,
+  pub path: PathBuf,
+  pub file_name: String,
+  pub mime: String,
+  pub size: u64,
+  /// Backend response to the final chunk (JSON, or the raw text wrapped in a string).
+  pub response: serde_json::Value,
+}
+
+fn max_bytes(settings: &crate::settings::Settings) -> u64 {
+  settings.attachment_max_bytes.unwrap_or(DEFAULT_ATTACHMENT_MAX_BYTES)
+}
+
+fn new_upload_id() -> Result<String, String> {
+  let mut bytes = [0u8; 16];
+  getrandom::fill(&mut bytes).map_err(|e| e.to_string())?;
+  Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// MIME type from the leading bytes, falling back to the file extension.
+fn detect_mime(path: &Path, head: &[u8]) -> &'static str {
+  const MAGIC: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+  ];
+  if let Some((_, mime)) = MAGIC.iter().find(|(magic, _)| head.starts_with(magic)) {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+    // docx/xlsx/pptx are zip containers; keep the specific type.
+    if *mime != "application/zip" || !matches!(ext.as_str(), "docx" | "xlsx" | "pptx") {
+      return mime;
+    }
+  }
+  if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WEBP" {
+    return "image/webp";
+  }
+  match path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase()).as_deref() {
+    Some("txt" | "log") => "text/plain",
+    Some("md") => "text/markdown",
+    Some("csv") => "text/csv",
+    Some("json") => "application/json",
+    Some("html" | "htm") => "text/html",
+    Some("py" | "rs" | "ts" | "tsx" | "js" | "jsx" | "java" | "c" | "cpp" | "h" | "go" | "sql" | "toml" | "yaml" | "yml") => {
+      "text/plain"
+    }
+    Some("docx") => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    Some("xlsx") => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    Some("pptx") => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    Some("webp") => "image/webp",
+    Some("svg") => "image/svg+xml",
+    _ => "application/octet-stream",
+  }
+}
+
+/// Percent-encode everything outside [A-Za-z0-9._-] so any file name fits in a header.
+fn encode_header_value(value: &str) -> String {
+  value
+    .bytes()
+    .map(|b| match b {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'_' | b'-' => (b as char).to_string(),
+      _ => format!("%{:02X}", b),
+    })
+    .collect()
+}
+
+/// Fill `buf` from `file` until full or EOF; returns the number of bytes read.
+async fn read_chunk(file: &mut tokio::fs::File, buf: &mut [u8]) -> std::io::Result<usize> {
+  let mut filled = 0;
+  while filled < buf.len() {
+    let n = file.read(&mut buf[filled..]).await?;
+    if n == 0 {
+      break;
+    }
+    filled += n;
+  }
+  Ok(filled)
+}
+
+async fn upload_file(app: &tauri::AppHandle, session_id: &str, path: &Path, limit: u64) -> Result<AttachmentResult, String> {
+  let file_name = path
+    .file_name()
+    .and_then(|n| n.to_str())
+    .ok_or_else(|| format!("not a file: {}", path.display()))?
+    .to_string();
+  let mut file = tokio::fs::File::open(path)
+    .await
+    .map_err(|e| format!("{}: {}", path.display(), e))?;
+  let size = file.metadata().await.map_err(|e| e.to_string())?.len();
+  if size > limit {
+    return Err(format!("{}: {} bytes exceeds the {} byte limit", file_name, size, limit));
+  }
+  let upload_id = new_upload_id()?;
+  let encoded_name = encode_header_value(&file_name);
+  let url = format!(
+    "{}/api/v1/sessions/{}/attachments/{}",
+    crate::api_base(),
+    session_id,
+    upload_id
+  );
+
+  let mut buf = vec![0u8; CHUNK_BYTES];
+  let mut sent: u64 = 0;
+  let mut mime = "application/octet-stream";
+  let response = loop {
+    let n = read_chunk(&mut file, &mut buf).await.map_err(|e| e.to_string())?;
+    if sent == 0 {
+      mime = detect_mime(path, &buf[..n]);
+    }
+    // An empty file is still sent once so the backend records it.
+    if n == 0 && sent > 0 {
+      break serde_json::Value::Null;
+    }
+    let end = (sent + n as u64).saturating_sub(1);
+    let resp = crate::profiles::client_for(&url)
+      .put(&url)
+      .timeout(std::time::Duration::from_secs(CHUNK_TIMEOUT_SECS))
+      .header("Content-Type", mime)
+      .header("Content-Range", format!("bytes {}-{}/{}", sent, end, size))
+      .header("X-File-Name", &encoded_name)
+      .body(buf[..n].to_vec())
+      .send()
+      .await
+      .map_err(|e| e.to_string())?;
+    let status = resp.status();
+    let text = resp.text().await.unwrap_or_default();
+    if !status.is_success() {
+      return Err(format!("{}: backend returned {} at byte {}: {}", file_name, status, sent, text));
+    }
+    sent += n as u64;
+    let done = sent >= size;
+    crate::chat_windows::emit_to_session(
+      app,
+      session_id,
+      ATTACHMENT_PROGRESS_EVENT,
+      AttachmentProgress {
+        upload_id: upload_id.clone(),
+        path: path.to_path_buf(),
+        sent,
+        total: size,
+        done,
+      },
+    );
+    if done {
+      break serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text));
+    }
+  };
+  crate::app_log(&format!("attachments: uploaded {} ({} bytes, {})", file_name, size, mime));
+  Ok(AttachmentResult {
+    upload_id,
+    path: path.to_path_buf(),
+    file_name,
+    mime: mime.to_string(),
+    size,
+    response,
+  })
+}
+
+/// Stream `paths` to the backend for `session_id`, one after another. Stops at the first failure.
+#[tauri::command]
+pub async fn attach_files_to_backend(
+  app: tauri::AppHandle,
+  paths: Vec<PathBuf>,
+  session_id: String,
+) -> Result<Vec<AttachmentResult>, AppError> {
+  crate::command_stats::track(&app, "attach_files_to_backend", async {
+    crate::chat_windows::validate_session_id(&session_id)?;
+    let limit = max_bytes(&app.state::<SettingsState>().get());
+    let _activity = crate::maintenance::activity(&app, "attachment_upload");
+    let mut results = Vec::with_capacity(paths.len());
+    for path in &paths {
+      results.push(upload_file(&app, &session_id, path, limit).await?);
+    }
+    Ok(results)
+  })
+  .await
+}
+
+/// Set the per-file attachment size cap in bytes (None = DEFAULT_ATTACHMENT_MAX_BYTES). Returns the effective cap.
+#[tauri::command]
+pub fn set_attachment_max_bytes(settings: tauri::State<SettingsState>, max_bytes: Option<u64>) -> Result<u64, AppError> {
+  let next = settings.update(|s| s.attachment_max_bytes = max_bytes.filter(|b| *b > 0))?;
+  Ok(self::max_bytes(&next))
+}
@@ -0,0 +1,247 @@
+// Autostart state machine driven end to end against a mock HTTP backend (ephemeral port) and a fake
+// launcher: no real process is spawned and port 8000 is never touched. Data dir is a temp dir.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Once};
+use std::time::{Duration, Instant};
+
+use crate::launcher::{BackendLauncher, BackendProcess, HttpHealthProbe, LaunchSpec};
+use crate::BackendState;
+
+fn isolate_data_dir() {
+  static ONCE: Once = Once::new();
+  ONCE.call_once(|| {
+    let dir = std::env::temp_dir().join(format!("ai-mentor-autostart-tests-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::env::set_var("LOCALAPPDATA", &dir);
+  });
+}
+
+fn free_port() -> u16 {
+  TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+/// Answer every request with 503 until `ready_after` has elapsed (None = never), then 200 {"status":"ok"}.
+fn serve(listener: TcpListener, ready_after: Option<Duration>) {
+  serve_body(listener, ready_after, r#"{"status":"ok"}"#);
+}
+
+/// Like serve, with `ready_body` as the 200 answer.
+fn serve_body(listener: TcpListener, ready_after: Option<Duration>, ready_body: &'static str) {
+  let started = Instant::now();
+  std::thread::spawn(move || {
+    for stream in listener.incoming() {
+      let Ok(mut stream) = stream else {
+        continue;
+      };
+      let mut buf = [0u8; 1024];
+      let _ = stream.read(&mut buf);
+      let ready = ready_after.is_some_and(|d| started.elapsed() >= d);
+      let (status, body) = if ready {
+        ("200 OK", ready_body)
+      } else {
+        ("503 Service Unavailable", r#"{"status":"starting"}"#)
+      };
+      let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+      );
+    }
+  });
+}
+
+enum OnLaunch {
+  /// The "backend" binds the port at launch and becomes healthy after the delay.
+  Serve(Duration),
+  /// The process exits with code 1 right away.
+  CrashOnStart,
+  /// Spawning fails (missing exe, permissions).
+  SpawnError,
+}
+
+struct FakeProcess {
+  exit_code: Option<i32>,
+  killed: Arc<AtomicBool>,
+}
+
+impl BackendProcess for FakeProcess {
+  fn id(&self) -> u32 {
+    4242
+  }
+
+  fn kill(&mut self) -> std::io::Result<()> {
+    self.killed.store(true, Ordering::SeqCst);
+    self.exit_code = Some(-1);
+    Ok(())
+  }
+
+  fn exit_code(&mut self) -> Option<i32> {
+    self.exit_code
+  }
+}
+
+struct FakeLauncher {
+  port: u16,
+  on_launch: OnLaunch,
+  launches: Arc<AtomicUsize>,
+  killed: Arc<AtomicBool>,
+}
+
+impl BackendLauncher for FakeLauncher {
+  fn launch(&self, _spec: &LaunchSpec) -> Result<Box<dyn BackendProcess>, String> {
+    self.launches.fetch_add(1, Ordering::SeqCst);
+    let exit_code = match self.on_launch {
+      OnLaunch::Serve(ready_after) => {
+        serve(TcpListener::bind(("127.0.0.1", self.port)).map_err(|e| e.to_string())?, Some(ready_after));
+        None
+      }
+      OnLaunch::CrashOnStart => Some(1),
+      OnLaunch::SpawnError => return Err("spawn failed: not found".to_string()),
+    };
+    Ok(Box::new(FakeProcess {
+      exit_code,
+      killed: self.killed.clone(),
+    }))
+  }
+}
+
+struct Harness {
+  state: Arc<BackendState>,
+  launches: Arc<AtomicUsize>,
+  killed: Arc<AtomicBool>,
+}
+
+impl Harness {
+  fn new(port: u16, on_launch: OnLaunch) -> Self {
+    isolate_data_dir();
+    let launches = Arc::new(AtomicUsize::new(0));
+    let killed = Arc::new(AtomicBool::new(false));
+    let launcher = FakeLauncher {
+      port,
+      on_launch,
+      launches: launches.clone(),
+      killed: killed.clone(),
+    };
+    let probe = HttpHealthProbe {
+      url: format!("http://127.0.0.1:{}/health", port),
+      host: "127.0.0.1".to_string(),
+      port,
+    };
+    Self {
+      state: Arc::new(BackendState::new(Box::new(launcher), Box::new(probe))),
+      launches,
+      killed,
+    }
+  }
+
+  fn next_generation(&self) -> u64 {
+    self.state.inner.lock().unwrap().supersede()
+  }
+
+  fn run_autostart(&self) {
+    let generation = self.next_generation();
+    tauri::async_runtime::block_on(crate::run_autostart_flow(
+      self.state.clone(),
+      generation,
+      PathBuf::from("fake-backend.exe"),
+      crate::settings::Settings::default(),
+    ));
+  }
+
+  fn status(&self) -> String {
+    crate::backend_status_string(&self.state)
+  }
+
+  fn has_child(&self) -> bool {
+    self.state.inner.lock().unwrap().child.is_some()
+  }
+}
+
+#[test]
+fn healthy_backend_is_reused_without_launch() {
+  let port = free_port();
+  serve(TcpListener::bind(("127.0.0.1", port)).unwrap(), Some(Duration::ZERO));
+  let h = Harness::new(port, OnLaunch::Serve(Duration::ZERO));
+  h.run_autostart();
+  assert_eq!(h.status(), "READY");
+  assert_eq!(h.launches.load(Ordering::SeqCst), 0);
+  assert!(!h.has_child());
+}
+
+#[test]
+fn healthy_backend_with_unready_component_is_degraded() {
+  let port = free_port();
+  serve_body(
+    TcpListener::bind(("127.0.0.1", port)).unwrap(),
+    Some(Duration::ZERO),
+    r#"{"status":"ok","components":{"model":"loading"}}"#,
+  );
+  let h = Harness::new(port, OnLaunch::Serve(Duration::ZERO));
+  h.run_autostart();
+  assert_eq!(h.status(), "DEGRADED:model");
+  assert_eq!(h.launches.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn slow_start_becomes_ready() {
+  let h = Harness::new(free_port(), OnLaunch::Serve(Duration::from_millis(1_200)));
+  h.run_autostart();
+  assert_eq!(h.status(), "READY");
+  assert_eq!(h.launches.load(Ordering::SeqCst), 1);
+  assert!(h.has_child());
+  let history = h.state.inner.lock().unwrap().restart_history.clone();
+  assert_eq!(history.len(), 1);
+  assert!(history[0].ok);
+}
+
+#[test]
+fn crash_on_start_is_reported_before_timeout() {
+  let h = Harness::new(free_port(), OnLaunch::CrashOnStart);
+  let started = Instant::now();
+  h.run_autostart();
+  assert_eq!(h.status(), format!("NOT_READY:{}", crate::NOT_READY_REASON_EXITED));
+  assert!(started.elapsed() < Duration::from_millis(crate::HEALTH_TIMEOUT_MS / 2));
+  assert!(!h.has_child());
+}
+
+#[test]
+fn occupied_port_without_health_is_not_spawned_over() {
+  let port = free_port();
+  serve(TcpListener::bind(("127.0.0.1", port)).unwrap(), None);
+  let h = Harness::new(port, OnLaunch::Serve(Duration::ZERO));
+  h.run_autostart();
+  assert_eq!(h.status(), format!("NOT_READY:{}", crate::NOT_READY_REASON_PORT_IN_USE));
+  assert_eq!(h.launches.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn spawn_error_leaves_not_ready() {
+  let h = Harness::new(free_port(), OnLaunch::SpawnError);
+  h.run_autostart();
+  assert_eq!(h.status(), "NOT_READY");
+  assert_eq!(h.launches.load(Ordering::SeqCst), 1);
+  assert!(!h.has_child());
+}
+
+#[test]
+fn superseded_attempt_kills_its_child_and_keeps_state() {
+  let h = Harness::new(free_port(), OnLaunch::CrashOnStart);
+  let stale = h.next_generation();
+  h.next_generation();
+  tauri::async_runtime::block_on(crate::try_spawn_and_health(
+    h.state.clone(),
+    stale,
+    PathBuf::from("fake-backend.exe"),
+    PathBuf::from("child.log"),
+    crate::settings::Settings::default(),
+  ));
+  assert!(h.killed.load(Ordering::SeqCst));
+  assert_eq!(h.status(), "NOT_READY");
+  assert!(!h.has_child());
+}
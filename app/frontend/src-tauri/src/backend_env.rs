@@ -0,0 +1,92 @@
+// Extra environment for the spawned backend from settings.backend_env (data dir, model path, log
+// level, auth token, ...). Applied after the app's own defaults, so it can override e.g.
+// AI_MENTOR_MODELS_DIR; --log-level and keyring secrets (see secrets) still win. The port variables
+// the health check depends on cannot be set here.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error::AppError;
+use crate::settings::{Settings, SettingsState};
+
+const ENV_VALUE_MAX_LEN: usize = 4096;
+/// Name fragments whose values are masked in backend_autostart.log.
+const SENSITIVE_NAME_PARTS: &[&str] = &["TOKEN", "SECRET", "PASSWORD", "PASSWD", "KEY", "AUTH", "CREDENTIAL"];
+const MASK: &str = "***";
+
+/// Set by the app itself (backend port, shutdown token, companion ports). Case-insensitive, as
+/// Windows environment names are.
+fn reserved(var: &str) -> bool {
+  let upper = var.to_ascii_uppercase();
+  upper == "AI_MENTOR_PORT"
+    || upper == crate::BACKEND_SHUTDOWN_TOKEN_VAR
+    || crate::services::companion_env().iter().any(|(v, _)| v.to_ascii_uppercase() == upper)
+}
+
+fn validate_var(var: &str, value: &str) -> Result<(), AppError> {
+  crate::secrets::validate_env_var(var)?;
+  if reserved(var) {
+    return Err(AppError::InvalidInput(format!("{} is set by the app and cannot be overridden", var)));
+  }
+  if value.len() > ENV_VALUE_MAX_LEN || value.contains('\0') {
+    return Err(AppError::InvalidInput(format!(
+      "{}: value must be at most {} bytes without NUL characters",
+      var, ENV_VALUE_MAX_LEN
+    )));
+  }
+  Ok(())
+}
+
+/// Configured pairs, skipping (and logging) anything that does not validate, e.g. from a
+/// hand-edited settings.json.
+pub(crate) fn configured(settings: &Settings) -> Vec<(String, String)> {
+  let mut vars: Vec<(String, String)> = settings.backend_env.clone().into_iter().collect();
+  vars.retain(|(var, value)| match validate_var(var, value) {
+    Ok(()) => true,
+    Err(e) => {
+      crate::backend_autostart_log(&format!("backend env: skipping {}: {}", var, e.message()));
+      false
+    }
+  });
+  vars.sort();
+  vars
+}
+
+fn sensitive(var: &str) -> bool {
+  let upper = var.to_ascii_uppercase();
+  SENSITIVE_NAME_PARTS.iter().any(|part| upper.contains(part))
+}
+
+/// Effective environment (last value per variable wins, as for Command::envs) as one log line, with
+/// `secret_vars` and sensitive-looking names masked.
+pub(crate) fn describe(env: &[(String, String)], secret_vars: &[String]) -> String {
+  let effective: BTreeMap<&str, &str> = env.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+  effective
+    .into_iter()
+    .map(|(var, value)| {
+      if sensitive(var) || secret_vars.iter().any(|s| s == var) {
+        format!("{}={}", var, MASK)
+      } else {
+        format!("{}={}", var, value)
+      }
+    })
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+#[tauri::command]
+pub fn get_backend_env(settings: tauri::State<SettingsState>) -> HashMap<String, String> {
+  settings.get().backend_env
+}
+
+/// Replace the extra backend environment. Takes effect the next time the backend is spawned.
+#[tauri::command]
+pub fn set_backend_env(settings: tauri::State<SettingsState>, env: HashMap<String, String>) -> Result<(), AppError> {
+  for (var, value) in &env {
+    validate_var(var, value)?;
+  }
+  let mut names: Vec<&str> = env.keys().map(String::as_str).collect();
+  names.sort();
+  crate::app_log(&format!("backend env: set {}", names.join(", ")));
+  settings.update(|s| s.backend_env = env.clone())?;
+  Ok(())
+}
@@ -0,0 +1,164 @@
+// Windows Scheduled Task (AI_Mentor_Backend) management: create, delete and query the per-user
+// logon task that runs the backend exe with stdout/stderr redirected to backend_task.log.
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use serde::Serialize;
+
+use crate::error::AppError;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+pub(crate) const BACKEND_TASK_NAME: &str = "AI_Mentor_Backend";
+const BACKEND_TASK_LOG_NAME: &str = "backend_task.log";
+
+/// schtasks /Query /FO CSV /V column positions (stable across locales, unlike the headers).
+const CSV_COL_STATUS: usize = 3;
+const CSV_COL_LAST_RUN_TIME: usize = 5;
+const CSV_COL_LAST_RESULT: usize = 6;
+const CSV_COL_TASK_TO_RUN: usize = 8;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendTaskStatus {
+  pub installed: bool,
+  pub status: Option<String>,
+  pub last_run_time: Option<String>,
+  pub last_result: Option<String>,
+  pub task_to_run: Option<String>,
+}
+
+impl BackendTaskStatus {
+  fn not_installed() -> Self {
+    Self {
+      installed: false,
+      status: None,
+      last_run_time: None,
+      last_result: None,
+      task_to_run: None,
+    }
+  }
+}
+
+fn backend_task_log_path() -> std::path::PathBuf {
+  crate::logs_dir().join(BACKEND_TASK_LOG_NAME)
+}
+
+/// Splits one CSV line as emitted by schtasks (every field double-quoted, "" for a literal quote).
+fn split_csv_line(line: &str) -> Vec<String> {
+  let mut fields = Vec::new();
+  let mut cur = String::new();
+  let mut in_quotes = false;
+  let mut chars = line.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' if in_quotes && chars.peek() == Some(&'"') => {
+        cur.push('"');
+        chars.next();
+      }
+      '"' => in_quotes = !in_quotes,
+      ',' if !in_quotes => fields.push(std::mem::take(&mut cur)),
+      _ => cur.push(c),
+    }
+  }
+  fields.push(cur);
+  fields
+}
+
+/// Parses `schtasks /Query /TN <name> /FO CSV /V /NH` output (first non-empty line).
+fn parse_query_csv(output: &str) -> Option<BackendTaskStatus> {
+  let line = output.lines().find(|l| !l.trim().is_empty())?;
+  let fields = split_csv_line(line.trim());
+  let get = |i: usize| fields.get(i).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+  Some(BackendTaskStatus {
+    installed: true,
+    status: get(CSV_COL_STATUS),
+    last_run_time: get(CSV_COL_LAST_RUN_TIME),
+    last_result: get(CSV_COL_LAST_RESULT),
+    task_to_run: get(CSV_COL_TASK_TO_RUN),
+  })
+}
+
+/// Task action: cmd.exe /c ""<exe>" >> "<log>" 2>&1" so the backend output lands in our logs dir.
+fn task_run_command(exe_path: &std::path::Path, log_path: &std::path::Path) -> String {
+  let system_root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+  format!(
+    "\"{}\\System32\\cmd.exe\" /c \"\"{}\" >> \"{}\" 2>&1\"",
+    system_root,
+    exe_path.display(),
+    log_path.display()
+  )
+}
+
+#[cfg(windows)]
+fn run_schtasks(args: &[&str]) -> Result<std::process::Output, String> {
+  std::process::Command::new("schtasks")
+    .args(args)
+    .creation_flags(crate::CREATE_NO_WINDOW)
+    .output()
+    .map_err(|e| e.to_string())
+}
+
+/// Create (or replace) the AI_Mentor_Backend logon task pointing at the bundled backend exe.
+/// Set `elevate` to allow a UAC prompt if the per-user create is denied by policy.
+#[tauri::command]
+pub fn install_backend_task(app: tauri::AppHandle, elevate: Option<bool>) -> Result<BackendTaskStatus, AppError> {
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = (app, elevate);
+    Err(AppError::Unsupported("Windows only".to_string()))
+  }
+  #[cfg(target_os = "windows")]
+  {
+    let exe_path = crate::backend_exe_path(&app)?;
+    if !exe_path.is_file() {
+      return Err(AppError::ResourceNotFound(format!("backend exe not found: {}", exe_path.display())));
+    }
+    let log_path = backend_task_log_path();
+    if let Some(parent) = log_path.parent() {
+      let _ = std::fs::create_dir_all(parent);
+    }
+    let tr = task_run_command(&exe_path, &log_path);
+    crate::elevate::run_checked(
+      "schtasks",
+      &["/Create", "/F", "/SC", "ONLOGON", "/RL", "LIMITED", "/TN", BACKEND_TASK_NAME, "/TR", &tr],
+      elevate.unwrap_or(false),
+    )?;
+    crate::app_log(&format!("backend task: installed tr={}", tr));
+    query_backend_task_status()
+  }
+}
+
+/// End and delete the AI_Mentor_Backend task. Succeeds if the task does not exist.
+#[tauri::command]
+pub fn uninstall_backend_task(elevate: Option<bool>) -> Result<(), AppError> {
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = elevate;
+    Err(AppError::Unsupported("Windows only".to_string()))
+  }
+  #[cfg(target_os = "windows")]
+  {
+    if !query_backend_task_status()?.installed {
+      return Ok(());
+    }
+    let _ = run_schtasks(&["/End", "/TN", BACKEND_TASK_NAME]);
+    crate::elevate::run_checked("schtasks", &["/Delete", "/F", "/TN", BACKEND_TASK_NAME], elevate.unwrap_or(false))?;
+    crate::app_log("backend task: uninstalled");
+    Ok(())
+  }
+}
+
+/// Query the AI_Mentor_Backend task. `installed` is false when the task does not exist.
+#[tauri::command]
+pub fn query_backend_task_status() -> Result<BackendTaskStatus, AppError> {
+  #[cfg(not(target_os = "windows"))]
+  return Err(AppError::Unsupported("Windows only".to_string()));
+  #[cfg(target_os = "windows")]
+  {
+    let out = run_schtasks(&["/Query", "/TN", BACKEND_TASK_NAME, "/FO", "CSV", "/V", "/NH"])?;
+    if !out.status.success() {
+      return Ok(BackendTaskStatus::not_installed());
+    }
+    Ok(parse_query_csv(&String::from_utf8_lossy(&out.stdout)).unwrap_or_else(BackendTaskStatus::not_installed))
+  }
+}
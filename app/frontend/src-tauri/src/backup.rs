@@ -0,0 +1,427 @@
+// Backup and restore of the mentor's data: the backend's data dir (<AI_MENTOR_BASE_DIR>/data, holding
+// ai_mentor.sqlite and the embeddings) plus settings and data_version.json, in a zip with a backup.json
+// manifest. A data dir without the database is refused rather than archived. The backend this app owns is
+// stopped first so the database is consistent, and the autostart flow runs again afterwards whatever
+// the outcome. Restore extracts to a staging dir and only then swaps it in; the replaced data dir is
+// kept as data.before-restore. Progress is emitted as `backend-data-progress`.
+
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{Emitter, Manager};
+
+use crate::BackendState;
+use crate::error::AppError;
+use crate::settings::SettingsState;
+
+pub const BACKEND_DATA_PROGRESS_EVENT: &str = "backend-data-progress";
+const DATA_DIR_NAME: &str = "data";
+const PREVIOUS_DATA_DIR_NAME: &str = "data.before-restore";
+const STAGING_DIR_NAME: &str = "restore-staging";
+const MANIFEST_NAME: &str = "backup.json";
+/// The backend's database (backend/core/config.py), required in every backup.
+const DATABASE_FILE_NAME: &str = "ai_mentor.sqlite";
+/// Files directly under the data dir that belong in a backup.
+const ROOT_FILES: &[&str] = &["settings.json", "settings.db", "data_version.json"];
+const STOP_TIMEOUT_MS: u64 = 5_000;
+const PROGRESS_INTERVAL_MS: u64 = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataProgress {
+  /// "backup" | "restore"
+  pub operation: &'static str,
+  /// "stopping" | "copying" | "restarting" | "done"
+  pub stage: &'static str,
+  pub done_bytes: u64,
+  pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataTransferResult {
+  pub path: PathBuf,
+  pub files: u64,
+  pub bytes: u64,
+  /// Backend status after the restart.
+  pub backend_status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+  app_version: String,
+  data_version: u32,
+  created_at: u64,
+  files: u64,
+  bytes: u64,
+}
+
+struct Progress {
+  app: tauri::AppHandle,
+  operation: &'static str,
+  total_bytes: u64,
+  last: Instant,
+}
+
+impl Progress {
+  fn new(app: &tauri::AppHandle, operation: &'static str) -> Self {
+    Self {
+      app: app.clone(),
+      operation,
+      total_bytes: 0,
+      last: Instant::now(),
+    }
+  }
+
+  fn stage(&self, stage: &'static str, done_bytes: u64) {
+    let _ = self.app.emit(
+      BACKEND_DATA_PROGRESS_EVENT,
+      DataProgress {
+        operation: self.operation,
+        stage,
+        done_bytes,
+        total_bytes: self.total_bytes,
+      },
+    );
+  }
+
+  /// Throttled "copying" update.
+  fn copied(&mut self, done_bytes: u64) {
+    if self.last.elapsed() >= Duration::from_millis(PROGRESS_INTERVAL_MS) || done_bytes >= self.total_bytes {
+      self.last = Instant::now();
+      self.stage("copying", done_bytes);
+    }
+  }
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0)
+}
+
+/// Files under `dir`, recursively, with their sizes.
+fn walk(dir: &Path, out: &mut Vec<(PathBuf, u64)>) -> std::io::Result<()> {
+  for entry in std::fs::read_dir(dir)? {
+    let entry = entry?;
+    let meta = entry.metadata()?;
+    if meta.is_dir() {
+      walk(&entry.path(), out)?;
+    } else if meta.is_file() {
+      out.push((entry.path(), meta.len()));
+    }
+  }
+  Ok(())
+}
+
+/// Zip entry name for `path` under the data dir, always with '/' separators.
+fn entry_name(data_dir: &Path, path: &Path) -> Option<String> {
+  let rel = path.strip_prefix(data_dir).ok()?;
+  let parts: Vec<String> = rel.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+  Some(format!("{}/{}", DATA_DIR_NAME, parts.join("/")))
+}
+
+/// Base dir of the backend this app launches: AI_MENTOR_BASE_DIR as passed to it, which
+/// settings.backend_env may override. A DATABASE_URL override puts the database where this cannot
+/// reach it, so that is refused.
+fn backend_base_dir(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+  let overrides = crate::backend_env::configured(&app.state::<SettingsState>().get());
+  if overrides.iter().any(|(var, _)| var == "DATABASE_URL") {
+    return Err(AppError::Unsupported(
+      "DATABASE_URL is set in the backend environment; back up that database separately".to_string(),
+    ));
+  }
+  Ok(overrides
+    .into_iter()
+    .find(|(var, _)| var == "AI_MENTOR_BASE_DIR")
+    .map(|(_, dir)| PathBuf::from(dir))
+    .unwrap_or_else(crate::app_base_dir))
+}
+
+/// Backed-up entries may only be data/... or one of ROOT_FILES.
+fn allowed_entry(name: &Path) -> bool {
+  let mut parts = name.components();
+  match parts.next().and_then(|c| c.as_os_str().to_str()) {
+    Some(DATA_DIR_NAME) => parts.next().is_some(),
+    Some(first) => parts.next().is_none() && ROOT_FILES.contains(&first),
+    None => false,
+  }
+}
+
+/// Zip the backend's data dir under `backend_base` and the ROOT_FILES under `base` into `dest`.
+fn write_archive(base: &Path, backend_base: &Path, dest: &Path, progress: &mut Progress) -> Result<(u64, u64), String> {
+  let data_dir = backend_base.join(DATA_DIR_NAME);
+  let database = data_dir.join(DATABASE_FILE_NAME);
+  if !database.is_file() {
+    return Err(format!("{} not found; nothing to back up", database.display()));
+  }
+  let mut found = Vec::new();
+  walk(&data_dir, &mut found).map_err(|e| e.to_string())?;
+  let mut files = Vec::with_capacity(found.len() + ROOT_FILES.len());
+  for (path, len) in found {
+    let name = entry_name(&data_dir, &path).ok_or_else(|| format!("outside data dir: {}", path.display()))?;
+    files.push((path, name, len));
+  }
+  for name in ROOT_FILES {
+    let path = base.join(name);
+    if let Ok(meta) = std::fs::metadata(&path) {
+      files.push((path, name.to_string(), meta.len()));
+    }
+  }
+  progress.total_bytes = files.iter().map(|(_, _, len)| len).sum();
+
+  if let Some(parent) = dest.parent() {
+    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+  }
+  let file = std::fs::File::create(dest).map_err(|e| format!("{}: {}", dest.display(), e))?;
+  let mut zip = zip::ZipWriter::new(file);
+  let options = zip::write::SimpleFileOptions::default()
+    .compression_method(zip::CompressionMethod::Deflated)
+    .large_file(true);
+  let mut done = 0u64;
+  let mut buf = vec![0u8; 64 * 1024];
+  for (path, name, _) in &files {
+    zip.start_file(name.as_str(), options).map_err(|e| e.to_string())?;
+    let mut src = std::fs::File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    loop {
+      let n = src.read(&mut buf).map_err(|e| e.to_string())?;
+      if n == 0 {
+        break;
+      }
+      zip.write_all(&buf[..n]).map_err(|e| e.to_string())?;
+      done += n as u64;
+      progress.copied(done);
+    }
+  }
+  let manifest = Manifest {
+    app_version: env!("CARGO_PKG_VERSION").to_string(),
+    data_version: crate::migrations::current_version(),
+    created_at: now_secs(),
+    files: files.len() as u64,
+    bytes: done,
+  };
+  zip.start_file(MANIFEST_NAME, options).map_err(|e| e.to_string())?;
+  let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+  zip.write_all(&json).map_err(|e| e.to_string())?;
+  zip.finish().map_err(|e| e.to_string())?;
+  Ok((manifest.files, manifest.bytes))
+}
+
+/// Validate `src` and extract it into `staging`. Nothing outside `staging` is touched.
+fn extract_archive(src: &Path, staging: &Path, progress: &mut Progress) -> Result<(u64, u64), String> {
+  let file = std::fs::File::open(src).map_err(|e| format!("{}: {}", src.display(), e))?;
+  let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+  let manifest: Manifest = {
+    let mut entry = zip
+      .by_name(MANIFEST_NAME)
+      .map_err(|_| "not an AI Mentor backup (backup.json missing)".to_string())?;
+    let mut text = String::new();
+    entry.read_to_string(&mut text).map_err(|e| e.to_string())?;
+    serde_json::from_str(&text).map_err(|e| format!("invalid backup.json: {}", e))?
+  };
+  if manifest.data_version > crate::migrations::current_version() {
+    return Err(format!(
+      "backup is from a newer data format ({}) than this app supports ({})",
+      manifest.data_version,
+      crate::migrations::current_version()
+    ));
+  }
+
+  let mut names = Vec::with_capacity(zip.len());
+  for i in 0..zip.len() {
+    let entry = zip.by_index(i).map_err(|e| e.to_string())?;
+    if entry.is_dir() || entry.name() == MANIFEST_NAME {
+      continue;
+    }
+    let name = entry
+      .enclosed_name()
+      .filter(|n| allowed_entry(n))
+      .ok_or_else(|| format!("unexpected entry in backup: {}", entry.name()))?;
+    progress.total_bytes += entry.size();
+    names.push((i, name));
+  }
+  let database = Path::new(DATA_DIR_NAME).join(DATABASE_FILE_NAME);
+  if !names.iter().any(|(_, name)| *name == database) {
+    return Err(format!("backup does not contain {}/{}", DATA_DIR_NAME, DATABASE_FILE_NAME));
+  }
+
+  let _ = std::fs::remove_dir_all(staging);
+  std::fs::create_dir_all(staging).map_err(|e| e.to_string())?;
+  let mut done = 0u64;
+  for (i, name) in &names {
+    let mut entry = zip.by_index(*i).map_err(|e| e.to_string())?;
+    let out_path = staging.join(name);
+    if let Some(parent) = out_path.parent() {
+      std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut out = std::fs::File::create(&out_path).map_err(|e| e.to_string())?;
+    done += std::io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+    progress.copied(done);
+  }
+  Ok((names.len() as u64, done))
+}
+
+/// Move the staged data dir into `backend_base` and the root files into `base`. The old data dir
+/// becomes data.before-restore.
+fn swap_in(base: &Path, backend_base: &Path, staging: &Path) -> Result<(), String> {
+  let data = backend_base.join(DATA_DIR_NAME);
+  let staged_data = staging.join(DATA_DIR_NAME);
+  if staged_data.is_dir() {
+    let previous = backend_base.join(PREVIOUS_DATA_DIR_NAME);
+    let _ = std::fs::remove_dir_all(&previous);
+    if data.exists() {
+      std::fs::rename(&data, &previous).map_err(|e| format!("could not move current data aside: {}", e))?;
+    }
+    if let Err(e) = std::fs::rename(&staged_data, &data) {
+      let _ = std::fs::rename(&previous, &data);
+      return Err(format!("could not move restored data into place: {}", e));
+    }
+  }
+  for name in ROOT_FILES {
+    let staged = staging.join(name);
+    if staged.is_file() {
+      std::fs::copy(&staged, base.join(name)).map_err(|e| format!("{}: {}", name, e))?;
+    }
+  }
+  let _ = std::fs::remove_dir_all(staging);
+  Ok(())
+}
+
+/// Stop the owned backend and wait for its port to close. Service mode is not handled here.
+async fn stop_for_data(app: &tauri::AppHandle, state: &BackendState) -> Result<(), AppError> {
+  if crate::service_mode::is_enabled(app) {
+    return Err(AppError::Unsupported(
+      "backend runs as a service; stop the service before backup/restore".to_string(),
+    ));
+  }
+  crate::stop_owned_backend(state)?;
+  let deadline = Instant::now() + Duration::from_millis(STOP_TIMEOUT_MS);
+  while state.probe.port_in_use() {
+    if Instant::now() >= deadline {
+      return Err(AppError::PortInUse(
+        "backend port is still in use (backend not started by this app?)".to_string(),
+      ));
+    }
+    tokio::time::sleep(crate::health::config().poll()).await;
+  }
+  Ok(())
+}
+
+/// The user stopped the backend; backup/restore must leave it stopped afterwards.
+fn stopped_by_user(state: &BackendState) -> bool {
+  state.inner.lock().is_ok_and(|g| g.status == "STOPPED")
+}
+
+/// Restart after a backup/restore attempt (or put a user-stopped backend back to STOPPED) and fold
+/// the outcome into the result.
+async fn finish(
+  app: &tauri::AppHandle,
+  state: &Arc<BackendState>,
+  progress: &Progress,
+  was_stopped: bool,
+  outcome: Result<(PathBuf, u64, u64), AppError>,
+) -> Result<DataTransferResult, AppError> {
+  let restarted = if was_stopped {
+    state.inner.lock().map_err(|e| AppError::from(e.to_string())).map(|mut g| {
+      g.set_status("STOPPED", None);
+      g.status.clone()
+    })
+  } else {
+    progress.stage("restarting", 0);
+    crate::rerun_autostart(app, state).await
+  };
+  progress.stage("done", progress.total_bytes);
+  let (path, files, bytes) = outcome?;
+  Ok(DataTransferResult {
+    path,
+    files,
+    bytes,
+    backend_status: restarted.unwrap_or_else(|e| format!("restart failed: {}", e)),
+  })
+}
+
+/// Run `work` with the progress reporter on a blocking thread and hand both back.
+async fn blocking<T, F>(progress: Progress, work: F) -> (Result<T, String>, Progress)
+where
+  T: Send + 'static,
+  F: FnOnce(&mut Progress) -> Result<T, String> + Send + 'static,
+{
+  let fallback = Progress::new(&progress.app, progress.operation);
+  let joined = tauri::async_runtime::spawn_blocking(move || {
+    let mut progress = progress;
+    (work(&mut progress), progress)
+  })
+  .await;
+  joined.unwrap_or_else(|e| (Err(e.to_string()), fallback))
+}
+
+/// Stop the backend, zip its data (plus settings) to `dest_zip`, then start it again.
+#[tauri::command]
+pub async fn backup_backend_data(app: tauri::AppHandle, dest_zip: String) -> Result<DataTransferResult, AppError> {
+  crate::command_stats::track(&app, "backup_backend_data", async {
+    let state = app.state::<Arc<BackendState>>().inner().clone();
+    let dest = PathBuf::from(&dest_zip);
+    let backend_base = backend_base_dir(&app)?;
+    let progress = Progress::new(&app, "backup");
+    progress.stage("stopping", 0);
+    let was_stopped = stopped_by_user(&state);
+    if let Err(e) = stop_for_data(&app, &state).await {
+      return finish(&app, &state, &progress, was_stopped, Err(e)).await;
+    }
+    let base = crate::app_base_dir();
+    let out = dest.clone();
+    let (archived, progress) = blocking(progress, move |p| {
+      let res = write_archive(&base, &backend_base, &out, p);
+      if res.is_err() {
+        let _ = std::fs::remove_file(&out);
+      }
+      res
+    })
+    .await;
+    let outcome = archived.map_err(AppError::Io).map(|(files, bytes)| {
+      crate::app_log(&format!("backup: {} files, {} bytes -> {}", files, bytes, dest.display()));
+      (dest.clone(), files, bytes)
+    });
+    finish(&app, &state, &progress, was_stopped, outcome).await
+  })
+  .await
+}
+
+/// Stop the backend, replace its data (and settings) with the contents of `src_zip`, then start it again.
+#[tauri::command]
+pub async fn restore_backend_data(app: tauri::AppHandle, src_zip: String) -> Result<DataTransferResult, AppError> {
+  crate::command_stats::track(&app, "restore_backend_data", async {
+    let state = app.state::<Arc<BackendState>>().inner().clone();
+    let src = PathBuf::from(&src_zip);
+    let backend_base = backend_base_dir(&app)?;
+    let progress = Progress::new(&app, "restore");
+    progress.stage("stopping", 0);
+    let was_stopped = stopped_by_user(&state);
+    if let Err(e) = stop_for_data(&app, &state).await {
+      return finish(&app, &state, &progress, was_stopped, Err(e)).await;
+    }
+    let base = crate::app_base_dir();
+    let archive = src.clone();
+    let (restored, progress) = blocking(progress, move |p| {
+      let staging = backend_base.join(STAGING_DIR_NAME);
+      let res = extract_archive(&archive, &staging, p).and_then(|r| swap_in(&base, &backend_base, &staging).map(|_| r));
+      if res.is_err() {
+        let _ = std::fs::remove_dir_all(&staging);
+      }
+      res
+    })
+    .await;
+    let outcome = restored.map_err(AppError::Io).map(|(files, bytes)| {
+      // Restored settings replace the in-memory copy so later saves do not write the old ones back.
+      if let Ok(mut s) = app.state::<SettingsState>().inner.lock() {
+        *s = crate::settings::load();
+      }
+      crate::app_log(&format!("restore: {} files, {} bytes from {}", files, bytes, src.display()));
+      (src.clone(), files, bytes)
+    });
+    finish(&app, &state, &progress, was_stopped, outcome).await
+  })
+  .await
+}
@@ -0,0 +1,175 @@
+// Screen and clipboard capture for mentor context. Images are written as PNG under
+// <base>/captures (the newest MAX_CAPTURES are kept) and the path is returned for the frontend to
+// attach or upload. Screen capture uses GDI and is Windows only; clipboard works everywhere.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::error::AppError;
+
+const CAPTURES_DIR_NAME: &str = "captures";
+const MAX_CAPTURES: usize = 20;
+
+/// Rectangle in physical pixels of the virtual screen (all monitors).
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub struct Region {
+  pub x: i32,
+  pub y: i32,
+  pub width: u32,
+  pub height: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturedImage {
+  pub path: PathBuf,
+  pub width: u32,
+  pub height: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipboardContext {
+  pub text: Option<String>,
+  pub image: Option<CapturedImage>,
+}
+
+pub fn captures_dir() -> PathBuf {
+  crate::app_base_dir().join(CAPTURES_DIR_NAME)
+}
+
+/// Delete all but the newest MAX_CAPTURES images.
+fn prune_captures(dir: &Path) {
+  let Ok(entries) = std::fs::read_dir(dir) else {
+    return;
+  };
+  let mut files: Vec<(SystemTime, PathBuf)> = entries
+    .filter_map(|e| e.ok())
+    .filter(|e| e.path().extension().is_some_and(|x| x == "png"))
+    .map(|e| (e.metadata().and_then(|m| m.modified()).unwrap_or(UNIX_EPOCH), e.path()))
+    .collect();
+  files.sort();
+  let excess = files.len().saturating_sub(MAX_CAPTURES);
+  for (_, old) in &files[..excess] {
+    let _ = std::fs::remove_file(old);
+  }
+}
+
+/// Encode RGBA pixels as <captures>/<prefix>-<millis>.png.
+fn save_png(prefix: &str, width: u32, height: u32, rgba: &[u8]) -> Result<CapturedImage, String> {
+  let dir = captures_dir();
+  std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+  let millis = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis())
+    .unwrap_or(0);
+  let path = dir.join(format!("{}-{}.png", prefix, millis));
+  let file = std::fs::File::create(&path).map_err(|e| e.to_string())?;
+  let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+  encoder.set_color(png::ColorType::Rgba);
+  encoder.set_depth(png::BitDepth::Eight);
+  let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+  writer.write_image_data(rgba).map_err(|e| e.to_string())?;
+  writer.finish().map_err(|e| e.to_string())?;
+  prune_captures(&dir);
+  Ok(CapturedImage { path, width, height })
+}
+
+/// Copy `region` (default: the whole virtual screen) off the screen DC. Returns RGBA pixels.
+#[cfg(target_os = "windows")]
+fn grab_screen(region: Option<Region>) -> Result<(u32, u32, Vec<u8>), String> {
+  use windows_sys::Win32::Graphics::Gdi::{
+    BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits, ReleaseDC,
+    SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, CAPTUREBLT, DIB_RGB_COLORS, SRCCOPY,
+  };
+  use windows_sys::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+  };
+
+  let (x, y, width, height) = match region {
+    Some(r) => (r.x, r.y, r.width as i32, r.height as i32),
+    None => unsafe {
+      (
+        GetSystemMetrics(SM_XVIRTUALSCREEN),
+        GetSystemMetrics(SM_YVIRTUALSCREEN),
+        GetSystemMetrics(SM_CXVIRTUALSCREEN),
+        GetSystemMetrics(SM_CYVIRTUALSCREEN),
+      )
+    },
+  };
+  if width <= 0 || height <= 0 {
+    return Err("empty capture region".to_string());
+  }
+
+  let mut pixels = vec![0u8; width as usize * height as usize * 4];
+  // SAFETY: every handle created here is checked and released before returning; the DIB buffer is
+  // sized for width * height 32-bit pixels, matching the header passed to GetDIBits.
+  let copied = unsafe {
+    let screen = GetDC(std::ptr::null_mut());
+    if screen.is_null() {
+      return Err("GetDC failed".to_string());
+    }
+    let mem = CreateCompatibleDC(screen);
+    let bitmap = CreateCompatibleBitmap(screen, width, height);
+    let previous = SelectObject(mem, bitmap);
+    let blitted = BitBlt(mem, 0, 0, width, height, screen, x, y, SRCCOPY | CAPTUREBLT) != 0;
+    SelectObject(mem, previous);
+
+    let mut info: BITMAPINFO = std::mem::zeroed();
+    info.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+    info.bmiHeader.biWidth = width;
+    info.bmiHeader.biHeight = -height; // top-down rows
+    info.bmiHeader.biPlanes = 1;
+    info.bmiHeader.biBitCount = 32;
+    info.bmiHeader.biCompression = BI_RGB;
+    let lines = if blitted {
+      GetDIBits(mem, bitmap, 0, height as u32, pixels.as_mut_ptr().cast(), &mut info, DIB_RGB_COLORS)
+    } else {
+      0
+    };
+
+    DeleteObject(bitmap);
+    DeleteDC(mem);
+    ReleaseDC(std::ptr::null_mut(), screen);
+    lines == height
+  };
+  if !copied {
+    return Err("screen copy failed".to_string());
+  }
+  // GDI gives BGRX; PNG wants RGBA.
+  for px in pixels.chunks_exact_mut(4) {
+    px.swap(0, 2);
+    px[3] = 255;
+  }
+  Ok((width as u32, height as u32, pixels))
+}
+
+/// Capture the screen (or `region`, physical pixels) to a PNG under the captures dir (Windows only).
+#[tauri::command]
+pub fn capture_screenshot(region: Option<Region>) -> Result<CapturedImage, AppError> {
+  #[cfg(target_os = "windows")]
+  {
+    let (width, height, rgba) = grab_screen(region)?;
+    let image = save_png("screen", width, height, &rgba)?;
+    crate::app_log(&format!("capture: screenshot {}x{} -> {}", width, height, image.path.display()));
+    Ok(image)
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let _ = region;
+    Err(AppError::Unsupported("Windows only".to_string()))
+  }
+}
+
+/// Current clipboard text and/or image; an image is saved like a screenshot. Empty clipboard -> both None.
+#[tauri::command]
+pub fn read_clipboard_context(app: tauri::AppHandle) -> Result<ClipboardContext, AppError> {
+  let clipboard = app.clipboard();
+  let text = clipboard.read_text().ok().filter(|t| !t.trim().is_empty());
+  let image = match clipboard.read_image() {
+    Ok(img) => Some(save_png("clipboard", img.width(), img.height(), img.rgba())?),
+    Err(_) => None,
+  };
+  Ok(ClipboardContext { text, image })
+}
@@ -0,0 +1,129 @@
+// Additional chat windows, one per session (label chat-<session_id>), so a mentor chat can sit next to
+// the editor while the dashboard stays open. The page gets `window.__CHAT_SESSION__` and renders only
+// that conversation. Backend status is broadcast to every window; session-scoped events go through
+// emit_to_session, which targets the session's window and falls back to the main window.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::error::AppError;
+
+pub const CHAT_WINDOW_PREFIX: &str = "chat-";
+const MAX_SESSION_ID_LEN: usize = 64;
+
+/// Open chat windows: label -> session id.
+#[derive(Default)]
+pub struct ChatWindowsState {
+  windows: Mutex<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatWindowInfo {
+  pub label: String,
+  pub session_id: String,
+  pub focused: bool,
+}
+
+/// Session ids end up in a window label and a script; keep them to [A-Za-z0-9_-].
+pub(crate) fn validate_session_id(session_id: &str) -> Result<(), AppError> {
+  let valid = !session_id.is_empty()
+    && session_id.len() <= MAX_SESSION_ID_LEN
+    && session_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+  if valid {
+    Ok(())
+  } else {
+    Err(AppError::InvalidInput(format!("invalid session id: {:?}", session_id)))
+  }
+}
+
+fn label_for(session_id: &str) -> String {
+  format!("{}{}", CHAT_WINDOW_PREFIX, session_id)
+}
+
+/// Window close: forget the session (called for every destroyed window).
+pub(crate) fn on_destroyed(app: &tauri::AppHandle, label: &str) {
+  if !label.starts_with(CHAT_WINDOW_PREFIX) {
+    return;
+  }
+  if let Ok(mut windows) = app.state::<ChatWindowsState>().windows.lock() {
+    windows.remove(label);
+  }
+}
+
+/// The open chat window for `session_id`, if any.
+pub(crate) fn window_for_session(app: &tauri::AppHandle, session_id: &str) -> Option<tauri::WebviewWindow> {
+  let label = label_for(session_id);
+  let tracked = app
+    .state::<ChatWindowsState>()
+    .windows
+    .lock()
+    .map(|w| w.contains_key(&label))
+    .unwrap_or(false);
+  if tracked {
+    app.get_webview_window(&label)
+  } else {
+    None
+  }
+}
+
+/// Emit to the chat window showing `session_id`, or to the main window if there is none.
+pub(crate) fn emit_to_session<S: Serialize + Clone>(app: &tauri::AppHandle, session_id: &str, event: &str, payload: S) {
+  let target = match window_for_session(app, session_id) {
+    Some(window) => window.label().to_string(),
+    None => crate::MAIN_WINDOW_LABEL.to_string(),
+  };
+  let _ = app.emit_to(target.as_str(), event, payload);
+}
+
+/// Open (or focus, if already open) a chat window for `session_id`. Returns the window label.
+#[tauri::command]
+pub fn open_chat_window(
+  app: tauri::AppHandle,
+  chats: tauri::State<ChatWindowsState>,
+  session_id: String,
+) -> Result<String, AppError> {
+  validate_session_id(&session_id)?;
+  let label = label_for(&session_id);
+  if let Some(existing) = app.get_webview_window(&label) {
+    let _ = existing.show();
+    let _ = existing.unminimize();
+    let _ = existing.set_focus();
+    return Ok(label);
+  }
+  let init = format!("window.__CHAT_SESSION__ = {};", serde_json::json!(session_id));
+  WebviewWindowBuilder::new(&app, &label, WebviewUrl::App("index.html".into()))
+    .title(format!("AI Μέντορας — #{}", session_id))
+    .inner_size(480.0, 720.0)
+    .min_inner_size(360.0, 400.0)
+    .initialization_script(&init)
+    .build()
+    .map_err(|e| e.to_string())?;
+  chats
+    .windows
+    .lock()
+    .map_err(|e| e.to_string())?
+    .insert(label.clone(), session_id.clone());
+  crate::app_log(&format!("chat window: opened {} for session {}", label, session_id));
+  Ok(label)
+}
+
+/// Chat windows that are currently open, sorted by label.
+#[tauri::command]
+pub fn list_chat_windows(app: tauri::AppHandle, chats: tauri::State<ChatWindowsState>) -> Result<Vec<ChatWindowInfo>, AppError> {
+  let windows = chats.windows.lock().map_err(|e| e.to_string())?;
+  let mut out: Vec<ChatWindowInfo> = windows
+    .iter()
+    .filter_map(|(label, session_id)| {
+      let window = app.get_webview_window(label)?;
+      Some(ChatWindowInfo {
+        label: label.clone(),
+        session_id: session_id.clone(),
+        focused: window.is_focused().unwrap_or(false),
+      })
+    })
+    .collect();
+  out.sort_by(|a, b| a.label.cmp(&b.label));
+  Ok(out)
+}
@@ -0,0 +1,140 @@
+// Command-line flags for the desktop shell. They override settings/env for this session only:
+//   --port <n>            backend port (default 8000)
+//   --no-autostart        never spawn the backend
+//   --backend-path <exe>  backend executable instead of the bundled resource
+//   --log-level <level>   error | warn | info | debug (shell logs; also passed to the backend)
+//   --data-dir <dir>      base dir instead of %LOCALAPPDATA%\AI_Mentor
+//   --minimized           start in the tray without splash or main window (launch at login)
+// Both `--flag value` and `--flag=value` are accepted; unknown arguments are ignored.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+  Error,
+  #[serde(alias = "warning")]
+  Warn,
+  Info,
+  #[serde(alias = "trace")]
+  Debug,
+}
+
+impl LogLevel {
+  fn parse(s: &str) -> Option<Self> {
+    match s.to_ascii_lowercase().as_str() {
+      "error" => Some(Self::Error),
+      "warn" | "warning" => Some(Self::Warn),
+      "info" => Some(Self::Info),
+      "debug" | "trace" => Some(Self::Debug),
+      _ => None,
+    }
+  }
+
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::Error => "error",
+      Self::Warn => "warn",
+      Self::Info => "info",
+      Self::Debug => "debug",
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CliArgs {
+  pub port: Option<u16>,
+  pub no_autostart: bool,
+  pub backend_path: Option<PathBuf>,
+  pub log_level: Option<LogLevel>,
+  pub data_dir: Option<PathBuf>,
+  pub minimized: bool,
+  /// Arguments that were not understood (reported in app.log once logging is set up).
+  pub ignored: Vec<String>,
+}
+
+impl CliArgs {
+  pub fn has_overrides(&self) -> bool {
+    self.port.is_some()
+      || self.no_autostart
+      || self.backend_path.is_some()
+      || self.log_level.is_some()
+      || self.data_dir.is_some()
+      || self.minimized
+  }
+}
+
+static CLI_ARGS: OnceLock<CliArgs> = OnceLock::new();
+pub const NO_AUTOSTART_FLAG: &str = "--no-autostart";
+pub const MINIMIZED_FLAG: &str = "--minimized";
+
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> CliArgs {
+  let mut out = CliArgs::default();
+  let mut it = args.into_iter();
+  while let Some(arg) = it.next() {
+    let (flag, inline) = match arg.split_once('=') {
+      Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+      _ => (arg.clone(), None),
+    };
+    let value = |it: &mut I::IntoIter| inline.clone().or_else(|| it.next());
+    match flag.as_str() {
+      NO_AUTOSTART_FLAG => out.no_autostart = true,
+      MINIMIZED_FLAG => out.minimized = true,
+      "--port" => match value(&mut it).and_then(|v| v.parse::<u16>().ok()).filter(|p| *p != 0) {
+        Some(p) => out.port = Some(p),
+        None => out.ignored.push(arg),
+      },
+      "--backend-path" => match value(&mut it) {
+        Some(v) => out.backend_path = Some(PathBuf::from(v)),
+        None => out.ignored.push(arg),
+      },
+      "--log-level" => match value(&mut it).as_deref().and_then(LogLevel::parse) {
+        Some(l) => out.log_level = Some(l),
+        None => out.ignored.push(arg),
+      },
+      "--data-dir" => match value(&mut it) {
+        Some(v) => out.data_dir = Some(PathBuf::from(v)),
+        None => out.ignored.push(arg),
+      },
+      _ => out.ignored.push(arg),
+    }
+  }
+  out
+}
+
+/// Parse std::env::args once at startup. Later calls keep the first result.
+pub fn init() -> &'static CliArgs {
+  CLI_ARGS.get_or_init(|| parse(std::env::args().skip(1)))
+}
+
+/// Session overrides (defaults if init() has not run, e.g. in helpers used before startup).
+pub fn args() -> &'static CliArgs {
+  static EMPTY: OnceLock<CliArgs> = OnceLock::new();
+  CLI_ARGS.get().unwrap_or_else(|| EMPTY.get_or_init(CliArgs::default))
+}
+
+static SETTINGS_LOG_LEVEL: RwLock<Option<LogLevel>> = RwLock::new(None);
+
+/// settings.log_level; applied at startup and when the settings file changes.
+pub fn set_settings_log_level(level: Option<LogLevel>) {
+  if let Ok(mut g) = SETTINGS_LOG_LEVEL.write() {
+    *g = level;
+  }
+}
+
+/// Effective shell log level: --log-level, then AI_MENTOR_LOG_LEVEL, then settings, then info.
+pub fn log_level() -> LogLevel {
+  args()
+    .log_level
+    .or_else(|| std::env::var("AI_MENTOR_LOG_LEVEL").ok().as_deref().and_then(LogLevel::parse))
+    .or_else(|| SETTINGS_LOG_LEVEL.read().ok().and_then(|g| *g))
+    .unwrap_or(LogLevel::Info)
+}
+
+/// Active session overrides, for the frontend/support view.
+#[tauri::command]
+pub fn get_cli_overrides() -> CliArgs {
+  args().clone()
+}
@@ -0,0 +1,175 @@
+// Per-command execution stats. `wrap` sits around the generated invoke handler and times every
+// dispatch; for sync commands that is the whole call (they run inside the handler). Async commands
+// return from the handler immediately, so they time themselves with `track`, which also sees their
+// Ok/Err outcome. Calls slower than SLOW_COMMAND_MS are logged with redacted arguments.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::Manager;
+
+use crate::error::AppError;
+
+pub const SLOW_COMMAND_MS: u64 = 500;
+/// Commands whose handler only spawns the work; they report through `track` instead.
+const ASYNC_COMMANDS: &[&str] = &[
+  "retry_backend_start",
+  "kill_backend_and_retry",
+  "stop_backend",
+  "run_doctor_report",
+  "get_backend_metrics",
+  "attach_files_to_backend",
+  "backup_backend_data",
+  "restore_backend_data",
+  "download_model",
+  "switch_profile",
+  "submit_crash_report",
+  "diagnose_connectivity",
+  "wake_backend",
+  "get_network_state",
+];
+/// Argument names whose values never reach the log.
+const REDACTED_KEYS: &[&str] = &["value", "token", "secret", "password", "localStorage"];
+const MAX_LOGGED_ARGS_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CommandStat {
+  pub calls: u64,
+  /// Err results (async commands), panics and unknown commands.
+  pub failures: u64,
+  pub slow_calls: u64,
+  pub total_ms: u64,
+  pub max_ms: u64,
+  pub last_ms: u64,
+}
+
+#[derive(Default)]
+pub struct CommandStatsState {
+  stats: Mutex<HashMap<String, CommandStat>>,
+  /// Redacted args of the latest dispatch of each async command, for its slow-call log line.
+  pending_args: Mutex<HashMap<String, String>>,
+}
+
+impl CommandStatsState {
+  fn record(&self, command: &str, elapsed_ms: u64, ok: bool, args: &str) {
+    crate::telemetry::record_command(command, elapsed_ms, ok);
+    let slow = elapsed_ms >= SLOW_COMMAND_MS;
+    if let Ok(mut stats) = self.stats.lock() {
+      let s = stats.entry(command.to_string()).or_default();
+      s.calls += 1;
+      s.total_ms += elapsed_ms;
+      s.max_ms = s.max_ms.max(elapsed_ms);
+      s.last_ms = elapsed_ms;
+      if !ok {
+        s.failures += 1;
+      }
+      if slow {
+        s.slow_calls += 1;
+      }
+    }
+    if slow {
+      crate::app_log(&format!(
+        "command: slow {} {}ms ok={} args={}",
+        command, elapsed_ms, ok, args
+      ));
+    }
+  }
+}
+
+fn redact_value(key: Option<&str>, value: &serde_json::Value) -> serde_json::Value {
+  use serde_json::Value;
+  if key.is_some_and(|k| REDACTED_KEYS.contains(&k)) {
+    return Value::String("<redacted>".to_string());
+  }
+  match value {
+    Value::String(s) => Value::String(crate::anonymize::anonymize(s).0),
+    Value::Array(items) => Value::Array(items.iter().map(|v| redact_value(None, v)).collect()),
+    Value::Object(map) => Value::Object(
+      map
+        .iter()
+        .map(|(k, v)| (k.clone(), redact_value(Some(k), v)))
+        .collect(),
+    ),
+    other => other.clone(),
+  }
+}
+
+/// Arguments as loggable JSON: secret-looking keys blanked, strings anonymized, length capped.
+fn redacted_args(body: &InvokeBody) -> String {
+  let mut out = match body {
+    InvokeBody::Json(v) => redact_value(None, v).to_string(),
+    InvokeBody::Raw(bytes) => format!("<{} raw bytes>", bytes.len()),
+  };
+  if out.len() > MAX_LOGGED_ARGS_CHARS {
+    let mut cut = MAX_LOGGED_ARGS_CHARS;
+    while !out.is_char_boundary(cut) {
+      cut -= 1;
+    }
+    out.truncate(cut);
+    out.push('…');
+  }
+  out
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+  started.elapsed().as_millis() as u64
+}
+
+/// Wrap the generated invoke handler. Panics are recorded and then resumed so Tauri still sees them.
+pub(crate) fn wrap<F>(handler: F) -> impl Fn(Invoke) -> bool + Send + Sync + 'static
+where
+  F: Fn(Invoke) -> bool + Send + Sync + 'static,
+{
+  move |invoke: Invoke| {
+    let app = invoke.message.webview().app_handle().clone();
+    let command = invoke.message.command().to_string();
+    let args = redacted_args(invoke.message.payload());
+    let is_async = ASYNC_COMMANDS.contains(&command.as_str());
+    if is_async {
+      if let Ok(mut pending) = app.state::<CommandStatsState>().pending_args.lock() {
+        pending.insert(command.clone(), args.clone());
+      }
+    }
+    let started = Instant::now();
+    match std::panic::catch_unwind(AssertUnwindSafe(|| handler(invoke))) {
+      Ok(handled) => {
+        if !is_async || !handled {
+          app.state::<CommandStatsState>().record(&command, elapsed_ms(started), handled, &args);
+        }
+        handled
+      }
+      Err(panic) => {
+        app.state::<CommandStatsState>().record(&command, elapsed_ms(started), false, &args);
+        std::panic::resume_unwind(panic)
+      }
+    }
+  }
+}
+
+/// Time an async command body to completion and record its outcome under `command`.
+pub(crate) async fn track<T, E, Fut>(app: &tauri::AppHandle, command: &str, fut: Fut) -> Result<T, E>
+where
+  Fut: Future<Output = Result<T, E>>,
+{
+  let started = Instant::now();
+  let result = fut.await;
+  let stats = app.state::<CommandStatsState>();
+  let args = stats
+    .pending_args
+    .lock()
+    .ok()
+    .and_then(|mut p| p.remove(command))
+    .unwrap_or_default();
+  stats.record(command, elapsed_ms(started), result.is_ok(), &args);
+  result
+}
+
+/// Stats per command since app start, keyed by command name.
+#[tauri::command]
+pub fn get_command_stats(stats: tauri::State<CommandStatsState>) -> Result<HashMap<String, CommandStat>, AppError> {
+  Ok(stats.stats.lock().map(|s| s.clone()).map_err(|e| e.to_string())?)
+}
@@ -0,0 +1,308 @@
+// Loopback connectivity diagnostics for the "NOT_READY but the backend is running" cases: security
+// software blocking 127.0.0.1, firewall block rules on the backend exe (Windows), and proxies picked
+// up by reqwest from the environment or system settings. Each finding carries an action the UI can
+// show as is.
+
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::error::AppError;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const ACCEPT_POLL: Duration = Duration::from_millis(10);
+const PROXY_VARS: &[&str] = &["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY", "http_proxy", "https_proxy", "all_proxy"];
+const NO_PROXY_VARS: &[&str] = &["NO_PROXY", "no_proxy"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+  pub id: &'static str,
+  /// "ok" | "warn" | "fail" | "skipped"
+  pub severity: &'static str,
+  pub summary: String,
+  /// What the user can do about it; None when nothing is needed.
+  pub action: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectivityReport {
+  pub generated_at: u64,
+  pub backend_url: String,
+  pub findings: Vec<Finding>,
+}
+
+fn finding(id: &'static str, severity: &'static str, summary: impl Into<String>, action: Option<&str>) -> Finding {
+  Finding {
+    id,
+    severity,
+    summary: summary.into(),
+    action: action.map(str::to_string),
+  }
+}
+
+/// Bind an ephemeral port on 127.0.0.1 and round-trip a byte through it.
+fn check_loopback_roundtrip() -> Finding {
+  const ID: &str = "loopback_roundtrip";
+  const ACTION: &str = "Security software is blocking local connections. Allow AI Mentor and ai-mentor-backend.exe in its firewall / web protection settings.";
+  let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, 0)) {
+    Ok(l) => l,
+    Err(e) => return finding(ID, "fail", format!("cannot bind 127.0.0.1: {}", e), Some(ACTION)),
+  };
+  let addr = match listener.local_addr() {
+    Ok(a) => a,
+    Err(e) => return finding(ID, "fail", e.to_string(), Some(ACTION)),
+  };
+  // Non-blocking accept with a deadline, so the thread (and the listener) end even if the
+  // connection never arrives.
+  if let Err(e) = listener.set_nonblocking(true) {
+    return finding(ID, "fail", format!("cannot configure listener on {}: {}", addr, e), Some(ACTION));
+  }
+  let deadline = Instant::now() + CONNECT_TIMEOUT;
+  let server = std::thread::spawn(move || -> std::io::Result<()> {
+    let mut conn = loop {
+      match listener.accept() {
+        Ok((conn, _)) => break conn,
+        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock && Instant::now() < deadline => {
+          std::thread::sleep(ACCEPT_POLL)
+        }
+        Err(e) => return Err(e),
+      }
+    };
+    conn.set_nonblocking(false)?;
+    conn.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    let mut buf = [0u8; 1];
+    conn.read_exact(&mut buf)?;
+    conn.write_all(&buf)
+  });
+  let client = (|| -> std::io::Result<()> {
+    let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    stream.write_all(b"x")?;
+    let mut buf = [0u8; 1];
+    stream.read_exact(&mut buf)
+  })();
+  let _ = server.join();
+  match client {
+    Ok(()) => finding(ID, "ok", format!("connected to {}", addr), None),
+    Err(e) => finding(ID, "fail", format!("round trip through {} failed: {}", addr, e), Some(ACTION)),
+  }
+}
+
+fn check_localhost_resolution() -> Finding {
+  const ID: &str = "localhost_resolution";
+  match "localhost:80".to_socket_addrs() {
+    Ok(addrs) => {
+      let addrs: Vec<SocketAddr> = addrs.collect();
+      if !addrs.is_empty() && addrs.iter().all(|a| a.ip().is_loopback()) {
+        finding(ID, "ok", "localhost resolves to loopback", None)
+      } else {
+        finding(
+          ID,
+          "warn",
+          format!("localhost resolves to {:?}", addrs),
+          Some("Check the hosts file: localhost should map to 127.0.0.1 and ::1 only."),
+        )
+      }
+    }
+    Err(e) => finding(ID, "warn", format!("localhost does not resolve: {}", e), Some("Check the hosts file.")),
+  }
+}
+
+/// NO_PROXY covers loopback if it lists 127.0.0.1, localhost or "*".
+fn no_proxy_covers_loopback(value: &str) -> bool {
+  value
+    .split(',')
+    .map(|s| s.trim().trim_start_matches('.'))
+    .any(|s| matches!(s, "*" | "127.0.0.1" | "localhost" | "127.0.0.0/8"))
+}
+
+fn check_proxy_env() -> Finding {
+  const ID: &str = "proxy_env";
+  let set: Vec<&str> = PROXY_VARS
+    .iter()
+    .copied()
+    .filter(|v| std::env::var(v).is_ok_and(|s| !s.trim().is_empty()))
+    .collect();
+  if set.is_empty() {
+    return finding(ID, "ok", "no proxy variables set", None);
+  }
+  let bypassed = NO_PROXY_VARS
+    .iter()
+    .any(|v| std::env::var(v).is_ok_and(|s| no_proxy_covers_loopback(&s)));
+  if bypassed {
+    finding(ID, "ok", format!("{} set, loopback excluded via NO_PROXY", set.join(", ")), None)
+  } else {
+    finding(
+      ID,
+      "warn",
+      format!("{} set without a loopback exclusion; backend requests may go through the proxy", set.join(", ")),
+      Some("Add 127.0.0.1,localhost to the NO_PROXY environment variable and restart AI Mentor."),
+    )
+  }
+}
+
+#[cfg(windows)]
+fn powershell(script: &str) -> Result<String, String> {
+  let out = std::process::Command::new("powershell")
+    .args(["-NoProfile", "-NonInteractive", "-Command", script])
+    .creation_flags(crate::CREATE_NO_WINDOW)
+    .output()
+    .map_err(|e| format!("powershell: {}", e))?;
+  if !out.status.success() {
+    return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+  }
+  Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// WinINet proxy (what reqwest uses on Windows) without a <local> / loopback bypass.
+fn check_system_proxy() -> Finding {
+  const ID: &str = "system_proxy";
+  #[cfg(not(windows))]
+  return finding(ID, "skipped", "system proxy settings are checked on Windows only", None);
+  #[cfg(windows)]
+  {
+    let script = "$s = Get-ItemProperty 'HKCU:\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings'; \
+                  \"$($s.ProxyEnable)|$($s.ProxyServer)|$($s.ProxyOverride)\"";
+    let out = match powershell(script) {
+      Ok(o) => o,
+      Err(e) => return finding(ID, "skipped", format!("could not read proxy settings: {}", e), None),
+    };
+    let mut parts = out.splitn(3, '|');
+    let enabled = parts.next().unwrap_or("").trim() == "1";
+    let server = parts.next().unwrap_or("").trim().to_string();
+    let bypass = parts.next().unwrap_or("").to_ascii_lowercase();
+    if !enabled {
+      return finding(ID, "ok", "no system proxy", None);
+    }
+    if bypass.contains("<local>") || bypass.split(';').any(no_proxy_covers_loopback) {
+      finding(ID, "ok", format!("system proxy {} bypasses local addresses", server), None)
+    } else {
+      finding(
+        ID,
+        "warn",
+        format!("system proxy {} does not bypass local addresses", server),
+        Some("In Windows proxy settings, enable \"Don't use the proxy server for local (intranet) addresses\"."),
+      )
+    }
+  }
+}
+
+/// Enabled inbound/outbound block rules whose program is the backend exe.
+fn check_firewall(exe: Option<std::path::PathBuf>) -> Finding {
+  const ID: &str = "firewall_rules";
+  #[cfg(not(windows))]
+  {
+    let _ = exe;
+    finding(ID, "skipped", "firewall rules are checked on Windows only", None)
+  }
+  #[cfg(windows)]
+  {
+    let Some(exe) = exe else {
+      return finding(ID, "skipped", "backend exe path could not be resolved", None);
+    };
+    let program = exe.display().to_string().replace('\'', "''");
+    let script = format!(
+      "Get-NetFirewallApplicationFilter -Program '{}' -ErrorAction SilentlyContinue | Get-NetFirewallRule | \
+       Where-Object {{ $_.Enabled -eq 'True' -and $_.Action -eq 'Block' }} | \
+       ForEach-Object {{ \"$($_.DisplayName) ($($_.Direction))\" }}",
+      program
+    );
+    match powershell(&script) {
+      Ok(out) if out.is_empty() => finding(ID, "ok", format!("no block rules for {}", exe.display()), None),
+      Ok(out) => finding(
+        ID,
+        "fail",
+        format!("block rules for the backend: {}", out.lines().collect::<Vec<_>>().join(", ")),
+        Some("Remove or disable these Windows Firewall rules (wf.msc), or ask your administrator."),
+      ),
+      Err(e) => finding(ID, "skipped", format!("could not query firewall: {}", e), None),
+    }
+  }
+}
+
+/// Compare the app's HTTP client (proxy-aware) with a direct connection to the local backend.
+fn check_backend_paths() -> Finding {
+  const ID: &str = "backend_path";
+  let Some(port) = crate::profiles::active().local_port else {
+    return finding(ID, "skipped", "active profile is not a local backend", None);
+  };
+  let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+  if let Err(e) = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+    return finding(
+      ID,
+      "warn",
+      format!("nothing accepts connections on {}: {}", addr, e),
+      Some("The backend is not running; use Retry, or check backend_child.log for start-up errors."),
+    );
+  }
+  let url = crate::health_url();
+  let timeout = crate::health::config().request_timeout();
+  let via_client = tauri::async_runtime::block_on(async {
+    crate::profiles::client_for(&url)
+      .get(&url)
+      .timeout(timeout)
+      .send()
+      .await
+      .is_ok_and(|r| r.status().is_success())
+  });
+  let direct = tauri::async_runtime::block_on(async {
+    match reqwest::Client::builder().no_proxy().build() {
+      Ok(c) => c.get(&url).timeout(timeout).send().await.is_ok_and(|r| r.status().is_success()),
+      Err(_) => false,
+    }
+  });
+  match (via_client, direct) {
+    (true, _) => finding(ID, "ok", format!("{} answers", url), None),
+    (false, true) => finding(
+      ID,
+      "fail",
+      format!("{} answers directly but not through the configured proxy", url),
+      Some("A proxy is intercepting local traffic. Exclude 127.0.0.1 and localhost from the proxy."),
+    ),
+    (false, false) => finding(
+      ID,
+      "fail",
+      format!("port {} accepts connections but {} does not answer", port, url),
+      Some("Another program may be using this port, or security software is inspecting local HTTP. Try Kill & retry."),
+    ),
+  }
+}
+
+fn run_checks(exe: Option<std::path::PathBuf>) -> ConnectivityReport {
+  ConnectivityReport {
+    generated_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+    backend_url: crate::api_base(),
+    findings: vec![
+      check_loopback_roundtrip(),
+      check_localhost_resolution(),
+      check_proxy_env(),
+      check_system_proxy(),
+      check_firewall(exe),
+      check_backend_paths(),
+    ],
+  }
+}
+
+/// Check loopback binding, proxies and firewall rules that keep the UI from reaching the backend.
+#[tauri::command]
+pub async fn diagnose_connectivity(app: tauri::AppHandle) -> Result<ConnectivityReport, AppError> {
+  crate::command_stats::track(&app, "diagnose_connectivity", async {
+    let exe = crate::backend_exe_path(&app).ok();
+    let report = tauri::async_runtime::spawn_blocking(move || run_checks(exe))
+      .await
+      .map_err(|e| e.to_string())?;
+    let problems: Vec<&str> = report
+      .findings
+      .iter()
+      .filter(|f| f.severity == "warn" || f.severity == "fail")
+      .map(|f| f.id)
+      .collect();
+    crate::app_log(&format!("connectivity: problems {:?}", problems));
+    Ok(report)
+  })
+  .await
+}
@@ -0,0 +1,275 @@
+// Crash reports. A panic hook and non-zero exits of managed sidecars each write one JSON report to
+// <base>/crashes (build id, backend status, anonymized log tail; backtrace for panics, exit code and
+// last child output for sidecars). Reports stay pending until the user sends or dismisses them, so
+// the UI can offer them on the next launch. Sent reports move to crashes/sent.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::Manager;
+
+use crate::BackendState;
+use crate::error::AppError;
+
+const CRASHES_DIR_NAME: &str = "crashes";
+const SENT_DIR_NAME: &str = "sent";
+/// Oldest pending reports beyond this are dropped.
+const MAX_PENDING_REPORTS: usize = 20;
+const LOG_TAIL_LINES: usize = 50;
+const CHILD_OUTPUT_LINES: usize = 30;
+/// How often owned sidecars are checked for an exit after they became READY.
+const EXIT_WATCH_MS: u64 = 2_000;
+const SUBMIT_TIMEOUT_SECS: u64 = 15;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CrashDetails {
+  Panic {
+    message: String,
+    location: Option<String>,
+    thread: Option<String>,
+    backtrace: String,
+  },
+  ServiceExit {
+    service: String,
+    exit_code: i32,
+    /// Last lines of the service's child log (stdout and stderr).
+    last_output: String,
+  },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+  pub id: String,
+  pub created_at: u64,
+  pub build_id: String,
+  pub app_version: String,
+  pub backend_status: String,
+  pub app_log_tail: String,
+  #[serde(flatten)]
+  pub details: CrashDetails,
+}
+
+/// Backend state for the panic hook, which has no app handle.
+static BACKEND: OnceLock<Arc<BackendState>> = OnceLock::new();
+
+fn crashes_dir() -> PathBuf {
+  crate::app_base_dir().join(CRASHES_DIR_NAME)
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0)
+}
+
+fn new_id(kind: &str) -> String {
+  let mut bytes = [0u8; 4];
+  let _ = getrandom::fill(&mut bytes);
+  let suffix: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+  format!("{}-{}-{}", kind, now_secs(), suffix)
+}
+
+/// Ids are generated by new_id; anything else is rejected before it reaches a path.
+fn validate_id(id: &str) -> Result<(), AppError> {
+  if !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+    Ok(())
+  } else {
+    Err(AppError::InvalidInput(format!("invalid crash report id: {:?}", id)))
+  }
+}
+
+fn log_tail(path: &Path, lines: usize) -> String {
+  let tail = crate::diagnostics::last_lines(&crate::diagnostics::read_log(path), lines);
+  crate::anonymize::anonymize(&tail).0
+}
+
+/// Status without blocking: the panic may have happened while the state lock was held.
+fn backend_status() -> String {
+  let Some(state) = BACKEND.get() else {
+    return "unknown".to_string();
+  };
+  match state.inner.try_lock() {
+    Ok(g) => match (g.status.as_str(), g.not_ready_reason.as_ref()) {
+      ("NOT_READY" | "DEGRADED", Some(r)) => format!("{}:{}", g.status, r),
+      (s, _) => s.to_string(),
+    },
+    Err(_) => "unknown (state locked)".to_string(),
+  }
+}
+
+fn write_report(details: CrashDetails, kind: &str) -> Result<PathBuf, String> {
+  let report = CrashReport {
+    id: new_id(kind),
+    created_at: now_secs(),
+    build_id: std::env!("BUILD_ID").to_string(),
+    app_version: env!("CARGO_PKG_VERSION").to_string(),
+    backend_status: backend_status(),
+    app_log_tail: log_tail(&crate::app_log_path(), LOG_TAIL_LINES),
+    details,
+  };
+  let dir = crashes_dir();
+  std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+  let path = dir.join(format!("{}.json", report.id));
+  let json = serde_json::to_vec_pretty(&report).map_err(|e| e.to_string())?;
+  std::fs::write(&path, json).map_err(|e| e.to_string())?;
+  prune_pending();
+  Ok(path)
+}
+
+fn pending_paths() -> Vec<PathBuf> {
+  let Ok(entries) = std::fs::read_dir(crashes_dir()) else {
+    return Vec::new();
+  };
+  let mut paths: Vec<PathBuf> = entries
+    .filter_map(|e| e.ok())
+    .map(|e| e.path())
+    .filter(|p| p.is_file() && p.extension().is_some_and(|x| x == "json"))
+    .collect();
+  // Ids start with kind-<secs>; order by the timestamp part.
+  paths.sort_by_key(|p| {
+    p.file_stem()
+      .and_then(|s| s.to_str())
+      .and_then(|s| s.split('-').rev().nth(1))
+      .and_then(|t| t.parse::<u64>().ok())
+      .unwrap_or(0)
+  });
+  paths
+}
+
+fn prune_pending() {
+  let paths = pending_paths();
+  for path in paths.iter().take(paths.len().saturating_sub(MAX_PENDING_REPORTS)) {
+    let _ = std::fs::remove_file(path);
+  }
+}
+
+/// Write a report on panic, then run the previous hook (stderr message). Call once, early in run().
+pub fn install(backend: Arc<BackendState>) {
+  let _ = BACKEND.set(backend);
+  let previous = std::panic::take_hook();
+  std::panic::set_hook(Box::new(move |info| {
+    let message = info
+      .payload()
+      .downcast_ref::<&str>()
+      .map(|s| s.to_string())
+      .or_else(|| info.payload().downcast_ref::<String>().cloned())
+      .unwrap_or_else(|| "<non-string panic payload>".to_string());
+    let details = CrashDetails::Panic {
+      message: crate::anonymize::anonymize(&message).0,
+      location: info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+      thread: std::thread::current().name().map(str::to_string),
+      backtrace: crate::anonymize::anonymize(&std::backtrace::Backtrace::force_capture().to_string()).0,
+    };
+    match write_report(details, "panic") {
+      Ok(path) => crate::app_log(&format!("crash: panic report written to {}", path.display())),
+      Err(e) => crate::app_log(&format!("crash: could not write panic report: {}", e)),
+    }
+    previous(info);
+  }));
+}
+
+/// Record a managed service exiting with a non-zero code. Exit code 0 is a clean stop, not a crash.
+pub(crate) fn record_service_exit(service: &str, exit_code: i32, child_log: &Path) {
+  if exit_code == 0 {
+    return;
+  }
+  let details = CrashDetails::ServiceExit {
+    service: service.to_string(),
+    exit_code,
+    last_output: log_tail(child_log, CHILD_OUTPUT_LINES),
+  };
+  match write_report(details, "service") {
+    Ok(path) => crate::app_log(&format!(
+      "crash: {} exited with code {}, report {}",
+      service,
+      exit_code,
+      path.display()
+    )),
+    Err(e) => crate::app_log(&format!("crash: could not write {} exit report: {}", service, e)),
+  }
+}
+
+/// Watch owned sidecars that are READY or DEGRADED; one that exits is marked NOT_READY:BACKEND_EXITED
+/// and, on a non-zero code, reported. Start-up exits are handled by the spawn flow itself.
+pub fn start_exit_watch(app: &tauri::AppHandle) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tokio::time::sleep(Duration::from_millis(EXIT_WATCH_MS)).await;
+      let registry = app.state::<crate::services::ServiceRegistry>();
+      for service in registry.all() {
+        let exited = {
+          let Ok(mut g) = service.state.inner.lock() else {
+            continue;
+          };
+          if !crate::is_up(&g.status) {
+            continue;
+          }
+          let Some(code) = g.child.as_mut().and_then(|c| c.exit_code()) else {
+            continue;
+          };
+          g.child.take();
+          g.set_status("NOT_READY", Some(crate::NOT_READY_REASON_EXITED.to_string()));
+          code
+        };
+        crate::app_log(&format!("{}: process exited with code {} while running", service.name, exited));
+        record_service_exit(service.name, exited, &service.child_log_path());
+      }
+    }
+  });
+}
+
+/// Reports not yet sent or dismissed, oldest first.
+#[tauri::command]
+pub fn get_pending_crash_reports() -> Vec<CrashReport> {
+  pending_paths()
+    .iter()
+    .filter_map(|p| std::fs::read_to_string(p).ok())
+    .filter_map(|t| serde_json::from_str(&t).ok())
+    .collect()
+}
+
+/// POST the report as JSON to settings.crash_report_url with an optional user comment, then move it
+/// to crashes/sent. The backend has no crash-reports endpoint, so without a URL this is Unsupported.
+#[tauri::command]
+pub async fn submit_crash_report(app: tauri::AppHandle, id: String, comment: Option<String>) -> Result<(), AppError> {
+  crate::command_stats::track(&app, "submit_crash_report", async {
+    validate_id(&id)?;
+    let url = app
+      .state::<crate::settings::SettingsState>()
+      .get()
+      .crash_report_url
+      .ok_or_else(|| AppError::Unsupported("crash reporting is not configured (crash_report_url)".to_string()))?;
+    let path = crashes_dir().join(format!("{}.json", id));
+    let text = std::fs::read_to_string(&path)?;
+    let mut body: serde_json::Value = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+    if let (Some(obj), Some(c)) = (body.as_object_mut(), comment) {
+      obj.insert("comment".to_string(), serde_json::json!(c));
+    }
+    crate::http_client()
+      .post(&url)
+      .timeout(Duration::from_secs(SUBMIT_TIMEOUT_SECS))
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(body.to_string())
+      .send()
+      .await?
+      .error_for_status()?;
+    let sent = crashes_dir().join(SENT_DIR_NAME);
+    std::fs::create_dir_all(&sent)?;
+    std::fs::rename(&path, sent.join(format!("{}.json", id)))?;
+    crate::app_log(&format!("crash: report {} sent", id));
+    Ok(())
+  })
+  .await
+}
+
+/// Delete a pending report without sending it.
+#[tauri::command]
+pub fn dismiss_crash_report(id: String) -> Result<(), AppError> {
+  validate_id(&id)?;
+  std::fs::remove_file(crashes_dir().join(format!("{}.json", id)))?;
+  Ok(())
+}
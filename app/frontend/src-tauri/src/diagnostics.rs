@@ -0,0 +1,139 @@
+// Diagnostics bundle: zip of the app/autostart/child logs plus a manifest, anonymized by default.
+// preview_log_anonymization shows exactly what a log will look like inside the bundle.
+
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::BackendState;
+use crate::anonymize::{self, AnonymizeCounts};
+use crate::error::AppError;
+
+const PREVIEW_DEFAULT_LINES: usize = 200;
+
+/// Logs included in the bundle, by the name the frontend uses.
+pub(crate) fn bundle_logs() -> Vec<(&'static str, PathBuf)> {
+  vec![
+    ("app", crate::app_log_path()),
+    ("backend_autostart", crate::backend_autostart_log_path()),
+    ("backend_child", crate::backend_child_log_path()),
+    ("status_history", crate::status_history::history_path()),
+  ]
+}
+
+pub(crate) fn log_path_by_name(name: &str) -> Result<PathBuf, String> {
+  bundle_logs()
+    .into_iter()
+    .find(|(n, _)| *n == name)
+    .map(|(_, p)| p)
+    .ok_or_else(|| format!("unknown log: {}", name))
+}
+
+/// A log's text with encrypted lines decrypted (see log_privacy).
+pub(crate) fn read_log(path: &Path) -> String {
+  std::fs::read(path)
+    .map(|b| crate::log_privacy::unprotect(&String::from_utf8_lossy(&b)))
+    .unwrap_or_default()
+}
+
+pub(crate) fn last_lines(text: &str, n: usize) -> String {
+  let lines: Vec<&str> = text.lines().collect();
+  lines[lines.len().saturating_sub(n)..].join("\n")
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnonymizationPreview {
+  pub log: String,
+  pub original: String,
+  pub anonymized: String,
+  pub replacements: AnonymizeCounts,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BundleManifest {
+  build_id: &'static str,
+  generated_at: u64,
+  anonymized: bool,
+  backend_status: String,
+  files: Vec<BundleFile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BundleFile {
+  name: String,
+  bytes: usize,
+  replacements: usize,
+}
+
+/// Writes the diagnostics zip to `dest`. Shared by the Tauri command and other exporters.
+pub(crate) fn write_bundle(dest: &Path, anonymize_logs: bool, backend_status: String) -> Result<(), String> {
+  if let Some(parent) = dest.parent() {
+    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+  }
+  let file = std::fs::File::create(dest).map_err(|e| e.to_string())?;
+  let mut zip = zip::ZipWriter::new(file);
+  let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+  let mut files = Vec::new();
+  for (name, path) in bundle_logs() {
+    let text = read_log(&path);
+    let (text, replacements) = if anonymize_logs {
+      let (t, c) = anonymize::anonymize(&text);
+      (t, c.total())
+    } else {
+      (text, 0)
+    };
+    let entry = format!("logs/{}.log", name);
+    zip.start_file(entry.as_str(), options).map_err(|e| e.to_string())?;
+    zip.write_all(text.as_bytes()).map_err(|e| e.to_string())?;
+    files.push(BundleFile {
+      name: entry,
+      bytes: text.len(),
+      replacements,
+    });
+  }
+
+  let manifest = BundleManifest {
+    build_id: std::env!("BUILD_ID"),
+    generated_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+    anonymized: anonymize_logs,
+    backend_status,
+    files,
+  };
+  zip.start_file("manifest.json", options).map_err(|e| e.to_string())?;
+  let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+  zip.write_all(&json).map_err(|e| e.to_string())?;
+  zip.finish().map_err(|e| e.to_string())?;
+  Ok(())
+}
+
+/// Show the last `max_lines` of a log (app | backend_autostart | backend_child | status_history) before and after anonymization.
+#[tauri::command]
+pub fn preview_log_anonymization(log: String, max_lines: Option<usize>) -> Result<AnonymizationPreview, AppError> {
+  let path = log_path_by_name(&log)?;
+  let original = last_lines(&read_log(&path), max_lines.unwrap_or(PREVIEW_DEFAULT_LINES));
+  let (anonymized, replacements) = anonymize::anonymize(&original);
+  Ok(AnonymizationPreview {
+    log,
+    original,
+    anonymized,
+    replacements,
+  })
+}
+
+/// Export logs + manifest to `dest_zip`. Logs are anonymized unless `anonymize` is explicitly false.
+#[tauri::command]
+pub fn export_diagnostics(
+  state: tauri::State<Arc<BackendState>>,
+  dest_zip: String,
+  anonymize: Option<bool>,
+) -> Result<String, AppError> {
+  let anonymize_logs = anonymize.unwrap_or(true);
+  let status = crate::backend_status_string(&state);
+  let dest = PathBuf::from(&dest_zip);
+  write_bundle(&dest, anonymize_logs, status)?;
+  crate::app_log(&format!("diagnostics: exported {} anonymized={}", dest.display(), anonymize_logs));
+  Ok(dest.display().to_string())
+}
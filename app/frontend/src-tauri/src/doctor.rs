@@ -0,0 +1,210 @@
+// Doctor report: environment checks run concurrently, each with its own timeout. Every result is
+// emitted as a `doctor-check` event as soon as it is known; a stalled probe is reported as "timeout".
+
+use serde::Serialize;
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+use std::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
+
+use crate::error::AppError;
+
+pub const DOCTOR_CHECK_EVENT: &str = "doctor-check";
+pub const DOCTOR_COMPLETE_EVENT: &str = "doctor-complete";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+  pub id: &'static str,
+  /// "ok" | "warn" | "fail" | "timeout"
+  pub status: &'static str,
+  pub detail: String,
+  pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+  pub generated_at: u64,
+  pub build_id: &'static str,
+  pub checks: Vec<DoctorCheck>,
+}
+
+type Outcome = (&'static str, String);
+type Probe = Box<dyn FnOnce() -> Outcome + Send>;
+
+struct CheckSpec {
+  id: &'static str,
+  timeout: Duration,
+  probe: Probe,
+}
+
+fn ok(detail: impl Into<String>) -> Outcome {
+  ("ok", detail.into())
+}
+
+fn warn(detail: impl Into<String>) -> Outcome {
+  ("warn", detail.into())
+}
+
+fn fail(detail: impl Into<String>) -> Outcome {
+  ("fail", detail.into())
+}
+
+fn check_backend_exe(exe: Option<std::path::PathBuf>) -> Outcome {
+  match exe {
+    Some(p) if p.is_file() => ok(p.display().to_string()),
+    Some(p) => fail(format!("missing: {}", p.display())),
+    None => fail("resource path could not be resolved"),
+  }
+}
+
+fn check_logs_writable() -> Outcome {
+  let dir = crate::logs_dir();
+  let probe = dir.join(".doctor_probe");
+  let res = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&probe, b"ok"));
+  let _ = std::fs::remove_file(&probe);
+  match res {
+    Ok(()) => ok(dir.display().to_string()),
+    Err(e) => fail(format!("{}: {}", dir.display(), e)),
+  }
+}
+
+fn check_port() -> Outcome {
+  let profile = crate::profiles::active();
+  if profile.local_port.is_none() {
+    return ok(format!("not checked: profile {} is remote", profile.name));
+  }
+  if crate::port_in_use() {
+    match crate::port_owner::find(crate::backend_port()) {
+      Some(owner) => warn(format!("port {} is in use by {}", crate::backend_port(), owner)),
+      None => warn(format!("port {} is in use", crate::backend_port())),
+    }
+  } else {
+    ok(format!("port {} is free", crate::backend_port()))
+  }
+}
+
+fn check_health() -> Outcome {
+  if tauri::async_runtime::block_on(crate::probe_health_ok()) {
+    ok(crate::health_url())
+  } else {
+    fail(format!("{} not healthy", crate::health_url()))
+  }
+}
+
+fn check_keyring() -> Outcome {
+  match crate::secrets::get("doctor-probe") {
+    Ok(_) => ok("keyring reachable"),
+    Err(e) => fail(e),
+  }
+}
+
+fn check_service_mode(app: &tauri::AppHandle) -> Outcome {
+  match crate::service_mode::current_status(app) {
+    Ok(s) if !s.mode_enabled => ok(format!("disabled ({} installed={})", s.manager, s.installed)),
+    Ok(s) if s.running => ok(format!("{} running", s.manager)),
+    Ok(s) => warn(format!("{} enabled but not running (installed={})", s.manager, s.installed)),
+    Err(e) => warn(e),
+  }
+}
+
+fn check_gpu() -> Outcome {
+  let mut cmd = std::process::Command::new("nvidia-smi");
+  cmd.args(["--query-gpu=name,memory.total", "--format=csv,noheader"]);
+  #[cfg(windows)]
+  cmd.creation_flags(crate::CREATE_NO_WINDOW);
+  match cmd.output() {
+    Ok(o) if o.status.success() => ok(String::from_utf8_lossy(&o.stdout).trim().to_string()),
+    Ok(o) => warn(format!("nvidia-smi exited with {}", o.status)),
+    Err(_) => warn("no NVIDIA GPU detected (nvidia-smi not found)"),
+  }
+}
+
+fn check_migrations() -> Outcome {
+  match crate::migrations::report() {
+    None => warn("not run"),
+    Some(r) if r.newer_than_app => warn(format!("data format {} is newer than this app ({})", r.from, r.current)),
+    Some(r) => match r.failed {
+      Some(ref f) => fail(format!("step {} failed at version {}: {}", f.step, r.to, f.error)),
+      None => ok(format!("data format {}", r.to)),
+    },
+  }
+}
+
+fn check_specs(app: &tauri::AppHandle) -> Vec<CheckSpec> {
+  let exe = crate::backend_exe_path(app).ok();
+  let handle = app.clone();
+  vec![
+    CheckSpec { id: "backend_exe", timeout: Duration::from_secs(1), probe: Box::new(move || check_backend_exe(exe)) },
+    CheckSpec { id: "logs_writable", timeout: Duration::from_secs(2), probe: Box::new(check_logs_writable) },
+    CheckSpec { id: "data_migrations", timeout: Duration::from_secs(1), probe: Box::new(check_migrations) },
+    CheckSpec { id: "backend_port", timeout: Duration::from_secs(1), probe: Box::new(check_port) },
+    CheckSpec { id: "health", timeout: Duration::from_secs(3), probe: Box::new(check_health) },
+    CheckSpec { id: "keyring", timeout: Duration::from_secs(3), probe: Box::new(check_keyring) },
+    CheckSpec { id: "service_mode", timeout: Duration::from_secs(5), probe: Box::new(move || check_service_mode(&handle)) },
+    CheckSpec { id: "gpu", timeout: Duration::from_secs(5), probe: Box::new(check_gpu) },
+  ]
+}
+
+/// Runs one probe on its own thread; gives up after `timeout` (the probe thread is left to finish on its own).
+fn run_with_timeout(spec: CheckSpec) -> DoctorCheck {
+  let started = Instant::now();
+  let (tx, rx) = mpsc::channel();
+  let probe = spec.probe;
+  std::thread::spawn(move || {
+    let _ = tx.send(probe());
+  });
+  let (status, detail) = match rx.recv_timeout(spec.timeout) {
+    Ok(outcome) => outcome,
+    Err(mpsc::RecvTimeoutError::Timeout) => ("timeout", format!("no result within {} ms", spec.timeout.as_millis())),
+    Err(mpsc::RecvTimeoutError::Disconnected) => fail("probe panicked"),
+  };
+  DoctorCheck {
+    id: spec.id,
+    status,
+    detail,
+    duration_ms: started.elapsed().as_millis() as u64,
+  }
+}
+
+fn run_checks(app: &tauri::AppHandle) -> DoctorReport {
+  let specs = check_specs(app);
+  let order: Vec<&'static str> = specs.iter().map(|s| s.id).collect();
+  let (tx, rx) = mpsc::channel();
+  for spec in specs {
+    let tx = tx.clone();
+    let app = app.clone();
+    std::thread::spawn(move || {
+      let check = run_with_timeout(spec);
+      let _ = app.emit(DOCTOR_CHECK_EVENT, &check);
+      let _ = tx.send(check);
+    });
+  }
+  drop(tx);
+
+  // Every runner sends exactly once (bounded by its timeout), so this ends when the slowest check does.
+  let mut checks: Vec<DoctorCheck> = rx.iter().collect();
+  checks.sort_by_key(|c| order.iter().position(|id| *id == c.id));
+  DoctorReport {
+    generated_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+    build_id: std::env!("BUILD_ID"),
+    checks,
+  }
+}
+
+/// Run all doctor checks concurrently. Results stream as `doctor-check` events; the full report is
+/// returned and also emitted as `doctor-complete`.
+#[tauri::command]
+pub async fn run_doctor_report(app: tauri::AppHandle) -> Result<DoctorReport, AppError> {
+  crate::command_stats::track(&app, "run_doctor_report", async {
+    let app_clone = app.clone();
+    let report = tauri::async_runtime::spawn_blocking(move || run_checks(&app_clone))
+      .await
+      .map_err(|e| e.to_string())?;
+    let failed: Vec<&str> = report.checks.iter().filter(|c| c.status != "ok").map(|c| c.id).collect();
+    crate::app_log(&format!("doctor: {} checks, not ok: {:?}", report.checks.len(), failed));
+    let _ = app.emit(DOCTOR_COMPLETE_EVENT, &report);
+    Ok(report)
+  })
+  .await
+}
@@ -0,0 +1,90 @@
+// Backend downtime summary for the frontend banner: status + reason + how long the backend has been
+// down + an estimated recovery time derived from recent restart durations.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::AppError;
+
+const RESTART_HISTORY_MAX: usize = 20;
+/// Number of most recent successful starts averaged for the recovery estimate.
+const ESTIMATE_SAMPLE: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestartRecord {
+  pub started_at: u64,
+  pub duration_ms: u64,
+  pub ok: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DowntimeInfo {
+  pub status: String,
+  pub reason: Option<String>,
+  /// Unix seconds when the backend stopped being READY; None while READY.
+  pub down_since: Option<u64>,
+  pub elapsed_downtime_ms: Option<u64>,
+  /// Only set while STARTING and at least one earlier start succeeded.
+  pub estimated_recovery_ms: Option<u64>,
+  pub restart_attempts: usize,
+  pub restart_failures: usize,
+}
+
+fn unix_secs(t: SystemTime) -> u64 {
+  t.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn elapsed_ms(since: SystemTime) -> u64 {
+  SystemTime::now()
+    .duration_since(since)
+    .unwrap_or(Duration::ZERO)
+    .as_millis() as u64
+}
+
+pub(crate) fn push_restart_record(history: &mut VecDeque<RestartRecord>, started: SystemTime, ok: bool) {
+  if history.len() >= RESTART_HISTORY_MAX {
+    history.pop_front();
+  }
+  history.push_back(RestartRecord {
+    started_at: unix_secs(started),
+    duration_ms: elapsed_ms(started),
+    ok,
+  });
+}
+
+/// Average duration of the most recent successful starts.
+fn typical_start_ms(history: &VecDeque<RestartRecord>) -> Option<u64> {
+  let samples: Vec<u64> = history
+    .iter()
+    .rev()
+    .filter(|r| r.ok)
+    .take(ESTIMATE_SAMPLE)
+    .map(|r| r.duration_ms)
+    .collect();
+  if samples.is_empty() {
+    return None;
+  }
+  Some(samples.iter().sum::<u64>() / samples.len() as u64)
+}
+
+/// One coherent snapshot of backend availability for the downtime banner.
+#[tauri::command]
+pub fn get_downtime_info(state: tauri::State<std::sync::Arc<crate::BackendState>>) -> Result<DowntimeInfo, AppError> {
+  let g = state.inner.lock().map_err(|e| e.to_string())?;
+  let estimated_recovery_ms = match (g.status.as_str(), g.attempt_started) {
+    ("STARTING", Some(started)) => {
+      typical_start_ms(&g.restart_history).map(|typical| typical.saturating_sub(elapsed_ms(started)))
+    }
+    _ => None,
+  };
+  Ok(DowntimeInfo {
+    status: g.status.clone(),
+    reason: g.not_ready_reason.clone(),
+    down_since: g.down_since.map(unix_secs),
+    elapsed_downtime_ms: g.down_since.map(elapsed_ms),
+    estimated_recovery_ms,
+    restart_attempts: g.restart_history.len(),
+    restart_failures: g.restart_history.iter().filter(|r| !r.ok).count(),
+  })
+}
@@ -0,0 +1,66 @@
+// Re-run a Windows tool elevated (UAC prompt) via PowerShell Start-Process -Verb RunAs.
+#![cfg_attr(not(windows), allow(dead_code))]
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+/// Quotes one argument for a Windows command line.
+pub(crate) fn quote_windows_arg(arg: &str) -> String {
+  if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+    return arg.to_string();
+  }
+  format!("\"{}\"", arg.replace('"', "\\\""))
+}
+
+/// True if the tool's output looks like ERROR_ACCESS_DENIED (text is localized, so also check codes).
+pub(crate) fn is_access_denied(out: &std::process::Output) -> bool {
+  let text = format!(
+    "{}{}",
+    String::from_utf8_lossy(&out.stdout),
+    String::from_utf8_lossy(&out.stderr)
+  );
+  text.contains("Access is denied") || text.contains("0x80070005") || out.status.code() == Some(5)
+}
+
+/// Runs `program args...` elevated and waits for it. Returns the elevated process exit code.
+#[cfg(windows)]
+pub(crate) fn run_elevated(program: &str, args: &[&str]) -> Result<i32, String> {
+  let arg_line = args.iter().map(|a| quote_windows_arg(a)).collect::<Vec<_>>().join(" ");
+  let script = format!(
+    "$p = Start-Process -FilePath '{}' -ArgumentList '{}' -Verb RunAs -WindowStyle Hidden -Wait -PassThru; exit $p.ExitCode",
+    program.replace('\'', "''"),
+    arg_line.replace('\'', "''")
+  );
+  let status = std::process::Command::new("powershell")
+    .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+    .creation_flags(crate::CREATE_NO_WINDOW)
+    .status()
+    .map_err(|e| e.to_string())?;
+  Ok(status.code().unwrap_or(-1))
+}
+
+/// Runs `program args...`; on access denied retries elevated when `allow_elevation` is set.
+#[cfg(windows)]
+pub(crate) fn run_checked(program: &str, args: &[&str], allow_elevation: bool) -> Result<(), String> {
+  let out = std::process::Command::new(program)
+    .args(args)
+    .creation_flags(crate::CREATE_NO_WINDOW)
+    .output()
+    .map_err(|e| e.to_string())?;
+  if out.status.success() {
+    return Ok(());
+  }
+  let verb = args.first().copied().unwrap_or("");
+  if allow_elevation && is_access_denied(&out) {
+    crate::app_log(&format!("{} {} denied, retrying elevated", program, verb));
+    let code = run_elevated(program, args)?;
+    if code == 0 {
+      return Ok(());
+    }
+    return Err(format!("{} {} (elevated) failed with code {}", program, verb, code));
+  }
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  let stdout = String::from_utf8_lossy(&out.stdout);
+  let detail = if stderr.trim().is_empty() { stdout.trim() } else { stderr.trim() };
+  Err(format!("{} {} failed: {}", program, verb, detail))
+}
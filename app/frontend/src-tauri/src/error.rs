@@ -0,0 +1,125 @@
+// Error type of every Tauri command. It reaches the frontend as `{ "code": "PORT_IN_USE", "message":
+// "..." }`, so the UI can branch on the code (and show the matching remediation) while the message
+// stays human-readable. Internal helpers may still return Result<_, String>; `?` turns those into
+// Internal, so give a specific variant wherever the UI can do something about the failure.
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+  /// A bundled resource, file, profile, model, window or report does not exist.
+  ResourceNotFound(String),
+  /// The backend (or a companion / helper process) could not be started.
+  SpawnFailed(String),
+  /// The bundled binary is built for another OS or CPU architecture.
+  WrongArch(String),
+  /// The port is held by another process.
+  PortInUse(String),
+  /// The backend did not become healthy in time.
+  HealthTimeout(String),
+  /// Another instance or operation holds the lock (single instance, download in progress, ...).
+  LockHeld(String),
+  Io(String),
+  /// A request to the backend or a remote service failed.
+  Network(String),
+  /// An argument was rejected.
+  InvalidInput(String),
+  /// Not available on this platform, profile or mode.
+  Unsupported(String),
+  /// The caller has to confirm first (e.g. kill_port_owner).
+  ConfirmationRequired(String),
+  /// The operation was cancelled or superseded.
+  Cancelled,
+  Internal(String),
+}
+
+impl AppError {
+  /// Machine-readable code sent to the frontend.
+  pub fn code(&self) -> &'static str {
+    match self {
+      AppError::ResourceNotFound(_) => "RESOURCE_NOT_FOUND",
+      AppError::SpawnFailed(_) => "SPAWN_FAILED",
+      AppError::WrongArch(_) => "WRONG_ARCH",
+      AppError::PortInUse(_) => "PORT_IN_USE",
+      AppError::HealthTimeout(_) => "HEALTH_TIMEOUT",
+      AppError::LockHeld(_) => "LOCK_HELD",
+      AppError::Io(_) => "IO",
+      AppError::Network(_) => "NETWORK",
+      AppError::InvalidInput(_) => "INVALID_INPUT",
+      AppError::Unsupported(_) => "UNSUPPORTED",
+      AppError::ConfirmationRequired(_) => "CONFIRMATION_REQUIRED",
+      AppError::Cancelled => "CANCELLED",
+      AppError::Internal(_) => "INTERNAL",
+    }
+  }
+
+  pub fn message(&self) -> &str {
+    match self {
+      AppError::ResourceNotFound(m)
+      | AppError::SpawnFailed(m)
+      | AppError::WrongArch(m)
+      | AppError::PortInUse(m)
+      | AppError::HealthTimeout(m)
+      | AppError::LockHeld(m)
+      | AppError::Io(m)
+      | AppError::Network(m)
+      | AppError::InvalidInput(m)
+      | AppError::Unsupported(m)
+      | AppError::ConfirmationRequired(m)
+      | AppError::Internal(m) => m,
+      AppError::Cancelled => "cancelled",
+    }
+  }
+}
+
+impl std::fmt::Display for AppError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}", self.code(), self.message())
+  }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_struct("AppError", 2)?;
+    s.serialize_field("code", self.code())?;
+    s.serialize_field("message", self.message())?;
+    s.end()
+  }
+}
+
+impl From<String> for AppError {
+  fn from(message: String) -> Self {
+    AppError::Internal(message)
+  }
+}
+
+impl From<&str> for AppError {
+  fn from(message: &str) -> Self {
+    AppError::Internal(message.to_string())
+  }
+}
+
+impl From<std::io::Error> for AppError {
+  fn from(e: std::io::Error) -> Self {
+    match e.kind() {
+      std::io::ErrorKind::NotFound => AppError::ResourceNotFound(e.to_string()),
+      _ => AppError::Io(e.to_string()),
+    }
+  }
+}
+
+impl From<reqwest::Error> for AppError {
+  fn from(e: reqwest::Error) -> Self {
+    AppError::Network(e.to_string())
+  }
+}
+
+/// For String-returning helpers that call AppError-returning ones.
+impl From<AppError> for String {
+  fn from(e: AppError) -> Self {
+    e.to_string()
+  }
+}
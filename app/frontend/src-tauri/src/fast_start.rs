@@ -0,0 +1,146 @@
+// Cold-start fast path. With settings.backend_keep_alive, a clean exit leaves the READY backend running
+// and records its PID/port; a relaunch within FAST_START_MAX_AGE_SECS validates that record with a
+// single health request instead of running the full probe / port check / spawn flow.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tauri::Manager;
+
+use crate::BackendState;
+use crate::settings::SettingsState;
+
+const READY_CACHE_FILE_NAME: &str = "backend_ready.json";
+const FAST_START_MAX_AGE_SECS: u64 = 120;
+const FAST_START_HEALTH_TIMEOUT_MS: u64 = 1_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadyCache {
+  pid: u32,
+  port: u16,
+  build_id: String,
+  exited_at: u64,
+  /// The left-running backend's shutdown token, so the next session can still stop it gracefully.
+  #[serde(default)]
+  shutdown_token: Option<String>,
+}
+
+fn ready_cache_path() -> PathBuf {
+  crate::app_base_dir().join(READY_CACHE_FILE_NAME)
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn process_alive(pid: u32) -> bool {
+  let pid = Pid::from_u32(pid);
+  let mut sys = System::new();
+  sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+  sys.process(pid).is_some()
+}
+
+/// Called on clean exit. With keep-alive, a READY backend is left running and recorded for the next
+/// launch; otherwise the backend we own (spawned or adopted) is stopped.
+pub(crate) fn on_exit(app: &tauri::AppHandle, state: &BackendState) {
+  let keep_alive = app.state::<SettingsState>().inner.lock().is_ok_and(|s| s.backend_keep_alive);
+  let Ok(mut g) = state.inner.lock() else {
+    return;
+  };
+  let pid = g.child.as_ref().map(|c| c.id()).or(g.adopted_pid);
+  if !keep_alive {
+    let _ = std::fs::remove_file(ready_cache_path());
+    if let Some(mut child) = g.child.take() {
+      let _ = child.kill();
+    } else if let Some(pid) = g.adopted_pid.take() {
+      kill_pid(pid);
+    }
+    return;
+  }
+  let cache = match pid {
+    Some(pid) if crate::is_up(&g.status) => ReadyCache {
+      pid,
+      port: crate::backend_port(),
+      build_id: std::env!("BUILD_ID").to_string(),
+      exited_at: now_secs(),
+      shutdown_token: Some(crate::shutdown_token()),
+    },
+    _ => {
+      let _ = std::fs::remove_file(ready_cache_path());
+      return;
+    }
+  };
+  // Dropping the Child handle does not kill the process.
+  g.child = None;
+  match serde_json::to_vec(&cache) {
+    Ok(json) => match std::fs::write(ready_cache_path(), json) {
+      Ok(()) => crate::app_log(&format!("fast start: left backend pid={} running (keep-alive)", cache.pid)),
+      Err(e) => crate::app_log(&format!("fast start: could not write ready cache: {}", e)),
+    },
+    Err(e) => crate::app_log(&format!("fast start: could not encode ready cache: {}", e)),
+  }
+}
+
+/// Kill an adopted backend by PID (no Child handle for it).
+pub(crate) fn kill_pid(pid: u32) {
+  let mut sys = System::new();
+  sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), true);
+  if let Some(p) = sys.process(Pid::from_u32(pid)) {
+    p.kill();
+  }
+}
+
+/// Reuse the backend recorded by on_exit if it is recent, from this build, on the same port, still
+/// alive and healthy. On success the state is READY and the PID is adopted. The cache is one-shot.
+pub(crate) async fn try_fast_start(state: &BackendState, generation: u64) -> bool {
+  let path = ready_cache_path();
+  let Ok(bytes) = std::fs::read(&path) else {
+    return false;
+  };
+  let _ = std::fs::remove_file(&path);
+  let cache: ReadyCache = match serde_json::from_slice(&bytes) {
+    Ok(c) => c,
+    Err(_) => return false,
+  };
+
+  let age = now_secs().saturating_sub(cache.exited_at);
+  let skip = if age > FAST_START_MAX_AGE_SECS {
+    Some(format!("cache is {}s old", age))
+  } else if cache.build_id != std::env!("BUILD_ID") {
+    Some("different build".to_string())
+  } else if cache.port != crate::backend_port() {
+    Some(format!("cached port {} != {}", cache.port, crate::backend_port()))
+  } else if !process_alive(cache.pid) {
+    Some(format!("pid {} is gone", cache.pid))
+  } else {
+    None
+  };
+  if let Some(why) = skip {
+    crate::backend_autostart_log(&format!("fast start: skipped ({})", why));
+    return false;
+  }
+
+  let healthy = crate::http_client()
+    .get(crate::health_url())
+    .timeout(Duration::from_millis(FAST_START_HEALTH_TIMEOUT_MS))
+    .send()
+    .await
+    .is_ok_and(|r| r.status().is_success());
+  if !healthy {
+    crate::backend_autostart_log(&format!("fast start: pid {} alive but health failed", cache.pid));
+    return false;
+  }
+  let Some(mut g) = state.lock_current(generation) else {
+    return true;
+  };
+  g.adopted_pid = Some(cache.pid);
+  if let Some(token) = cache.shutdown_token {
+    crate::adopt_shutdown_token(token);
+  }
+  g.set_status("READY", None);
+  drop(g);
+  crate::backend_autostart_log(&format!("fast start: reused pid {} ({}s after exit)", cache.pid, age));
+  crate::app_log("backend autostart: READY (fast start)");
+  true
+}
@@ -0,0 +1,89 @@
+// Opening app folders in the system file manager: Explorer on Windows, Finder (`open`) on macOS,
+// xdg-open elsewhere. reveal_file selects the file where the platform supports it (Explorer /select,
+// `open -R`, the freedesktop FileManager1 D-Bus interface) and opens its folder otherwise.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::AppError;
+
+fn spawn(program: &str, args: &[&std::ffi::OsStr]) -> Result<(), AppError> {
+  Command::new(program)
+    .args(args)
+    .spawn()
+    .map(|_| ())
+    .map_err(|e| AppError::SpawnFailed(format!("{}: {}", program, e)))
+}
+
+fn open_dir(dir: &Path) -> Result<(), AppError> {
+  #[cfg(target_os = "windows")]
+  let program = "explorer";
+  #[cfg(target_os = "macos")]
+  let program = "open";
+  #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+  let program = "xdg-open";
+  spawn(program, &[dir.as_os_str()])
+}
+
+#[cfg(target_os = "windows")]
+fn select(file: &Path) -> Result<(), AppError> {
+  use std::os::windows::process::CommandExt;
+  // Explorer does not understand the \\?\ prefix canonicalize() adds, and wants the path quoted
+  // after the comma rather than the whole argument.
+  let file = file.to_string_lossy();
+  Command::new("explorer")
+    .raw_arg(format!("/select,\"{}\"", file.strip_prefix(r"\\?\").unwrap_or(&file)))
+    .spawn()
+    .map(|_| ())
+    .map_err(|e| AppError::SpawnFailed(format!("explorer: {}", e)))
+}
+
+#[cfg(target_os = "macos")]
+fn select(file: &Path) -> Result<(), AppError> {
+  spawn("open", &["-R".as_ref(), file.as_os_str()])
+}
+
+/// Ask the running file manager to highlight the file; not every desktop implements FileManager1,
+/// so fall back to opening the containing folder.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn select(file: &Path) -> Result<(), AppError> {
+  let shown = reqwest::Url::from_file_path(file).ok().is_some_and(|url| {
+    Command::new("dbus-send")
+      .args([
+        "--session",
+        "--dest=org.freedesktop.FileManager1",
+        "--type=method_call",
+        "/org/freedesktop/FileManager1",
+        "org.freedesktop.FileManager1.ShowItems",
+      ])
+      .arg(format!("array:string:{}", url))
+      .arg("string:")
+      .status()
+      .is_ok_and(|s| s.success())
+  });
+  if shown {
+    return Ok(());
+  }
+  open_dir(file.parent().unwrap_or(file))
+}
+
+/// Open the logs folder in the system file manager.
+#[tauri::command]
+pub fn open_logs_folder() -> Result<(), AppError> {
+  let path = crate::logs_dir();
+  std::fs::create_dir_all(&path).map_err(|e| AppError::Io(format!("{}: {}", path.display(), e)))?;
+  open_dir(&path)
+}
+
+/// Show `path` (a file under the logs folder) selected in the system file manager.
+#[tauri::command]
+pub fn reveal_file(path: PathBuf) -> Result<(), AppError> {
+  let file = path
+    .canonicalize()
+    .map_err(|e| AppError::ResourceNotFound(format!("{}: {}", path.display(), e)))?;
+  let logs = crate::logs_dir().canonicalize()?;
+  if !file.starts_with(&logs) || !file.is_file() {
+    return Err(AppError::InvalidInput(format!("not a file in the logs folder: {}", path.display())));
+  }
+  select(&file)
+}
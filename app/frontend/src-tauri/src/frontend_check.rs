@@ -0,0 +1,84 @@
+// Frontend build check. build.rs embeds the BUILD_ID of the bundle in ../dist; the webview reports the
+// BUILD_ID it was built with via frontend_handshake. A mismatch (stale webview cache, partial update)
+// clears browsing data and reloads, at most once per session. localStorage survives the clear: the
+// frontend hands it over first and the next handshake returns it for restoring.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::error::AppError;
+
+const EXPECTED_FRONTEND_BUILD_ID: &str = std::env!("FRONTEND_BUILD_ID");
+const FRONTEND_BUNDLE_HASH: &str = std::env!("FRONTEND_BUNDLE_HASH");
+
+#[derive(Default)]
+pub struct FrontendCheckState {
+  /// Set once a cache clear + reload has been triggered; further mismatches are only logged.
+  reloaded: Mutex<bool>,
+  stashed_local_storage: Mutex<Option<HashMap<String, String>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HandshakeResult {
+  pub ok: bool,
+  pub expected: &'static str,
+  pub reported: String,
+  /// Frontend should call frontend_clear_cache_and_reload.
+  pub reload_required: bool,
+  /// localStorage stashed before the last reload, to be written back by the frontend.
+  pub restore: Option<HashMap<String, String>>,
+}
+
+/// Called by the frontend at startup with its compiled-in BUILD_ID. Always ok in debug builds, where the
+/// webview is served by the dev server rather than ../dist.
+#[tauri::command]
+pub fn frontend_handshake(state: tauri::State<FrontendCheckState>, build_id: String) -> HandshakeResult {
+  let restore = state.stashed_local_storage.lock().ok().and_then(|mut g| g.take());
+  let ok = cfg!(debug_assertions) || build_id == EXPECTED_FRONTEND_BUILD_ID;
+  let already_reloaded = state.reloaded.lock().map(|g| *g).unwrap_or(true);
+  if ok {
+    if already_reloaded {
+      crate::app_log(&format!("frontend: build {} ok after cache clear", build_id));
+    }
+  } else if already_reloaded {
+    crate::app_log(&format!(
+      "frontend: build mismatch persists after cache clear (webview={} expected={} bundle={})",
+      build_id, EXPECTED_FRONTEND_BUILD_ID, FRONTEND_BUNDLE_HASH
+    ));
+  } else {
+    crate::app_log(&format!(
+      "frontend: build mismatch (webview={} expected={} bundle={}), clearing cache",
+      build_id, EXPECTED_FRONTEND_BUILD_ID, FRONTEND_BUNDLE_HASH
+    ));
+  }
+  HandshakeResult {
+    ok,
+    expected: EXPECTED_FRONTEND_BUILD_ID,
+    reported: build_id,
+    reload_required: !ok && !already_reloaded,
+    restore,
+  }
+}
+
+/// Stash `local_storage`, clear the webview's browsing data and reload it. Only honored once per session.
+#[tauri::command]
+pub fn frontend_clear_cache_and_reload(
+  webview: tauri::Webview,
+  state: tauri::State<FrontendCheckState>,
+  local_storage: HashMap<String, String>,
+) -> Result<(), AppError> {
+  {
+    let mut reloaded = state.reloaded.lock().map_err(|e| e.to_string())?;
+    if *reloaded {
+      return Err(AppError::LockHeld("cache already cleared this session".to_string()));
+    }
+    *reloaded = true;
+  }
+  if let Ok(mut g) = state.stashed_local_storage.lock() {
+    *g = Some(local_storage);
+  }
+  webview.clear_all_browsing_data().map_err(|e| e.to_string())?;
+  webview.reload().map_err(|e| e.to_string())?;
+  Ok(())
+}
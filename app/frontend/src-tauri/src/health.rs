@@ -0,0 +1,292 @@
+// Health-check timing and endpoints. Defaults are the HEALTH_* constants; settings.health overrides
+// them and AI_MENTOR_HEALTH_{POLL_MS,TIMEOUT_MS,REQUEST_TIMEOUT_MS,PATH,READY_PATH} override settings.
+// Values are resolved once at startup and again when set_health_config saves new settings.
+//
+// The health path answers liveness (the process is up); readiness comes from ready_path, which
+// defaults to the same document. A live backend whose readiness response is not a 2xx or lists
+// components that are not ready is DEGRADED, with those components as the reason.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tauri::Manager;
+
+use crate::error::AppError;
+use crate::launcher::Readiness;
+use crate::settings::SettingsState;
+use crate::BackendState;
+
+const MIN_POLL_MS: u64 = 50;
+const MIN_REQUEST_TIMEOUT_MS: u64 = 100;
+/// How often a READY / DEGRADED backend's readiness is re-checked.
+const READINESS_WATCH_MS: u64 = 5_000;
+/// Status values (any case) that count as ready, for the document and for each component.
+const READY_WORDS: &[&str] = &["ok", "ready", "healthy", "up", "pass"];
+
+/// Stored in settings; unset fields use the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthSettings {
+  pub poll_ms: Option<u64>,
+  /// How long a starting backend gets to answer its first health poll.
+  pub timeout_ms: Option<u64>,
+  /// Timeout of each health request.
+  pub request_timeout_ms: Option<u64>,
+  /// Backend health (liveness) endpoint, e.g. "/health".
+  pub path: Option<String>,
+  /// Readiness endpoint, e.g. "/ready". None = readiness is read from the health document.
+  pub ready_path: Option<String>,
+}
+
+/// Effective values, reported in the `backend-status` payload.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthConfig {
+  pub poll_ms: u64,
+  pub timeout_ms: u64,
+  pub request_timeout_ms: u64,
+  pub path: String,
+  pub ready_path: String,
+}
+
+impl HealthConfig {
+  pub fn poll(&self) -> Duration {
+    Duration::from_millis(self.poll_ms)
+  }
+
+  pub fn request_timeout(&self) -> Duration {
+    Duration::from_millis(self.request_timeout_ms)
+  }
+}
+
+static CONFIG: RwLock<Option<HealthConfig>> = RwLock::new(None);
+
+fn env_u64(var: &str) -> Option<u64> {
+  let value = std::env::var(var).ok()?;
+  match value.trim().parse() {
+    Ok(v) => Some(v),
+    Err(_) => {
+      crate::app_log(&format!("health: ignoring {}={:?} (not a number)", var, value));
+      None
+    }
+  }
+}
+
+fn validate_path(path: &str) -> Result<(), AppError> {
+  if path.starts_with('/') && !path.contains(char::is_whitespace) {
+    Ok(())
+  } else {
+    Err(AppError::InvalidInput(format!("health path must start with '/': {:?}", path)))
+  }
+}
+
+/// `var` from the environment, else `setting`, else `default`; invalid values are logged and skipped.
+fn resolve_path(var: &str, setting: &Option<String>, default: &str) -> String {
+  std::env::var(var)
+    .ok()
+    .or_else(|| setting.clone())
+    .filter(|p| match validate_path(p) {
+      Ok(()) => true,
+      Err(e) => {
+        crate::app_log(&format!("health: {}, using {}", e, default));
+        false
+      }
+    })
+    .unwrap_or_else(|| default.to_string())
+}
+
+/// Defaults, then settings, then environment; out-of-range values are clamped.
+pub fn resolve(settings: &HealthSettings) -> HealthConfig {
+  let path = resolve_path("AI_MENTOR_HEALTH_PATH", &settings.path, crate::HEALTH_PATH);
+  let ready_path = resolve_path("AI_MENTOR_HEALTH_READY_PATH", &settings.ready_path, &path);
+  let poll_ms = env_u64("AI_MENTOR_HEALTH_POLL_MS")
+    .or(settings.poll_ms)
+    .unwrap_or(crate::HEALTH_POLL_MS)
+    .max(MIN_POLL_MS);
+  HealthConfig {
+    poll_ms,
+    timeout_ms: env_u64("AI_MENTOR_HEALTH_TIMEOUT_MS")
+      .or(settings.timeout_ms)
+      .unwrap_or(crate::HEALTH_TIMEOUT_MS)
+      .max(poll_ms),
+    request_timeout_ms: env_u64("AI_MENTOR_HEALTH_REQUEST_TIMEOUT_MS")
+      .or(settings.request_timeout_ms)
+      .unwrap_or(crate::HEALTH_REQUEST_TIMEOUT_MS)
+      .max(MIN_REQUEST_TIMEOUT_MS),
+    path,
+    ready_path,
+  }
+}
+
+/// Current values (defaults plus environment until init runs).
+pub fn config() -> HealthConfig {
+  CONFIG
+    .read()
+    .ok()
+    .and_then(|g| g.clone())
+    .unwrap_or_else(|| resolve(&HealthSettings::default()))
+}
+
+fn apply(config: HealthConfig) {
+  if let Ok(mut g) = CONFIG.write() {
+    *g = Some(config);
+  }
+}
+
+pub fn init(settings: &HealthSettings) {
+  let config = resolve(settings);
+  crate::app_log(&format!("health: {:?}", config));
+  apply(config);
+}
+
+#[tauri::command]
+pub fn get_health_config() -> HealthConfig {
+  config()
+}
+
+/// Save health settings (None fields = default) and apply them to the next check. Environment
+/// overrides still win. Returns the effective values.
+#[tauri::command]
+pub fn set_health_config(settings: tauri::State<SettingsState>, health: HealthSettings) -> Result<HealthConfig, AppError> {
+  for p in [&health.path, &health.ready_path].into_iter().flatten() {
+    validate_path(p)?;
+  }
+  settings.update(|s| s.health = health.clone())?;
+  let config = resolve(&health);
+  crate::app_log(&format!("health: updated {:?}", config));
+  apply(config.clone());
+  Ok(config)
+}
+
+fn ready_word(value: &serde_json::Value) -> Option<bool> {
+  match value {
+    serde_json::Value::Bool(b) => Some(*b),
+    serde_json::Value::String(s) => Some(READY_WORDS.contains(&s.to_ascii_lowercase().as_str())),
+    serde_json::Value::Object(o) => ["status", "ready", "ok"].iter().find_map(|k| o.get(*k).and_then(ready_word)),
+    _ => None,
+  }
+}
+
+/// Readiness from a response of a backend that answered. A JSON document may carry an overall
+/// `status` and per-component entries under `components` or `checks` (a status string, a bool, or an
+/// object with `status` / `ready`); anything not ready there, or a non-2xx, makes it Degraded.
+pub fn parse_readiness(success: bool, body: &str) -> Readiness {
+  let mut ready = success;
+  let mut unready = Vec::new();
+  if let Ok(serde_json::Value::Object(doc)) = serde_json::from_str::<serde_json::Value>(body) {
+    if let Some(status) = doc.get("status").and_then(ready_word) {
+      ready &= status;
+    }
+    for key in ["components", "checks"] {
+      let Some(components) = doc.get(key).and_then(|c| c.as_object()) else {
+        continue;
+      };
+      for (name, value) in components {
+        if ready_word(value) == Some(false) {
+          unready.push(name.clone());
+        }
+      }
+    }
+  }
+  unready.sort();
+  unready.dedup();
+  if ready && unready.is_empty() {
+    Readiness::Ready
+  } else {
+    Readiness::Degraded(unready)
+  }
+}
+
+/// Status for a live backend: READY, or DEGRADED with the unready components (comma-separated) as
+/// reason. None when it no longer answers.
+pub(crate) fn status_for(readiness: Readiness) -> Option<(&'static str, Option<String>)> {
+  match readiness {
+    Readiness::Ready => Some(("READY", None)),
+    Readiness::Degraded(components) => {
+      Some(("DEGRADED", (!components.is_empty()).then(|| components.join(","))))
+    }
+    Readiness::Down => None,
+  }
+}
+
+/// Re-check readiness of every READY / DEGRADED service and move it between the two. A service that
+/// stops answering is left to the exit watch and the spawn flows, except a backend of a profile
+/// without autostart, which has neither: it goes NOT_READY:UNREACHABLE and is checked until it
+/// answers again.
+pub fn start_readiness_watch(app: &tauri::AppHandle) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tokio::time::sleep(Duration::from_millis(READINESS_WATCH_MS)).await;
+      let registry = app.state::<crate::services::ServiceRegistry>();
+      for service in registry.all() {
+        let state: &Arc<BackendState> = &service.state;
+        let unmanaged = service.name == crate::services::BACKEND_SERVICE_NAME && !crate::profiles::active().autostart;
+        let watched = |status: &str, reason: Option<&str>| {
+          crate::is_up(status) || (unmanaged && crate::profiles::watch_unreachable(status, reason))
+        };
+        let generation = match state.inner.lock() {
+          Ok(g) if watched(&g.status, g.not_ready_reason.as_deref()) => g.generation,
+          _ => continue,
+        };
+        let (status, reason) = match status_for(state.probe.readiness().await) {
+          Some(next) => next,
+          None if unmanaged => ("NOT_READY", Some(crate::profiles::NOT_READY_REASON_UNREACHABLE.to_string())),
+          None => continue,
+        };
+        if let Some(mut g) = state.lock_current(generation) {
+          if watched(&g.status, g.not_ready_reason.as_deref()) {
+            g.set_status(status, reason);
+          }
+        }
+      }
+    }
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn plain_ok_is_ready() {
+    assert_eq!(parse_readiness(true, r#"{"status":"ok"}"#), Readiness::Ready);
+    assert_eq!(parse_readiness(true, "ok"), Readiness::Ready);
+  }
+
+  #[test]
+  fn non_success_is_degraded_without_components() {
+    assert_eq!(parse_readiness(false, r#"{"status":"ok"}"#), Readiness::Degraded(Vec::new()));
+  }
+
+  #[test]
+  fn overall_status_not_ready_is_degraded() {
+    assert_eq!(parse_readiness(true, r#"{"status":"degraded"}"#), Readiness::Degraded(Vec::new()));
+  }
+
+  #[test]
+  fn unready_components_are_named_sorted_once() {
+    let body = r#"{"status":"ok","components":{"vector":"down","model":"loading","db":"ok"},
+      "checks":{"model":{"status":"loading"},"cache":{"ready":false},"disk":true}}"#;
+    assert_eq!(
+      parse_readiness(true, body),
+      Readiness::Degraded(vec!["cache".to_string(), "model".to_string(), "vector".to_string()])
+    );
+  }
+
+  #[test]
+  fn ready_components_keep_it_ready() {
+    let body = r#"{"status":"healthy","components":{"db":"UP","model":{"ok":true}},"checks":{"disk":"pass"}}"#;
+    assert_eq!(parse_readiness(true, body), Readiness::Ready);
+  }
+
+  #[test]
+  fn status_for_maps_components_to_reason() {
+    assert_eq!(status_for(Readiness::Ready), Some(("READY", None)));
+    assert_eq!(status_for(Readiness::Degraded(Vec::new())), Some(("DEGRADED", None)));
+    assert_eq!(
+      status_for(Readiness::Degraded(vec!["db".to_string(), "model".to_string()])),
+      Some(("DEGRADED", Some("db,model".to_string())))
+    );
+    assert_eq!(status_for(Readiness::Down), None);
+  }
+}
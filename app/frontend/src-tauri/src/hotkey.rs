@@ -0,0 +1,100 @@
+// Global hotkey that summons or hides the main window. The shortcut comes from settings.summon_hotkey
+// (None = DEFAULT_SUMMON_HOTKEY, "" = disabled). Summoning emits `mentor-summoned` so the frontend can
+// focus the prompt input; hiding leaves the app in the tray.
+
+use tauri::{Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::error::AppError;
+use crate::settings::SettingsState;
+
+pub const DEFAULT_SUMMON_HOTKEY: &str = "CmdOrCtrl+Shift+M";
+pub const MENTOR_SUMMONED_EVENT: &str = "mentor-summoned";
+
+/// Configured shortcut string, or None when the hotkey is disabled.
+fn configured(settings: &crate::settings::Settings) -> Option<String> {
+  match settings.summon_hotkey.as_deref() {
+    None => Some(DEFAULT_SUMMON_HOTKEY.to_string()),
+    Some("") => None,
+    Some(s) => Some(s.to_string()),
+  }
+}
+
+pub(crate) fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+  tauri_plugin_global_shortcut::Builder::new()
+    .with_handler(|app, _shortcut, event| {
+      if event.state() == ShortcutState::Pressed {
+        toggle(app);
+      }
+    })
+    .build()
+}
+
+/// Show, unminimize and focus the main window, then tell the frontend.
+pub(crate) fn summon(app: &tauri::AppHandle) {
+  let Some(main) = app.get_webview_window(crate::MAIN_WINDOW_LABEL) else {
+    return;
+  };
+  let _ = main.show();
+  let _ = main.unminimize();
+  let _ = main.set_focus();
+  let _ = app.emit_to(crate::MAIN_WINDOW_LABEL, MENTOR_SUMMONED_EVENT, ());
+}
+
+/// Visible and focused -> hide to tray; otherwise summon.
+pub(crate) fn toggle(app: &tauri::AppHandle) {
+  let Some(main) = app.get_webview_window(crate::MAIN_WINDOW_LABEL) else {
+    return;
+  };
+  let visible = main.is_visible().unwrap_or(false) && !main.is_minimized().unwrap_or(false);
+  if visible && main.is_focused().unwrap_or(false) {
+    let _ = main.hide();
+  } else {
+    summon(app);
+  }
+}
+
+/// Replace whatever is registered with `shortcut` (None = unregister only).
+fn apply(app: &tauri::AppHandle, shortcut: Option<&str>) -> Result<(), String> {
+  let gs = app.global_shortcut();
+  gs.unregister_all().map_err(|e| e.to_string())?;
+  if let Some(s) = shortcut {
+    let parsed: Shortcut = s.parse().map_err(|e| format!("invalid shortcut {:?}: {}", s, e))?;
+    gs.register(parsed).map_err(|e| e.to_string())?;
+  }
+  Ok(())
+}
+
+/// Register the configured hotkey at startup. Failures (e.g. taken by another app) are only logged.
+pub(crate) fn register_from_settings(app: &tauri::AppHandle) {
+  let shortcut = configured(&app.state::<SettingsState>().get());
+  match apply(app, shortcut.as_deref()) {
+    Ok(()) => crate::app_log(&format!("hotkey: {}", shortcut.as_deref().unwrap_or("disabled"))),
+    Err(e) => crate::app_log(&format!("hotkey: registration failed: {}", e)),
+  }
+}
+
+/// Change the summon hotkey: None restores the default, "" disables it. Persisted only if it registers.
+#[tauri::command]
+pub fn set_summon_hotkey(
+  app: tauri::AppHandle,
+  settings: tauri::State<SettingsState>,
+  shortcut: Option<String>,
+) -> Result<Option<String>, AppError> {
+  let mut next = settings.get();
+  next.summon_hotkey = shortcut.map(|s| s.trim().to_string());
+  let effective = configured(&next);
+  if let Err(e) = apply(&app, effective.as_deref()) {
+    // Put the previous binding back.
+    let _ = apply(&app, configured(&settings.get()).as_deref());
+    return Err(AppError::InvalidInput(e));
+  }
+  settings.update(|s| s.summon_hotkey = next.summon_hotkey.clone())?;
+  Ok(effective)
+}
+
+/// The active summon hotkey, or None when disabled.
+#[tauri::command]
+pub fn get_summon_hotkey(settings: tauri::State<SettingsState>) -> Option<String> {
+  configured(&settings.get())
+}
@@ -0,0 +1,165 @@
+// Idle suspend (settings.idle_suspend, off by default). A managed backend that has seen no traffic
+// for idle_minutes while every window is hidden or minimized is stopped gracefully and marked
+// NOT_READY:IDLE_SUSPENDED, giving its model memory back. The next interaction (a window gaining
+// focus, begin_backend_activity, wake_backend) runs the autostart flow again, so the UI sees STARTING
+// and then READY through the usual status events.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::Manager;
+
+use crate::BackendState;
+use crate::error::AppError;
+use crate::maintenance::MaintenanceState;
+use crate::settings::SettingsState;
+
+pub const DEFAULT_IDLE_MINUTES: u64 = 30;
+pub const NOT_READY_REASON_IDLE_SUSPENDED: &str = "IDLE_SUSPENDED";
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Stored in settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdleSettings {
+  pub enabled: bool,
+  /// None = DEFAULT_IDLE_MINUTES.
+  pub idle_minutes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IdleStatus {
+  pub settings: IdleSettings,
+  pub idle_secs: u64,
+  pub suspended: bool,
+}
+
+static LAST_ACTIVITY_MS: AtomicU64 = AtomicU64::new(0);
+static WAKING: AtomicBool = AtomicBool::new(false);
+
+/// Record backend traffic or user interaction; the idle timer starts over.
+pub(crate) fn touch() {
+  LAST_ACTIVITY_MS.store(crate::status_history::now_ms(), Ordering::Relaxed);
+}
+
+fn idle_for() -> Duration {
+  let last = LAST_ACTIVITY_MS.load(Ordering::Relaxed);
+  Duration::from_millis(crate::status_history::now_ms().saturating_sub(last))
+}
+
+pub(crate) fn suspended(state: &BackendState) -> bool {
+  state
+    .inner
+    .lock()
+    .is_ok_and(|g| g.status == "NOT_READY" && g.not_ready_reason.as_deref() == Some(NOT_READY_REASON_IDLE_SUSPENDED))
+}
+
+/// No window the user can currently see.
+fn windows_hidden(app: &tauri::AppHandle) -> bool {
+  app
+    .webview_windows()
+    .values()
+    .all(|w| !w.is_visible().unwrap_or(false) || w.is_minimized().unwrap_or(false))
+}
+
+async fn tick(app: &tauri::AppHandle) {
+  let settings = app.state::<SettingsState>().get().idle_suspend;
+  let state = app.state::<Arc<BackendState>>().inner().clone();
+  let managed = crate::profiles::active().autostart && !crate::service_mode::is_enabled(app);
+  let up = state.inner.lock().is_ok_and(|g| crate::is_up(&g.status));
+  if !settings.enabled || !managed || !up {
+    return;
+  }
+  // Time with a visible window or a request in flight does not count as idle.
+  if !windows_hidden(app) || app.state::<MaintenanceState>().busy() {
+    touch();
+    return;
+  }
+  let limit = Duration::from_secs(settings.idle_minutes.unwrap_or(DEFAULT_IDLE_MINUTES) * 60);
+  if idle_for() < limit {
+    return;
+  }
+  crate::app_log(&format!("idle: no activity for {} min, suspending backend", idle_for().as_secs() / 60));
+  match crate::stop_owned_gracefully(&state).await {
+    Ok(()) => {
+      if let Ok(mut g) = state.inner.lock() {
+        g.set_status("NOT_READY", Some(NOT_READY_REASON_IDLE_SUSPENDED.to_string()));
+      }
+    }
+    Err(e) => crate::app_log(&format!("idle: suspend failed: {}", e)),
+  }
+}
+
+/// Check for idleness every CHECK_INTERVAL_SECS for the rest of the session.
+pub fn start(app: &tauri::AppHandle) {
+  touch();
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+      tick(&app).await;
+    }
+  });
+}
+
+/// Restart a suspended backend and wait for the outcome. Ok(None) when it was not suspended or a
+/// wake is already under way.
+async fn wake(app: &tauri::AppHandle) -> Result<Option<String>, AppError> {
+  touch();
+  let state = app.state::<Arc<BackendState>>().inner().clone();
+  if !suspended(&state) || WAKING.swap(true, Ordering::SeqCst) {
+    return Ok(None);
+  }
+  crate::app_log("idle: waking backend");
+  let result = crate::rerun_autostart(app, &state).await;
+  WAKING.store(false, Ordering::SeqCst);
+  result.map(Some)
+}
+
+/// A window gained focus or a request is about to start: reset the timer and wake the backend in
+/// the background if it is suspended.
+pub(crate) fn on_interaction(app: &tauri::AppHandle) {
+  touch();
+  if !suspended(&app.state::<Arc<BackendState>>()) {
+    return;
+  }
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    if let Err(e) = wake(&app).await {
+      crate::app_log(&format!("idle: wake failed: {}", e));
+    }
+  });
+}
+
+#[tauri::command]
+pub fn get_idle_suspend(settings: tauri::State<SettingsState>, state: tauri::State<Arc<BackendState>>) -> IdleStatus {
+  IdleStatus {
+    settings: settings.get().idle_suspend,
+    idle_secs: idle_for().as_secs(),
+    suspended: suspended(&state),
+  }
+}
+
+#[tauri::command]
+pub fn set_idle_suspend(settings: tauri::State<SettingsState>, idle: IdleSettings) -> Result<IdleSettings, AppError> {
+  if idle.idle_minutes == Some(0) {
+    return Err(AppError::InvalidInput("idle_minutes must be at least 1".to_string()));
+  }
+  settings.update(|s| s.idle_suspend = idle.clone())?;
+  crate::app_log(&format!("idle: {:?}", idle));
+  Ok(idle)
+}
+
+/// Make sure the backend is running before talking to it: wakes a suspended backend and resolves
+/// with the resulting status (the current status when it was not suspended).
+#[tauri::command]
+pub async fn wake_backend(app: tauri::AppHandle) -> Result<String, AppError> {
+  crate::command_stats::track(&app, "wake_backend", async {
+    match wake(&app).await? {
+      Some(status) => Ok(status),
+      None => Ok(crate::backend_status_string(&app.state::<Arc<BackendState>>())),
+    }
+  })
+  .await
+}
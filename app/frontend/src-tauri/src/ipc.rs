@@ -0,0 +1,210 @@
+// Local automation IPC for scripts (PowerShell, shell): a per-user named pipe on Windows
+// (\\.\pipe\ai-mentor-<user>), a Unix socket (<base>/ipc.sock) elsewhere. One JSON request per line:
+//   {"token":"<contents of <base>/ipc.token>","command":"status"}
+//   {"token":"...","command":"restart"}
+//   {"token":"...","command":"export-diagnostics","dest":"C:\\tmp\\diag.zip","anonymize":true}
+// and one JSON response per line: {"ok":true,"result":...} or {"ok":false,"error":"..."}.
+// The token is regenerated every session and only readable by the user, so only processes running
+// as that user can drive the app.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::Manager;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::BackendState;
+
+const TOKEN_FILE_NAME: &str = "ipc.token";
+#[cfg(unix)]
+const SOCKET_FILE_NAME: &str = "ipc.sock";
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct Request {
+  token: String,
+  command: String,
+  dest: Option<String>,
+  anonymize: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+  ok: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  result: Option<serde_json::Value>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  error: Option<String>,
+}
+
+impl Response {
+  fn ok(result: serde_json::Value) -> Self {
+    Self {
+      ok: true,
+      result: Some(result),
+      error: None,
+    }
+  }
+
+  fn err(error: impl Into<String>) -> Self {
+    Self {
+      ok: false,
+      result: None,
+      error: Some(error.into()),
+    }
+  }
+}
+
+pub fn token_path() -> PathBuf {
+  crate::app_base_dir().join(TOKEN_FILE_NAME)
+}
+
+#[cfg(windows)]
+fn pipe_name() -> String {
+  let user = std::env::var("USERNAME").unwrap_or_default();
+  let user: String = user.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-').collect();
+  format!(r"\\.\pipe\ai-mentor-{}", user)
+}
+
+#[cfg(unix)]
+fn socket_path() -> PathBuf {
+  crate::app_base_dir().join(SOCKET_FILE_NAME)
+}
+
+/// Owner-only permissions where the platform has them; %LOCALAPPDATA% is already per-user on Windows.
+fn restrict_to_owner(path: &std::path::Path) {
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+  }
+  #[cfg(not(unix))]
+  let _ = path;
+}
+
+fn write_token() -> Result<String, String> {
+  let mut bytes = [0u8; 32];
+  getrandom::fill(&mut bytes).map_err(|e| e.to_string())?;
+  let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+  let path = token_path();
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+  }
+  std::fs::write(&path, &token).map_err(|e| e.to_string())?;
+  restrict_to_owner(&path);
+  Ok(token)
+}
+
+fn token_matches(expected: &str, given: &str) -> bool {
+  expected.len() == given.len() && expected.bytes().zip(given.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+async fn dispatch(app: &tauri::AppHandle, req: Request) -> Response {
+  let state = app.state::<Arc<BackendState>>().inner().clone();
+  match req.command.as_str() {
+    "status" => Response::ok(serde_json::json!({ "status": crate::backend_status_string(&state) })),
+    "restart" => match crate::restart_backend(app, &state).await {
+      Ok(status) => Response::ok(serde_json::json!({ "status": status })),
+      Err(e) => Response::err(e),
+    },
+    "export-diagnostics" => {
+      let Some(dest) = req.dest else {
+        return Response::err("export-diagnostics requires \"dest\"");
+      };
+      let anonymize = req.anonymize.unwrap_or(true);
+      let status = crate::backend_status_string(&state);
+      let dest = PathBuf::from(dest);
+      let out = dest.clone();
+      let res = tauri::async_runtime::spawn_blocking(move || crate::diagnostics::write_bundle(&out, anonymize, status)).await;
+      match res {
+        Ok(Ok(())) => {
+          crate::app_log(&format!("ipc: exported diagnostics to {}", dest.display()));
+          Response::ok(serde_json::json!({ "path": dest.display().to_string() }))
+        }
+        Ok(Err(e)) => Response::err(e),
+        Err(e) => Response::err(e.to_string()),
+      }
+    }
+    other => Response::err(format!("unknown command: {}", other)),
+  }
+}
+
+/// Serve one connection: request lines until EOF; a bad token ends the connection.
+async fn handle_connection<S>(app: tauri::AppHandle, token: Arc<String>, stream: S)
+where
+  S: AsyncRead + AsyncWrite + Unpin,
+{
+  let (read, mut write) = tokio::io::split(stream);
+  let mut reader = BufReader::new(read);
+  loop {
+    let mut line = String::new();
+    match (&mut reader).take(MAX_REQUEST_BYTES).read_line(&mut line).await {
+      Ok(0) | Err(_) => return,
+      Ok(_) => {}
+    }
+    let (response, close) = match serde_json::from_str::<Request>(line.trim()) {
+      Ok(req) if !token_matches(&token, &req.token) => (Response::err("unauthorized"), true),
+      Ok(req) => {
+        crate::app_log(&format!("ipc: command {}", req.command));
+        (dispatch(&app, req).await, false)
+      }
+      Err(e) => (Response::err(format!("invalid request: {}", e)), false),
+    };
+    let mut out = serde_json::to_string(&response).unwrap_or_default();
+    out.push('\n');
+    if write.write_all(out.as_bytes()).await.is_err() || close {
+      return;
+    }
+  }
+}
+
+#[cfg(windows)]
+async fn serve(app: tauri::AppHandle, token: Arc<String>) -> std::io::Result<()> {
+  use tokio::net::windows::named_pipe::ServerOptions;
+  let name = pipe_name();
+  let mut server = ServerOptions::new().first_pipe_instance(true).create(&name)?;
+  crate::app_log(&format!("ipc: listening on {}", name));
+  loop {
+    server.connect().await?;
+    let connected = server;
+    server = ServerOptions::new().create(&name)?;
+    tauri::async_runtime::spawn(handle_connection(app.clone(), token.clone(), connected));
+  }
+}
+
+#[cfg(unix)]
+async fn serve(app: tauri::AppHandle, token: Arc<String>) -> std::io::Result<()> {
+  let path = socket_path();
+  let _ = std::fs::remove_file(&path);
+  let listener = tokio::net::UnixListener::bind(&path)?;
+  restrict_to_owner(&path);
+  crate::app_log(&format!("ipc: listening on {}", path.display()));
+  loop {
+    let (stream, _) = listener.accept().await?;
+    tauri::async_runtime::spawn(handle_connection(app.clone(), token.clone(), stream));
+  }
+}
+
+/// Write a fresh token and start serving in the background. Failures are logged; the GUI is unaffected.
+pub(crate) fn start(app: &tauri::AppHandle) {
+  let token = match write_token() {
+    Ok(t) => Arc::new(t),
+    Err(e) => {
+      crate::app_log(&format!("ipc: disabled, could not write token: {}", e));
+      return;
+    }
+  };
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    if let Err(e) = serve(app, token).await {
+      crate::app_log(&format!("ipc: stopped: {}", e));
+    }
+  });
+}
+
+/// Shutdown hook: invalidate the token (and remove the socket file).
+pub(crate) fn stop() {
+  let _ = std::fs::remove_file(token_path());
+  #[cfg(unix)]
+  let _ = std::fs::remove_file(socket_path());
+}
@@ -0,0 +1,255 @@
+// Launch at login: a per-user entry that starts the shell when the user logs in (Windows registry Run
+// key, macOS launchd agent, Linux XDG autostart .desktop file), so the backend is warm by the time
+// the mentor is needed. With `minimized` the entry passes --minimized and the shell starts in the
+// tray without the splash or main window. The entry itself is the state; nothing is kept in settings.
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::cli::MINIMIZED_FLAG;
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchAtLogin {
+  pub enabled: bool,
+  /// The entry starts the shell in the tray.
+  pub minimized: bool,
+  /// "registry" | "launchd" | "xdg-autostart"
+  pub mechanism: &'static str,
+  /// Registry value or file holding the entry.
+  pub location: String,
+}
+
+/// What the entry starts: the AppImage rather than its mount point on Linux, else this executable.
+fn launch_exe() -> Result<PathBuf, AppError> {
+  if let Some(appimage) = std::env::var_os("APPIMAGE").filter(|_| cfg!(target_os = "linux")) {
+    return Ok(PathBuf::from(appimage));
+  }
+  Ok(std::env::current_exe()?)
+}
+
+// --- Windows registry Run key ---
+
+#[cfg(windows)]
+mod platform {
+  use crate::service_mode::{run_cmd, run_ok};
+
+  pub const MECHANISM: &str = "registry";
+  const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+  const VALUE_NAME: &str = "AI Mentor";
+
+  pub fn location() -> String {
+    format!(r"{}\{}", RUN_KEY, VALUE_NAME)
+  }
+
+  /// The command line stored in the Run key, if any.
+  pub fn read() -> Result<Option<String>, String> {
+    let out = run_cmd("reg", &["query", RUN_KEY, "/v", VALUE_NAME])?;
+    if !out.status.success() {
+      return Ok(None);
+    }
+    Ok(String::from_utf8_lossy(&out.stdout)
+      .lines()
+      .find_map(|l| l.split_once("REG_SZ").map(|(_, v)| v.trim().to_string())))
+  }
+
+  pub fn write(exe: &std::path::Path, args: &[&str]) -> Result<(), String> {
+    let mut line = format!("\"{}\"", exe.display());
+    for arg in args {
+      line.push(' ');
+      line.push_str(arg);
+    }
+    run_ok("reg", &["add", RUN_KEY, "/v", VALUE_NAME, "/t", "REG_SZ", "/d", &line, "/f"])
+  }
+
+  pub fn remove() -> Result<(), String> {
+    if read()?.is_none() {
+      return Ok(());
+    }
+    run_ok("reg", &["delete", RUN_KEY, "/v", VALUE_NAME, "/f"])
+  }
+}
+
+// --- macOS launchd agent ---
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use crate::service_mode::{home_dir, xml_escape};
+  use std::fs;
+  use std::path::PathBuf;
+
+  pub const MECHANISM: &str = "launchd";
+  const LAUNCHD_LABEL: &str = "com.ai.mentor.desktop";
+
+  fn plist_path() -> PathBuf {
+    home_dir().join("Library/LaunchAgents").join(format!("{}.plist", LAUNCHD_LABEL))
+  }
+
+  pub fn location() -> String {
+    plist_path().display().to_string()
+  }
+
+  pub fn read() -> Result<Option<String>, String> {
+    Ok(fs::read_to_string(plist_path()).ok())
+  }
+
+  /// RunAtLoad only: unlike the backend service agent this one must not restart the app after Quit.
+  pub fn write(exe: &std::path::Path, args: &[&str]) -> Result<(), String> {
+    let program: String = std::iter::once(exe.display().to_string())
+      .chain(args.iter().map(|a| a.to_string()))
+      .map(|a| format!("<string>{}</string>", xml_escape(&a)))
+      .collect();
+    let plist = format!(
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key><string>{label}</string>
+  <key>ProgramArguments</key><array>{program}</array>
+  <key>RunAtLoad</key><true/>
+  <key>LimitLoadToSessionType</key><string>Aqua</string>
+</dict>
+</plist>
+"#,
+      label = LAUNCHD_LABEL,
+      program = program
+    );
+    let path = plist_path();
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&path, plist).map_err(|e| e.to_string())
+  }
+
+  pub fn remove() -> Result<(), String> {
+    match fs::remove_file(plist_path()) {
+      Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+      _ => Ok(()),
+    }
+  }
+}
+
+// --- Linux XDG autostart ---
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+  use crate::service_mode::home_dir;
+  use std::fs;
+  use std::path::PathBuf;
+
+  pub const MECHANISM: &str = "xdg-autostart";
+  const DESKTOP_FILE_NAME: &str = "ai-mentor.desktop";
+
+  fn desktop_path() -> PathBuf {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+      .map(PathBuf::from)
+      .filter(|p| p.is_absolute())
+      .unwrap_or_else(|| home_dir().join(".config"));
+    config.join("autostart").join(DESKTOP_FILE_NAME)
+  }
+
+  pub fn location() -> String {
+    desktop_path().display().to_string()
+  }
+
+  /// The entry, unless it is switched off in the desktop's startup settings (Hidden=true).
+  pub fn read() -> Result<Option<String>, String> {
+    let disabled = |l: &str| matches!(l.trim(), "Hidden=true" | "X-GNOME-Autostart-enabled=false");
+    Ok(fs::read_to_string(desktop_path()).ok().filter(|text| !text.lines().any(disabled)))
+  }
+
+  /// Exec= quoting from the Desktop Entry spec; a literal % is %% since % starts a field code.
+  fn quote_exec(arg: &str) -> String {
+    let mut out = String::from("\"");
+    for c in arg.chars() {
+      match c {
+        '"' | '`' | '$' | '\\' => out.push('\\'),
+        '%' => out.push('%'),
+        _ => {}
+      }
+      out.push(c);
+    }
+    out.push('"');
+    out
+  }
+
+  pub fn write(exe: &std::path::Path, args: &[&str]) -> Result<(), String> {
+    let mut exec = quote_exec(&exe.display().to_string());
+    for arg in args {
+      exec.push(' ');
+      exec.push_str(arg);
+    }
+    let entry = format!(
+      "[Desktop Entry]\nType=Application\nName=AI Mentor\nExec={}\nTerminal=false\nX-GNOME-Autostart-enabled=true\n",
+      exec
+    );
+    let path = desktop_path();
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&path, entry).map_err(|e| e.to_string())
+  }
+
+  pub fn remove() -> Result<(), String> {
+    match fs::remove_file(desktop_path()) {
+      Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+      _ => Ok(()),
+    }
+  }
+}
+
+fn status() -> Result<LaunchAtLogin, String> {
+  let entry = platform::read()?;
+  Ok(LaunchAtLogin {
+    enabled: entry.is_some(),
+    minimized: entry.is_some_and(|e| e.contains(MINIMIZED_FLAG)),
+    mechanism: platform::MECHANISM,
+    location: platform::location(),
+  })
+}
+
+fn write_entry(minimized: bool) -> Result<(), AppError> {
+  let exe = launch_exe()?;
+  let args: &[&str] = if minimized { &[MINIMIZED_FLAG] } else { &[] };
+  platform::write(&exe, args)?;
+  Ok(())
+}
+
+/// At startup: an existing entry that starts another executable (the app was moved or reinstalled
+/// elsewhere) is rewritten for this one, keeping its minimized flag.
+pub fn refresh() {
+  let Ok(Some(entry)) = platform::read() else {
+    return;
+  };
+  let Ok(exe) = launch_exe() else {
+    return;
+  };
+  if entry.contains(&exe.display().to_string()) {
+    return;
+  }
+  match write_entry(entry.contains(MINIMIZED_FLAG)) {
+    Ok(()) => crate::app_log(&format!("launch at login: entry updated for {}", exe.display())),
+    Err(e) => crate::app_log(&format!("launch at login: could not update entry: {}", e)),
+  }
+}
+
+#[tauri::command]
+pub fn get_launch_at_login() -> Result<LaunchAtLogin, AppError> {
+  Ok(status()?)
+}
+
+/// Create (or replace) or remove the login entry.
+#[tauri::command]
+pub fn set_launch_at_login(enabled: bool, minimized: bool) -> Result<LaunchAtLogin, AppError> {
+  if enabled {
+    write_entry(minimized)?;
+  } else {
+    platform::remove()?;
+  }
+  let current = status()?;
+  crate::app_log(&format!(
+    "launch at login: enabled={} minimized={} ({})",
+    current.enabled, current.minimized, current.location
+  ));
+  Ok(current)
+}
@@ -0,0 +1,230 @@
+// Seams between the autostart state machine and the outside world: how the backend process is started
+// (BackendLauncher / BackendProcess) and how its health is observed (HealthProbe). BackendState carries
+// one of each; the app uses CommandLauncher + ActiveBackendProbe, tests inject fakes.
+
+use std::future::Future;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+pub type HealthFuture<'a> = Pin<Box<dyn Future<Output = bool> + Send + 'a>>;
+pub type ReadinessFuture<'a> = Pin<Box<dyn Future<Output = Readiness> + Send + 'a>>;
+
+/// Readiness of a backend beyond liveness (see health::parse_readiness).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Readiness {
+  Ready,
+  /// Answering, but these components (possibly none named) are not ready.
+  Degraded(Vec<String>),
+  /// Not answering.
+  Down,
+}
+
+/// What to start: executable, extra environment, and where stdout/stderr go.
+#[derive(Debug, Clone)]
+pub struct LaunchSpec {
+  pub exe: PathBuf,
+  pub env: Vec<(String, String)>,
+  pub log_path: PathBuf,
+  /// Write output straight to log_path instead of piping it (see CommandLauncher).
+  pub direct_output: bool,
+}
+
+/// A started backend process.
+pub trait BackendProcess: Send {
+  fn id(&self) -> u32;
+  fn kill(&mut self) -> std::io::Result<()>;
+  /// Some(exit code) once the process has exited (-1 if killed by a signal), None while running.
+  fn exit_code(&mut self) -> Option<i32>;
+}
+
+impl BackendProcess for std::process::Child {
+  fn id(&self) -> u32 {
+    std::process::Child::id(self)
+  }
+
+  fn kill(&mut self) -> std::io::Result<()> {
+    std::process::Child::kill(self)
+  }
+
+  fn exit_code(&mut self) -> Option<i32> {
+    self.try_wait().ok().flatten().map(|s| s.code().unwrap_or(-1))
+  }
+}
+
+pub trait BackendLauncher: Send + Sync {
+  fn launch(&self, spec: &LaunchSpec) -> Result<Box<dyn BackendProcess>, String>;
+}
+
+pub trait HealthProbe: Send + Sync {
+  /// Liveness: 2xx and an "ok" (or "degraded") status body.
+  fn healthy(&self) -> HealthFuture<'_>;
+  /// Cheap readiness poll while a backend is starting: any 2xx.
+  fn responding(&self) -> HealthFuture<'_>;
+  /// Something is listening on the backend port.
+  fn port_in_use(&self) -> bool;
+  /// Readiness detail of a live backend. Defaults to healthy() alone, which never reports Degraded.
+  fn readiness(&self) -> ReadinessFuture<'_> {
+    Box::pin(async move {
+      if self.healthy().await {
+        Readiness::Ready
+      } else {
+        Readiness::Down
+      }
+    })
+  }
+}
+
+/// Spawns the backend executable with output appended to spec.log_path (no console window on Windows).
+/// Output is piped through log_privacy line by line, except with spec.direct_output (a backend kept
+/// alive past the app, backend_keep_alive, with log_encryption off): it writes the file directly so it
+/// never writes into a closed pipe, unredacted and unthrottled.
+pub struct CommandLauncher;
+
+impl BackendLauncher for CommandLauncher {
+  fn launch(&self, spec: &LaunchSpec) -> Result<Box<dyn BackendProcess>, String> {
+    let mut cmd = std::process::Command::new(&spec.exe);
+    if spec.direct_output {
+      let stdout_file = crate::open_append_log(&spec.log_path).ok_or("failed to open child log file")?;
+      let stderr_file = crate::open_append_log(&spec.log_path).ok_or("failed to open child log file (stderr)")?;
+      cmd.stdout(std::process::Stdio::from(stdout_file));
+      cmd.stderr(std::process::Stdio::from(stderr_file));
+    } else {
+      cmd.stdout(std::process::Stdio::piped());
+      cmd.stderr(std::process::Stdio::piped());
+    }
+    #[cfg(windows)]
+    cmd.creation_flags(crate::CREATE_NO_WINDOW);
+    cmd.envs(spec.env.iter().map(|(k, v)| (k, v)));
+    let mut child = cmd.spawn().map_err(|e| format!("spawn failed: {}", e))?;
+    if let Some(out) = child.stdout.take() {
+      forward_output(out, spec.log_path.clone());
+    }
+    if let Some(err) = child.stderr.take() {
+      forward_output(err, spec.log_path.clone());
+    }
+    Ok(Box::new(child))
+  }
+}
+
+/// Copy child output into `log_path` through log_throttle and log_privacy until the stream closes.
+fn forward_output<R: Read + Send + 'static>(stream: R, log_path: PathBuf) {
+  std::thread::spawn(move || {
+    let Some(mut file) = crate::open_append_log(&log_path) else {
+      return;
+    };
+    let mut reader = BufReader::new(stream);
+    let mut buf = Vec::new();
+    loop {
+      buf.clear();
+      match reader.read_until(b'\n', &mut buf) {
+        Ok(0) | Err(_) => break,
+        Ok(_) => {}
+      }
+      let line = String::from_utf8_lossy(&buf);
+      for admitted in crate::log_throttle::admit(&log_path, line.trim_end_matches(['\r', '\n'])) {
+        let _ = writeln!(file, "{}", crate::log_privacy::protect(&admitted));
+      }
+    }
+  });
+}
+
+/// HTTP health probe against `url`, with the port check on `host:port`.
+pub struct HttpHealthProbe {
+  pub url: String,
+  pub host: String,
+  pub port: u16,
+}
+
+impl HttpHealthProbe {
+  /// The configured backend: health_url() on backend_port().
+  pub fn for_backend() -> Self {
+    Self {
+      url: crate::health_url(),
+      host: crate::BACKEND_HOST.to_string(),
+      port: crate::backend_port(),
+    }
+  }
+}
+
+impl HealthProbe for HttpHealthProbe {
+  fn healthy(&self) -> HealthFuture<'_> {
+    Box::pin(async move {
+      let req = crate::profiles::client_for(&self.url).get(&self.url);
+      let req = req.timeout(crate::health::config().request_timeout());
+      let res = match req.send().await {
+        Ok(r) => r,
+        Err(_) => return false,
+      };
+      if !res.status().is_success() {
+        return false;
+      }
+      let body = match res.text().await {
+        Ok(b) => b,
+        Err(_) => return false,
+      };
+      body.contains("\"status\":\"ok\"")
+        || body.contains("\"status\": \"ok\"")
+        || body.contains("ok")
+        || body.contains("degraded")
+    })
+  }
+
+  /// Reads the health document at `url`.
+  fn readiness(&self) -> ReadinessFuture<'_> {
+    self.readiness_at(&self.url)
+  }
+
+  fn responding(&self) -> HealthFuture<'_> {
+    Box::pin(async move {
+      let req = crate::profiles::client_for(&self.url).get(&self.url);
+      let req = req.timeout(crate::health::config().request_timeout());
+      req.send().await.is_ok_and(|res| res.status().is_success())
+    })
+  }
+
+  fn port_in_use(&self) -> bool {
+    TcpListener::bind((self.host.as_str(), self.port)).is_err()
+  }
+}
+
+impl HttpHealthProbe {
+  fn readiness_at<'a>(&'a self, url: &'a str) -> ReadinessFuture<'a> {
+    Box::pin(async move {
+      let req = crate::profiles::client_for(url).get(url).timeout(crate::health::config().request_timeout());
+      let Ok(res) = req.send().await else {
+        return Readiness::Down;
+      };
+      let success = res.status().is_success();
+      let body = res.text().await.unwrap_or_default();
+      crate::health::parse_readiness(success, &body)
+    })
+  }
+}
+
+/// HttpHealthProbe::for_backend() rebuilt per call, so checks follow profile switches. A remote
+/// profile never occupies a local port.
+pub struct ActiveBackendProbe;
+
+impl HealthProbe for ActiveBackendProbe {
+  fn healthy(&self) -> HealthFuture<'_> {
+    Box::pin(async move { HttpHealthProbe::for_backend().healthy().await })
+  }
+
+  fn responding(&self) -> HealthFuture<'_> {
+    Box::pin(async move { HttpHealthProbe::for_backend().responding().await })
+  }
+
+  fn port_in_use(&self) -> bool {
+    crate::profiles::active().local_port.is_some() && HttpHealthProbe::for_backend().port_in_use()
+  }
+
+  /// Uses the configured readiness endpoint.
+  fn readiness(&self) -> ReadinessFuture<'_> {
+    Box::pin(async move { HttpHealthProbe::for_backend().readiness_at(&crate::ready_url()).await })
+  }
+}
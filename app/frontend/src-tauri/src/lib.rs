@@ -1,43 +1,139 @@
 // Desktop app: optional backend sidecar auto-start in release only.
-// API base is fixed: http://127.0.0.1:8000
+// API base: http://127.0.0.1:8000 (port overridable per session with --port, see cli.rs)
 
 use std::fs;
-use std::net::TcpListener;
 use tauri::Manager;
 use std::io::Write;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, RwLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
 
-#[cfg(windows)]
-use std::os::windows::process::CommandExt;
+mod anonymize;
+mod attachments;
+#[cfg(test)]
+mod autostart_tests;
+mod backend_env;
+mod backend_task;
+mod backup;
+mod capture;
+mod chat_windows;
+mod cli;
+mod command_stats;
+mod connectivity;
+mod crash;
+mod diagnostics;
+mod doctor;
+mod downtime;
+mod elevate;
+mod error;
+mod fast_start;
+mod file_manager;
+mod frontend_check;
+mod health;
+mod hotkey;
+mod idle;
+mod ipc;
+mod launch_at_login;
+mod launcher;
+mod log_privacy;
+mod log_query;
+mod log_throttle;
+mod maintenance;
+mod metrics;
+mod migrations;
+mod models;
+mod network;
+mod notify;
+mod paths;
+mod port_owner;
+mod profiles;
+mod secrets;
+mod service_mode;
+mod services;
+mod settings;
+mod settings_store;
+mod settings_watch;
+mod shutdown;
+mod sidecar;
+mod splash;
+mod status_history;
+mod telemetry;
+mod tray;
+mod zoom;
+
+use error::AppError;
+use launcher::HealthProbe;
 
 const LOCK_FILE_NAME: &str = "app.lock";
 const APP_LOG_NAME: &str = "app.log";
 const BACKEND_AUTOSTART_LOG_NAME: &str = "backend_autostart.log";
 const BACKEND_CHILD_LOG_NAME: &str = "backend_child.log";
-const FIXED_API_BASE: &str = "http://127.0.0.1:8000";
-const HEALTH_URL: &str = "http://127.0.0.1:8000/health";
+const BACKEND_HOST: &str = "127.0.0.1";
+const DEFAULT_BACKEND_PORT: u16 = 8000;
+/// Bundled backend binary (see sidecar for the file names tried).
+const BACKEND_BINARY_NAME: &str = "ai-mentor-backend";
+/// Health check defaults; settings and env can override them (see health).
+const HEALTH_PATH: &str = "/health";
 const HEALTH_POLL_MS: u64 = 250;
 const HEALTH_TIMEOUT_MS: u64 = 10_000;
+const HEALTH_REQUEST_TIMEOUT_MS: u64 = 2_000;
 const NOT_READY_REASON_PORT_IN_USE: &str = "PORT_IN_USE_NO_HEALTH";
+const NOT_READY_REASON_CANCELLED: &str = "CANCELLED";
+const NOT_READY_REASON_EXITED: &str = "BACKEND_EXITED";
+const NOT_READY_REASON_WRONG_ARCH: &str = "WRONG_ARCH";
+/// Asked to exit cleanly by stop_backend before it falls back to killing.
+const BACKEND_SHUTDOWN_PATH: &str = "/api/v1/shutdown";
+/// The backend only honors a shutdown request carrying the token it was given in this variable.
+const BACKEND_SHUTDOWN_TOKEN_VAR: &str = "AI_MENTOR_SHUTDOWN_TOKEN";
+const BACKEND_SHUTDOWN_TOKEN_HEADER: &str = "X-Shutdown-Token";
+const BACKEND_STOP_GRACE_MS: u64 = 5_000;
+const MAIN_WINDOW_LABEL: &str = "main";
+const BACKEND_STATUS_EVENT: &str = "backend-status";
 
 /// Windows CREATE_NO_WINDOW to avoid black console.
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x0800_0000;
 
-fn local_app_data() -> PathBuf {
-  std::env::var_os("LOCALAPPDATA")
-    .map(PathBuf::from)
-    .unwrap_or_else(|| std::env::var_os("USERPROFILE").map(PathBuf::from).unwrap_or_default())
+/// Data directory for this session (see paths::AppPaths for how it is chosen).
+fn app_base_dir() -> PathBuf {
+  paths::get().base.clone()
 }
 
-fn app_base_dir() -> PathBuf {
-  local_app_data().join("AI_Mentor")
+/// Local port of the active profile's backend (--port or the default for a remote profile).
+fn backend_port() -> u16 {
+  profiles::active()
+    .local_port
+    .unwrap_or_else(|| cli::args().port.unwrap_or(DEFAULT_BACKEND_PORT))
+}
+
+fn api_base() -> String {
+  profiles::active().base_url
+}
+
+fn health_url() -> String {
+  format!("{}{}", api_base(), health::config().path)
+}
+
+fn ready_url() -> String {
+  format!("{}{}", api_base(), health::config().ready_path)
+}
+
+/// Reachable: READY, or DEGRADED (some components not ready).
+fn is_up(status: &str) -> bool {
+  status == "READY" || status == "DEGRADED"
+}
+
+/// Backend executable: --backend-path for this session, else the bundled sidecar for this target.
+fn backend_exe_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+  if let Some(ref path) = cli::args().backend_path {
+    return Ok(path.clone());
+  }
+  sidecar::resolve(app, BACKEND_BINARY_NAME)
 }
 
 fn logs_dir() -> PathBuf {
-  app_base_dir().join("logs")
+  paths::get().logs.clone()
 }
 
 fn app_log_path() -> PathBuf {
@@ -52,7 +148,15 @@ fn backend_child_log_path() -> PathBuf {
   logs_dir().join(BACKEND_CHILD_LOG_NAME)
 }
 
+/// Lifecycle messages are info level; --log-level warn/error silences them.
+fn log_enabled(level: cli::LogLevel) -> bool {
+  level <= cli::log_level()
+}
+
 fn app_log(msg: &str) {
+  if !log_enabled(cli::LogLevel::Info) {
+    return;
+  }
   let path = app_log_path();
   if let Some(parent) = path.parent() {
     let _ = fs::create_dir_all(parent);
@@ -61,13 +165,22 @@ fn app_log(msg: &str) {
     .duration_since(UNIX_EPOCH)
     .map(|d| d.as_secs())
     .unwrap_or(0);
+  let lines = log_throttle::admit(&path, msg);
+  if lines.is_empty() {
+    return;
+  }
   if let Ok(mut f) = fs::OpenOptions::new().create(true).append(true).open(&path) {
-    let _ = writeln!(f, "[{}] {}", ts, msg);
+    for line in lines {
+      let _ = writeln!(f, "{}", log_privacy::protect(&format!("[{}] {}", ts, line)));
+    }
     let _ = f.flush();
   }
 }
 
 fn backend_autostart_log(msg: &str) {
+  if !log_enabled(cli::LogLevel::Info) {
+    return;
+  }
   let path = backend_autostart_log_path();
   if let Some(parent) = path.parent() {
     let _ = fs::create_dir_all(parent);
@@ -76,14 +189,36 @@ fn backend_autostart_log(msg: &str) {
     .duration_since(UNIX_EPOCH)
     .map(|d| d.as_secs())
     .unwrap_or(0);
+  let lines = log_throttle::admit(&path, msg);
+  if lines.is_empty() {
+    return;
+  }
   if let Ok(mut f) = fs::OpenOptions::new().create(true).append(true).open(&path) {
-    let _ = writeln!(f, "[{}] {}", ts, msg);
+    for line in lines {
+      let _ = writeln!(f, "{}", log_privacy::protect(&format!("[{}] {}", ts, line)));
+    }
     let _ = f.flush();
   }
 }
 
+/// Write the repeat / dropped notes log_throttle is still holding, so they are not lost on exit.
+fn flush_logs() {
+  let ts = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0);
+  for (path, notes) in log_throttle::pending() {
+    if let Ok(mut f) = fs::OpenOptions::new().create(true).append(true).open(&path) {
+      for note in notes {
+        let _ = writeln!(f, "{}", log_privacy::protect(&format!("[{}] {}", ts, note)));
+      }
+      let _ = f.flush();
+    }
+  }
+}
+
 fn lock_file_path() -> PathBuf {
-  app_base_dir().join("runtime").join(LOCK_FILE_NAME)
+  paths::get().runtime.join(LOCK_FILE_NAME)
 }
 
 fn try_single_instance() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -95,7 +230,7 @@ fn try_single_instance() -> Result<(), Box<dyn std::error::Error + Send + Sync>>
           .duration_since(modified)
           .unwrap_or(Duration::from_secs(999));
         if age < Duration::from_secs(60) {
-          return Err("Another instance is already running".into());
+          return Err(AppError::LockHeld("Another instance is already running".to_string()).into());
         }
       }
     }
@@ -115,6 +250,9 @@ fn remove_lock() {
 /// Set AI_MENTOR_AUTOSTART_BACKEND=0 to disable (default ON for Windows release).
 /// Dev mode and non-Windows are unchanged (no autostart).
 fn autostart_enabled() -> bool {
+  if cli::args().no_autostart {
+    return false;
+  }
   #[cfg(not(target_os = "windows"))]
   return false;
   #[cfg(target_os = "windows")]
@@ -129,56 +267,172 @@ fn autostart_enabled() -> bool {
   }
 }
 
-/// Backend process state: READY | STARTING | NOT_READY.
-/// When NOT_READY, not_ready_reason may be set (e.g. PORT_IN_USE_NO_HEALTH).
+/// Backend process state: READY | DEGRADED | STARTING | NOT_READY | STOPPED.
+/// When NOT_READY, not_ready_reason may be set (e.g. PORT_IN_USE_NO_HEALTH); when DEGRADED it lists
+/// the components that are not ready (see health). STOPPED means the user stopped it (stop_backend);
+/// nothing restarts it until an explicit start.
+/// down_since is when the backend last stopped being reachable (None while READY or DEGRADED).
 struct BackendStateInner {
   status: String,
-  child: Option<std::process::Child>,
+  child: Option<Box<dyn launcher::BackendProcess>>,
   not_ready_reason: Option<String>,
+  down_since: Option<SystemTime>,
+  attempt_started: Option<SystemTime>,
+  restart_history: std::collections::VecDeque<downtime::RestartRecord>,
+  /// Recent status/reason changes (see status_history).
+  status_history: std::collections::VecDeque<status_history::StatusTransition>,
+  /// Abort handle of the in-flight spawn/health task (see spawn_flow).
+  flow_abort: Option<tokio::task::AbortHandle>,
+  /// Attempt generation. Bumped whenever a new attempt starts or the user changes state, so an older
+  /// attempt still polling health can no longer update status or take the child (see lock_current).
+  generation: u64,
+  /// Backend left running by a previous session and reused via the fast start path.
+  adopted_pid: Option<u32>,
+  /// Who holds the port while NOT_READY:PORT_IN_USE_NO_HEALTH.
+  port_owner: Option<port_owner::PortOwner>,
+  /// Status changes are forwarded here (see start_status_events); None until the app is set up.
+  status_tx: Option<std::sync::mpsc::Sender<StatusChange>>,
+}
+
+/// Payload of the `backend-status` event.
+#[derive(Debug, Clone, serde::Serialize)]
+struct StatusChange {
+  status: String,
+  reason: Option<String>,
+  /// Set with PORT_IN_USE_NO_HEALTH when the owner could be determined.
+  port_owner: Option<port_owner::PortOwner>,
+  /// Health timing in effect when the change happened.
+  health: health::HealthConfig,
+  /// Set when status or reason actually changed; persisted by the event thread.
+  #[serde(skip)]
+  transition: Option<status_history::StatusTransition>,
+}
+
+impl BackendStateInner {
+  /// Single place status changes go through, so downtime tracking stays consistent.
+  fn set_status(&mut self, status: &str, reason: Option<String>) {
+    if reason.as_deref() != Some(NOT_READY_REASON_PORT_IN_USE) {
+      self.port_owner = None;
+    }
+    if is_up(status) {
+      self.down_since = None;
+    } else if self.down_since.is_none() {
+      self.down_since = Some(SystemTime::now());
+    }
+    let changed = self.status != status || self.not_ready_reason != reason;
+    let transition = changed.then(|| status_history::StatusTransition {
+      at_ms: status_history::now_ms(),
+      from: self.status.clone(),
+      to: status.to_string(),
+      reason: reason.clone(),
+      attempt: self.generation,
+    });
+    if let Some(ref t) = transition {
+      status_history::push(&mut self.status_history, t.clone());
+    }
+    self.status = status.to_string();
+    self.not_ready_reason = reason;
+    if let Some(ref tx) = self.status_tx {
+      let _ = tx.send(StatusChange {
+        status: self.status.clone(),
+        reason: self.not_ready_reason.clone(),
+        port_owner: self.port_owner.clone(),
+        health: health::config(),
+        transition,
+      });
+    }
+  }
+
+  /// Mark the start of a spawn + health attempt (used for recovery estimates).
+  fn begin_attempt(&mut self) {
+    self.attempt_started = Some(SystemTime::now());
+  }
+
+  /// Invalidate the in-flight attempt (abort its task, bump the generation) and return the new generation.
+  fn supersede(&mut self) -> u64 {
+    if let Some(prev) = self.flow_abort.take() {
+      prev.abort();
+    }
+    self.generation += 1;
+    self.generation
+  }
+
+  /// Close the current attempt, if any, and append it to the bounded restart history.
+  fn end_attempt(&mut self, ok: bool) {
+    if let Some(started) = self.attempt_started.take() {
+      downtime::push_restart_record(&mut self.restart_history, started, ok);
+    }
+  }
 }
 
 struct BackendState {
   inner: Mutex<BackendStateInner>,
+  launcher: Box<dyn launcher::BackendLauncher>,
+  probe: Box<dyn launcher::HealthProbe>,
 }
 
-impl Default for BackendState {
-  fn default() -> Self {
+impl BackendState {
+  fn new(launcher: Box<dyn launcher::BackendLauncher>, probe: Box<dyn launcher::HealthProbe>) -> Self {
     Self {
       inner: Mutex::new(BackendStateInner {
         status: "NOT_READY".to_string(),
         child: None,
         not_ready_reason: None,
+        down_since: Some(SystemTime::now()),
+        attempt_started: None,
+        restart_history: std::collections::VecDeque::new(),
+        status_history: std::collections::VecDeque::new(),
+        flow_abort: None,
+        generation: 0,
+        adopted_pid: None,
+        port_owner: None,
+        status_tx: None,
       }),
+      launcher,
+      probe,
     }
   }
+
+  /// Lock the state only if `generation` is still the latest attempt; stale attempts get None.
+  fn lock_current(&self, generation: u64) -> Option<MutexGuard<'_, BackendStateInner>> {
+    let g = self.inner.lock().ok()?;
+    (g.generation == generation).then_some(g)
+  }
 }
 
-/// Returns true if GET health returns 200 and body contains {"status":"ok"} (or "ok").
-fn probe_health_ok() -> bool {
-  let client = match reqwest::blocking::Client::builder()
-    .timeout(Duration::from_secs(2))
-    .build()
-  {
-    Ok(c) => c,
-    Err(_) => return false,
-  };
-  let res = match client.get(HEALTH_URL).send() {
-    Ok(r) => r,
-    Err(_) => return false,
-  };
-  if !res.status().is_success() {
-    return false;
+/// The real thing: spawn the executable, probe the active profile's backend over HTTP.
+impl Default for BackendState {
+  fn default() -> Self {
+    Self::new(Box::new(launcher::CommandLauncher), Box::new(launcher::ActiveBackendProbe))
   }
-  let body = match res.text() {
-    Ok(b) => b,
-    Err(_) => return false,
-  };
-  body.contains("\"status\":\"ok\"") || body.contains("\"status\": \"ok\"") || body.contains("ok")
 }
 
-/// Returns true if port 8000 is in use (bind fails).
-fn port_8000_in_use() -> bool {
-  TcpListener::bind("127.0.0.1:8000").is_err()
+/// Shared async HTTP client for health probes; timeouts are set per request.
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// Full health check (2xx and an "ok" body) against the configured backend.
+async fn probe_health_ok() -> bool {
+  launcher::ActiveBackendProbe.healthy().await
+}
+
+/// Poll `probe` every health poll interval until it responds with 2xx or timeout_ms elapses.
+async fn wait_for_health(probe: &dyn launcher::HealthProbe, timeout_ms: u64) -> bool {
+  let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
+  while tokio::time::Instant::now() < deadline {
+    if probe.responding().await {
+      return true;
+    }
+    tokio::time::sleep(health::config().poll()).await;
+  }
+  false
+}
+
+/// Returns true if the backend port is in use (bind fails).
+fn port_in_use() -> bool {
+  launcher::ActiveBackendProbe.port_in_use()
 }
 
 fn open_append_log(path: &PathBuf) -> Option<std::fs::File> {
@@ -192,115 +446,243 @@ fn open_append_log(path: &PathBuf) -> Option<std::fs::File> {
     .ok()
 }
 
-/// Child stdout/stderr go to child_log_path; lifecycle messages go to backend_autostart.log only.
-fn try_spawn_and_health(state: std::sync::Arc<BackendState>, exe_path: PathBuf, child_log_path: PathBuf) {
-  backend_autostart_log("autostart: begin");
-  let stdout_file = match open_append_log(&child_log_path) {
-    Some(f) => f,
-    None => {
-      backend_autostart_log("autostart: failed to open child log file");
-      if let Ok(mut g) = state.inner.lock() {
-        g.status = "NOT_READY".to_string();
-        g.not_ready_reason = None;
-      }
-      return;
+static SHUTDOWN_TOKEN: RwLock<Option<String>> = RwLock::new(None);
+
+/// Token for the shutdown endpoint of the backend we spawn: one per session, or the one of a backend
+/// adopted from the previous session (fast start).
+fn shutdown_token() -> String {
+  if let Some(token) = SHUTDOWN_TOKEN.read().ok().and_then(|g| g.clone()) {
+    return token;
+  }
+  let mut bytes = [0u8; 16];
+  let _ = getrandom::fill(&mut bytes);
+  let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+  let mut g = SHUTDOWN_TOKEN.write().unwrap_or_else(|e| e.into_inner());
+  g.get_or_insert(token).clone()
+}
+
+fn adopt_shutdown_token(token: String) {
+  if let Ok(mut g) = SHUTDOWN_TOKEN.write() {
+    *g = Some(token);
+  }
+}
+
+/// Environment for the spawned backend: port, configured env, session overrides, and injected
+/// secrets (later entries win).
+fn backend_launch_env(settings: &settings::Settings) -> Vec<(String, String)> {
+  let mut env = vec![
+    ("AI_MENTOR_PORT".to_string(), backend_port().to_string()),
+    (BACKEND_SHUTDOWN_TOKEN_VAR.to_string(), shutdown_token()),
+  ];
+  // Always set, so the backend's database is where backup/restore look for it.
+  env.push(("AI_MENTOR_BASE_DIR".to_string(), app_base_dir().display().to_string()));
+  env.push(("AI_MENTOR_MODELS_DIR".to_string(), models::models_dir().display().to_string()));
+  env.extend(backend_env::configured(settings));
+  if let Some(level) = cli::args().log_level {
+    env.push(("AI_MENTOR_LOG_LEVEL".to_string(), level.as_str().to_string()));
+  }
+  env.extend(services::companion_env());
+  let secrets = secrets::backend_env(settings);
+  let mut secret_vars: Vec<String> = secrets.iter().map(|(var, _)| var.clone()).collect();
+  secret_vars.push(BACKEND_SHUTDOWN_TOKEN_VAR.to_string());
+  env.extend(secrets);
+  backend_autostart_log(&format!("autostart: env {}", backend_env::describe(&env, &secret_vars)));
+  env
+}
+
+/// How a health wait on a freshly launched backend ended.
+enum StartOutcome {
+  Ready,
+  Exited(i32),
+  Timeout,
+}
+
+/// Poll the probe until the backend responds, its process exits, or timeout_ms elapses.
+async fn wait_for_start(state: &BackendState, generation: u64, timeout_ms: u64) -> StartOutcome {
+  let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
+  loop {
+    if state.probe.responding().await {
+      return StartOutcome::Ready;
     }
-  };
-  let stderr_file = match open_append_log(&child_log_path) {
-    Some(f) => f,
-    None => {
-      backend_autostart_log("autostart: failed to open child log file (stderr)");
-      if let Ok(mut g) = state.inner.lock() {
-        g.status = "NOT_READY".to_string();
-        g.not_ready_reason = None;
-      }
-      return;
+    let exited = state
+      .lock_current(generation)
+      .and_then(|mut g| g.child.as_mut().and_then(|c| c.exit_code()));
+    if let Some(code) = exited {
+      return StartOutcome::Exited(code);
     }
-  };
+    if tokio::time::Instant::now() >= deadline {
+      return StartOutcome::Timeout;
+    }
+    tokio::time::sleep(health::config().poll()).await;
+  }
+}
 
-  let mut cmd = std::process::Command::new(&exe_path);
-  cmd.stdout(std::process::Stdio::from(stdout_file));
-  cmd.stderr(std::process::Stdio::from(stderr_file));
-  #[cfg(windows)]
-  cmd.creation_flags(CREATE_NO_WINDOW);
+/// Child stdout/stderr go to child_log_path; lifecycle messages go to backend_autostart.log only.
+/// `settings` is the SettingsState snapshot the launch env and keep-alive come from.
+async fn try_spawn_and_health(
+  state: Arc<BackendState>,
+  generation: u64,
+  exe_path: PathBuf,
+  child_log_path: PathBuf,
+  settings: settings::Settings,
+) {
+  let spec = launcher::LaunchSpec {
+    exe: exe_path,
+    env: backend_launch_env(&settings),
+    log_path: child_log_path,
+    // Encrypted logs must not get plaintext written past log_privacy, so they keep the pipe even
+    // though a kept-alive backend then loses its output once the app is gone.
+    direct_output: settings.backend_keep_alive && !settings.log_encryption,
+  };
+  spawn_and_health(state, generation, spec, services::BACKEND_SERVICE_NAME).await;
+}
 
-  let child = match cmd.spawn() {
+/// Spawn `spec` and wait for health, for any managed service. Log lines of services other than the
+/// backend are tagged with their name.
+async fn spawn_and_health(state: Arc<BackendState>, generation: u64, spec: launcher::LaunchSpec, service: &str) {
+  let tag = if service == services::BACKEND_SERVICE_NAME {
+    "autostart".to_string()
+  } else {
+    format!("autostart[{}]", service)
+  };
+  backend_autostart_log(&format!("{}: begin", tag));
+  if let Err(e) = sidecar::check_arch(&spec.exe) {
+    backend_autostart_log(&format!("{}: {}", tag, e));
+    if let Some(mut g) = state.lock_current(generation) {
+      g.set_status("NOT_READY", Some(NOT_READY_REASON_WRONG_ARCH.to_string()));
+    }
+    app_log(&format!("{} autostart: NOT_READY ({})", service, NOT_READY_REASON_WRONG_ARCH));
+    return;
+  }
+  let child = match state.launcher.launch(&spec) {
     Ok(c) => {
-      backend_autostart_log("autostart: process spawned");
+      backend_autostart_log(&format!("{}: process spawned", tag));
       c
     }
     Err(e) => {
-      backend_autostart_log(&format!("autostart: spawn failed: {}", e));
-      if let Ok(mut g) = state.inner.lock() {
-        g.status = "NOT_READY".to_string();
-        g.not_ready_reason = None;
+      backend_autostart_log(&format!("{}: {}", tag, e));
+      if let Some(mut g) = state.lock_current(generation) {
+        g.set_status("NOT_READY", None);
       }
       return;
     }
   };
 
-  {
-    let mut g = state.inner.lock().unwrap();
-    g.status = "STARTING".to_string();
-    g.not_ready_reason = None;
-    g.child = Some(child);
-  }
-
-  let deadline = SystemTime::now() + Duration::from_millis(HEALTH_TIMEOUT_MS);
-  let client = reqwest::blocking::Client::builder()
-    .timeout(Duration::from_millis(500))
-    .build()
-    .unwrap_or_default();
-
-  while SystemTime::now() < deadline {
-    if let Ok(res) = client.get(HEALTH_URL).send() {
-      if res.status().is_success() {
-        backend_autostart_log("autostart: health OK");
-        if let Ok(mut g) = state.inner.lock() {
-          g.status = "READY".to_string();
-          g.not_ready_reason = None;
-        }
-        app_log("backend autostart: READY");
-        return;
-      }
+  match state.lock_current(generation) {
+    Some(mut g) => {
+      g.set_status("STARTING", None);
+      g.begin_attempt();
+      g.child = Some(child);
+    }
+    None => {
+      backend_autostart_log(&format!("{}: attempt superseded before health wait, killing its process", tag));
+      let mut child = child;
+      let _ = child.kill();
+      return;
     }
-    std::thread::sleep(Duration::from_millis(HEALTH_POLL_MS));
   }
 
-  backend_autostart_log("autostart: health timeout");
-  if let Ok(mut g) = state.inner.lock() {
-    g.status = "NOT_READY".to_string();
-    g.not_ready_reason = None;
-    g.child.take();
+  let outcome = wait_for_start(&state, generation, health::config().timeout_ms).await;
+  // Responding ends STARTING; readiness decides between READY and DEGRADED.
+  let ready = match outcome {
+    StartOutcome::Ready => health::status_for(state.probe.readiness().await),
+    _ => None,
+  };
+  let Some(mut g) = state.lock_current(generation) else {
+    backend_autostart_log(&format!("{}: attempt superseded, result ignored", tag));
+    return;
+  };
+  match outcome {
+    StartOutcome::Ready => {
+      let (status, reason) = ready.unwrap_or(("READY", None));
+      g.set_status(status, reason.clone());
+      g.end_attempt(true);
+      drop(g);
+      backend_autostart_log(&format!("{}: health OK", tag));
+      match reason {
+        Some(r) => app_log(&format!("{} autostart: {} ({})", service, status, r)),
+        None => app_log(&format!("{} autostart: {}", service, status)),
+      }
+    }
+    StartOutcome::Exited(code) => {
+      g.set_status("NOT_READY", Some(NOT_READY_REASON_EXITED.to_string()));
+      g.end_attempt(false);
+      g.child.take();
+      drop(g);
+      backend_autostart_log(&format!("{}: process exited with code {} before health", tag, code));
+      app_log(&format!("{} autostart: NOT_READY (BACKEND_EXITED)", service));
+      crash::record_service_exit(service, code, &spec.log_path);
+    }
+    StartOutcome::Timeout => {
+      g.set_status("NOT_READY", None);
+      g.end_attempt(false);
+      g.child.take();
+      drop(g);
+      backend_autostart_log(&format!("{}: health timeout", tag));
+      app_log(&format!("{} autostart: NOT_READY (timeout)", service));
+    }
   }
-  app_log("backend autostart: NOT_READY (timeout)");
 }
 
 /// 1) Probe health -> if OK set READY and return. 2) If port 8000 in use set NOT_READY reason PORT_IN_USE_NO_HEALTH. 3) Else spawn + health wait.
-fn run_autostart_flow(state: std::sync::Arc<BackendState>, exe_path: PathBuf) {
+///
+/// Service mode is the caller's to check.
+async fn run_autostart_flow(state: Arc<BackendState>, generation: u64, exe_path: PathBuf, settings: settings::Settings) {
+  if !profiles::active().autostart {
+    profiles::run_probe_flow(state, generation).await;
+    return;
+  }
+  if fast_start::try_fast_start(&state, generation).await {
+    return;
+  }
   backend_autostart_log("autostart: probing health");
-  if probe_health_ok() {
+  if state.probe.healthy().await {
     backend_autostart_log("autostart: already healthy, skipping spawn");
-    if let Ok(mut g) = state.inner.lock() {
-      g.status = "READY".to_string();
-      g.not_ready_reason = None;
+    let (status, reason) = health::status_for(state.probe.readiness().await).unwrap_or(("READY", None));
+    if let Some(mut g) = state.lock_current(generation) {
+      g.set_status(status, reason);
     }
-    app_log("backend autostart: READY (already running)");
+    app_log(&format!("backend autostart: {} (already running)", status));
     return;
   }
 
-  if port_8000_in_use() {
-    backend_autostart_log("autostart: port 8000 in use but health failed -> NOT_READY");
-    if let Ok(mut g) = state.inner.lock() {
-      g.status = "NOT_READY".to_string();
-      g.not_ready_reason = Some(NOT_READY_REASON_PORT_IN_USE.to_string());
+  if state.probe.port_in_use() {
+    let owner = port_owner::find_async(backend_port()).await;
+    backend_autostart_log(&format!(
+      "autostart: port {} in use by {} but health failed -> NOT_READY",
+      backend_port(),
+      owner.as_ref().map(|o| o.to_string()).unwrap_or_else(|| "an unknown process".to_string())
+    ));
+    if let Some(mut g) = state.lock_current(generation) {
+      g.port_owner = owner;
+      g.set_status("NOT_READY", Some(NOT_READY_REASON_PORT_IN_USE.to_string()));
     }
     app_log("backend autostart: NOT_READY (PORT_IN_USE_NO_HEALTH)");
     return;
   }
 
   let child_log = backend_child_log_path();
-  try_spawn_and_health(state, exe_path, child_log);
+  try_spawn_and_health(state, generation, exe_path, child_log, settings).await;
+}
+
+/// Run `flow(generation)` on the Tauri runtime as the single in-flight spawn/health task, superseding
+/// any previous one. Await the returned handle to wait for the attempt; abort it via cancel_backend_start.
+fn spawn_flow<F, Fut>(state: &Arc<BackendState>, flow: F) -> tauri::async_runtime::JoinHandle<()>
+where
+  F: FnOnce(u64) -> Fut,
+  Fut: std::future::Future<Output = ()> + Send + 'static,
+{
+  let mut g = state.inner.lock().unwrap_or_else(|e| e.into_inner());
+  let generation = g.supersede();
+  let handle = tauri::async_runtime::spawn(flow(generation));
+  g.flow_abort = Some(handle.inner().abort_handle());
+  handle
+}
+
+/// Await a flow started with spawn_flow and report the resulting status; Err(Cancelled) if aborted.
+async fn await_flow(state: &BackendState, handle: tauri::async_runtime::JoinHandle<()>) -> Result<String, AppError> {
+  handle.await.map_err(|_| AppError::Cancelled)?;
+  Ok(backend_status_string(state))
 }
 
 #[tauri::command]
@@ -309,8 +691,8 @@ fn log_app_message(message: String) {
 }
 
 #[tauri::command]
-fn get_backend_base_url() -> Result<String, String> {
-  Ok(FIXED_API_BASE.to_string())
+fn get_backend_base_url() -> Result<String, AppError> {
+  Ok(api_base())
 }
 
 #[tauri::command]
@@ -319,169 +701,483 @@ fn is_backend_ready(state: tauri::State<std::sync::Arc<BackendState>>) -> bool {
   g.status == "READY"
 }
 
-#[tauri::command]
-fn get_backend_status(state: tauri::State<std::sync::Arc<BackendState>>) -> String {
+/// READY | DEGRADED | DEGRADED:<components> | STARTING | NOT_READY | NOT_READY:<reason> | STOPPED
+fn backend_status_string(state: &BackendState) -> String {
   let g = state.inner.lock().unwrap();
-  if g.status == "NOT_READY" {
+  if g.status == "NOT_READY" || g.status == "DEGRADED" {
     if let Some(ref r) = g.not_ready_reason {
-      return format!("NOT_READY:{}", r);
+      return format!("{}:{}", g.status, r);
     }
   }
   g.status.clone()
 }
 
-/// Retry backend start (spawn sidecar + health wait). Kills previous child if any.
+/// Status of `service` (default: backend).
 #[tauri::command]
-fn retry_backend_start(app: tauri::AppHandle, state: tauri::State<std::sync::Arc<BackendState>>) -> Result<(), String> {
-  let exe_path = app
-    .path()
-    .resolve("bin/ai-mentor-backend.exe", tauri::path::BaseDirectory::Resource)
-    .map_err(|e| format!("{:?}", e))?;
+fn get_backend_status(registry: tauri::State<services::ServiceRegistry>, service: Option<String>) -> Result<String, AppError> {
+  Ok(backend_status_string(&registry.get(service.as_deref())?.state))
+}
 
+/// Kill the spawned child (if any) and mark NOT_READY before a new start attempt.
+/// Kept sync so async commands never hold the state lock across an await.
+fn reset_backend(state: &BackendState) -> Result<(), String> {
   let mut g = state.inner.lock().map_err(|e| e.to_string())?;
+  g.supersede();
   if let Some(mut child) = g.child.take() {
     let _ = child.kill();
   }
-  g.status = "NOT_READY".to_string();
-  g.not_ready_reason = None;
-  drop(g);
-
-  let state_clone = state.inner().clone();
-  let child_log = backend_child_log_path();
-  std::thread::spawn(move || try_spawn_and_health(state_clone, exe_path, child_log));
+  g.adopted_pid = None;
+  g.set_status("NOT_READY", None);
   Ok(())
 }
 
-/// Ask Task Scheduler to run AI_Mentor_Backend task (Windows only). Does not spawn backend exe.
-#[tauri::command]
-fn run_backend_task() -> Result<(), String> {
-  #[cfg(not(target_os = "windows"))]
-  return Err("Windows only".to_string());
-  #[cfg(target_os = "windows")]
-  {
-    std::process::Command::new("schtasks")
-      .args(["/Run", "/TN", "AI_Mentor_Backend"])
-      .status()
-      .map_err(|e| e.to_string())?;
-    Ok(())
+/// Stop the backend this app owns (spawned child or adopted PID) and mark NOT_READY. A backend
+/// started by someone else is left alone.
+fn stop_owned_backend(state: &BackendState) -> Result<(), String> {
+  let mut g = state.inner.lock().map_err(|e| e.to_string())?;
+  g.supersede();
+  if let Some(mut child) = g.child.take() {
+    let _ = child.kill();
+  } else if let Some(pid) = g.adopted_pid.take() {
+    fast_start::kill_pid(pid);
   }
+  g.set_status("NOT_READY", None);
+  Ok(())
 }
 
-#[tauri::command]
-fn get_backend_autostart_log_path() -> PathBuf {
-  backend_autostart_log_path()
+/// Run the autostart flow again (reuse a healthy backend, else spawn) and wait for its outcome.
+async fn rerun_autostart(app: &tauri::AppHandle, state: &Arc<BackendState>) -> Result<String, AppError> {
+  let handle = if service_mode::is_enabled(app) {
+    spawn_flow(state, |gen| service_mode::run_service_flow(state.clone(), gen))
+  } else {
+    let exe_path = backend_exe_path(app)?;
+    let settings = app.state::<settings::SettingsState>().get();
+    spawn_flow(state, |gen| run_autostart_flow(state.clone(), gen, exe_path, settings))
+  };
+  await_flow(state, handle).await
 }
 
-/// Kill any ai-mentor-backend.exe processes (Windows), then spawn + health wait again.
+/// Kill the previous child (if any) and run a fresh spawn + health attempt (service start in service
+/// mode, health check only for a profile without autostart). Shared by retry_backend_start and the local IPC `restart` command.
+async fn restart_backend(app: &tauri::AppHandle, state: &Arc<BackendState>) -> Result<String, AppError> {
+  reset_backend(state)?;
+
+  let handle = if !profiles::active().autostart {
+    spawn_flow(state, |gen| profiles::run_probe_flow(state.clone(), gen))
+  } else if service_mode::is_enabled(app) {
+    spawn_flow(state, |gen| service_mode::run_service_flow(state.clone(), gen))
+  } else {
+    let exe_path = backend_exe_path(app)?;
+    let child_log = backend_child_log_path();
+    let settings = app.state::<settings::SettingsState>().get();
+    spawn_flow(state, |gen| try_spawn_and_health(state.clone(), gen, exe_path, child_log, settings))
+  };
+  await_flow(state, handle).await
+}
+
+/// Retry starting `service` (default: backend; spawn sidecar + health wait). Kills previous child if any.
+/// Resolves with the resulting status once the attempt finishes; rejects with "cancelled" if aborted.
 #[tauri::command]
-fn kill_backend_and_retry(app: tauri::AppHandle, state: tauri::State<std::sync::Arc<BackendState>>) -> Result<(), String> {
-  #[cfg(target_os = "windows")]
-  {
-    let _ = std::process::Command::new("taskkill")
-      .args(["/F", "/IM", "ai-mentor-backend.exe"])
-      .output();
-  }
+async fn retry_backend_start(
+  app: tauri::AppHandle,
+  registry: tauri::State<'_, services::ServiceRegistry>,
+  service: Option<String>,
+) -> Result<String, AppError> {
+  command_stats::track(&app, "retry_backend_start", async {
+    services::restart(&app, registry.get(service.as_deref())?).await
+  })
+  .await
+}
 
+/// Abort the in-flight start attempt (retry/kill-and-retry/autostart) and kill its child.
+#[tauri::command]
+fn cancel_backend_start(state: tauri::State<Arc<BackendState>>) -> Result<(), AppError> {
   let mut g = state.inner.lock().map_err(|e| e.to_string())?;
-  if let Some(mut child) = g.child.take() {
-    let _ = child.kill();
+  if g.flow_abort.as_ref().is_none_or(|a| a.is_finished()) {
+    return Ok(());
   }
-  g.status = "NOT_READY".to_string();
-  g.not_ready_reason = None;
-  drop(g);
-
-  let exe_path = app
-    .path()
-    .resolve("bin/ai-mentor-backend.exe", tauri::path::BaseDirectory::Resource)
-    .map_err(|e| format!("{:?}", e))?;
+  g.supersede();
+  if g.status == "STARTING" {
+    if let Some(mut child) = g.child.take() {
+      let _ = child.kill();
+    }
+    g.set_status("NOT_READY", Some(NOT_READY_REASON_CANCELLED.to_string()));
+    g.end_attempt(false);
+  }
+  backend_autostart_log("autostart: cancelled");
+  Ok(())
+}
 
-  let state_clone = state.inner().clone();
-  std::thread::spawn(move || run_autostart_flow(state_clone, exe_path));
+/// Ask the owned backend to exit (POST BACKEND_SHUTDOWN_PATH) and kill it if it is still running
+/// after BACKEND_STOP_GRACE_MS. Supersedes any in-flight flow; the caller sets the resulting status.
+async fn stop_owned_gracefully(state: &BackendState) -> Result<(), AppError> {
+  let (mut child, adopted) = {
+    let mut g = state.inner.lock().map_err(|e| e.to_string())?;
+    g.supersede();
+    (g.child.take(), g.adopted_pid.take())
+  };
+  if child.is_none() && adopted.is_none() {
+    return Ok(());
+  }
+  let url = format!("{}{}", api_base(), BACKEND_SHUTDOWN_PATH);
+  let asked = profiles::client_for(&url)
+    .post(&url)
+    .header(BACKEND_SHUTDOWN_TOKEN_HEADER, shutdown_token())
+    .timeout(health::config().request_timeout())
+    .send()
+    .await
+    .is_ok_and(|r| r.status().is_success());
+  backend_autostart_log(&format!("stop: {} {}", url, if asked { "accepted" } else { "not accepted" }));
+  let deadline = std::time::Instant::now() + Duration::from_millis(BACKEND_STOP_GRACE_MS);
+  let exited = loop {
+    let gone = match child.as_mut() {
+      Some(c) => c.exit_code().is_some(),
+      None => !state.probe.port_in_use(),
+    };
+    if gone || !asked || std::time::Instant::now() >= deadline {
+      break gone;
+    }
+    tokio::time::sleep(health::config().poll()).await;
+  };
+  if !exited {
+    backend_autostart_log("stop: backend still running, killing");
+    if let Some(ref mut c) = child {
+      let _ = c.kill();
+    } else if let Some(pid) = adopted {
+      fast_start::kill_pid(pid);
+    }
+  }
   Ok(())
 }
 
-/// Open the logs folder in the system file manager (e.g. Explorer on Windows).
+/// Stop the owned backend gracefully (see stop_owned_gracefully) and mark it STOPPED. The exit watch
+/// and maintenance restarts leave a STOPPED backend alone; retry_backend_start starts it again.
+/// Resolves with the new status.
 #[tauri::command]
-fn open_logs_folder() -> Result<(), String> {
-  let path = logs_dir();
-  if let Some(parent) = path.parent() {
-    let _ = fs::create_dir_all(parent);
-  }
+async fn stop_backend(app: tauri::AppHandle, state: tauri::State<'_, Arc<BackendState>>) -> Result<String, AppError> {
+  command_stats::track(&app, "stop_backend", async {
+    if service_mode::is_enabled(&app) {
+      return Err(AppError::Unsupported("backend runs as a service; use stop_backend_service".to_string()));
+    }
+    if !profiles::active().autostart {
+      return Err(AppError::Unsupported(format!(
+        "profile {} is not managed by this app",
+        profiles::active().name
+      )));
+    }
+    stop_owned_gracefully(&state).await?;
+    let mut g = state.inner.lock().map_err(|e| e.to_string())?;
+    g.set_status("STOPPED", None);
+    app_log("backend: stopped by user");
+    Ok(g.status.clone())
+  })
+  .await
+}
+
+/// Ask Task Scheduler to run AI_Mentor_Backend task (Windows only). Does not spawn backend exe.
+#[tauri::command]
+fn run_backend_task() -> Result<(), AppError> {
+  #[cfg(not(target_os = "windows"))]
+  return Err(AppError::Unsupported("Windows only".to_string()));
   #[cfg(target_os = "windows")]
   {
-    std::process::Command::new("explorer")
-      .args([path.as_os_str()])
+    std::process::Command::new("schtasks")
+      .args(["/Run", "/TN", backend_task::BACKEND_TASK_NAME])
       .status()
-      .map_err(|e| e.to_string())?;
-  }
-  #[cfg(not(target_os = "windows"))]
-  {
-    let _ = path;
-    return Err("Open logs folder is supported on Windows only".to_string());
+      .map_err(|e| AppError::SpawnFailed(e.to_string()))?;
+    Ok(())
   }
-  Ok(())
+}
+
+#[tauri::command]
+fn get_backend_autostart_log_path() -> PathBuf {
+  backend_autostart_log_path()
+}
+
+/// Kill any processes of `service` (default: backend, i.e. ai-mentor-backend.exe; Windows), then run
+/// its autostart flow again. Resolves with the resulting status like retry_backend_start.
+#[tauri::command]
+async fn kill_backend_and_retry(
+  app: tauri::AppHandle,
+  registry: tauri::State<'_, services::ServiceRegistry>,
+  service: Option<String>,
+) -> Result<String, AppError> {
+  command_stats::track(&app, "kill_backend_and_retry", async {
+    services::kill_and_retry(&app, registry.get(service.as_deref())?).await
+  })
+  .await
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+  let cli_args = cli::init();
   if let Err(e) = try_single_instance() {
     eprintln!("{}", e);
     std::process::exit(1);
   }
+  migrations::run();
 
   let backend_state = std::sync::Arc::new(BackendState::default());
+  crash::install(backend_state.clone());
 
   tauri::Builder::default()
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_fs::init())
+    .plugin(hotkey::plugin())
+    .plugin(tauri_plugin_clipboard_manager::init())
     .manage(backend_state.clone())
-    .setup(|app| {
+    .manage(services::ServiceRegistry::new(backend_state.clone()))
+    .manage(settings::SettingsState::load())
+    .manage(metrics::MetricsState::default())
+    .manage(frontend_check::FrontendCheckState::default())
+    .manage(shutdown::ShutdownHooks::default())
+    .manage(command_stats::CommandStatsState::default())
+    .manage(chat_windows::ChatWindowsState::default())
+    .manage(models::ModelsState::default())
+    .manage(maintenance::MaintenanceState::default())
+    .on_page_load(|webview, payload| {
+      if let tauri::webview::PageLoadEvent::Finished = payload.event() {
+        zoom::on_page_load(webview);
+      }
+    })
+    .setup(move |app| {
+      let stored = app.state::<settings::SettingsState>().get();
+      cli::set_settings_log_level(stored.log_level);
+      log_privacy::init(&stored);
+      telemetry::init(&stored);
+      profiles::init(&stored);
+      health::init(&stored.health);
       let build_id = std::env!("BUILD_ID");
       app_log(&format!("BUILD_ID={}", build_id));
       let exe_path = std::env::current_exe().unwrap_or_default();
       app_log(&format!(
-        "APP_START exe={} fixed_url={} autostart={}",
+        "APP_START exe={} api_base={} autostart={}",
         exe_path.display(),
-        FIXED_API_BASE,
+        api_base(),
         autostart_enabled()
       ));
+      let data = paths::get();
+      app_log(&format!("data dir: {} ({:?})", data.base.display(), data.source));
+      register_shutdown_hooks(app.handle());
+      if cli_args.has_overrides() {
+        app_log(&format!("CLI overrides: {:?}", cli_args));
+      }
+      if !cli_args.ignored.is_empty() {
+        app_log(&format!("CLI: ignored arguments {:?}", cli_args.ignored));
+      }
 
-      if autostart_enabled() {
-        let state = app.try_state::<std::sync::Arc<BackendState>>().unwrap().inner().clone();
-        let exe_path = app
-          .path()
-          .resolve("bin/ai-mentor-backend.exe", tauri::path::BaseDirectory::Resource)
-          .ok();
-        if let Some(path) = exe_path {
-          std::thread::spawn(move || run_autostart_flow(state, path));
-        } else {
-          app_log("backend autostart: exe not found (resource), NOT_READY");
-          if let Some(s) = app.try_state::<std::sync::Arc<BackendState>>() {
-            if let Ok(mut g) = s.inner().inner.lock() {
-              g.status = "NOT_READY".to_string();
+      let state = app.state::<Arc<BackendState>>().inner().clone();
+      for service in app.state::<services::ServiceRegistry>().all() {
+        start_status_events(app.handle(), service.name, &service.state);
+      }
+      ipc::start(app.handle());
+      crash::start_exit_watch(app.handle());
+      health::start_readiness_watch(app.handle());
+      maintenance::start(app.handle());
+      idle::start(app.handle());
+      network::start(app.handle());
+      settings_watch::start(app.handle());
+      if let Err(e) = tray::create(app.handle()) {
+        app_log(&format!("tray: could not create: {}", e));
+      }
+      hotkey::register_from_settings(app.handle());
+      launch_at_login::refresh();
+      if !profiles::active().autostart {
+        app_log("backend autostart: profile without autostart, health check only");
+        splash::show_main(app.handle());
+        spawn_flow(&state, |gen| profiles::run_probe_flow(state.clone(), gen));
+      } else if service_mode::is_enabled(app.handle()) {
+        app_log("backend autostart: service mode, not spawning child");
+        splash::show(app.handle());
+        spawn_flow(&state, |gen| service_mode::run_service_flow(state.clone(), gen));
+      } else if autostart_enabled() {
+        match backend_exe_path(app.handle()) {
+          Ok(path) => {
+            splash::show(app.handle());
+            let settings = app.state::<settings::SettingsState>().get();
+            spawn_flow(&state, |gen| run_autostart_flow(state.clone(), gen, path, settings));
+          }
+          Err(e) => {
+            app_log(&format!("backend autostart: {}, NOT_READY", e));
+            if let Ok(mut g) = state.inner.lock() {
+              g.set_status("NOT_READY", None);
             }
+            splash::show_main(app.handle());
           }
         }
+      } else {
+        splash::show_main(app.handle());
+      }
+      if profiles::active().autostart && (service_mode::is_enabled(app.handle()) || autostart_enabled()) {
+        services::autostart_companions(app.handle());
       }
 
       Ok(())
     })
-    .invoke_handler(tauri::generate_handler![
+    .invoke_handler(command_stats::wrap(tauri::generate_handler![
       log_app_message,
       get_backend_base_url,
       is_backend_ready,
       get_backend_status,
       retry_backend_start,
+      cancel_backend_start,
+      stop_backend,
       kill_backend_and_retry,
       run_backend_task,
+      backend_task::install_backend_task,
+      backend_task::uninstall_backend_task,
+      backend_task::query_backend_task_status,
       get_backend_autostart_log_path,
-      open_logs_folder,
-    ])
-    .on_window_event(|_window, event| {
-      if let tauri::WindowEvent::CloseRequested { .. } = event {
-        remove_lock();
+      file_manager::open_logs_folder,
+      file_manager::reveal_file,
+      zoom::set_zoom_level,
+      zoom::get_zoom_level,
+      downtime::get_downtime_info,
+      service_mode::install_backend_service,
+      service_mode::uninstall_backend_service,
+      service_mode::start_backend_service,
+      service_mode::stop_backend_service,
+      service_mode::get_backend_service_status,
+      secrets::secret_set,
+      secrets::secret_get,
+      secrets::secret_delete,
+      backend_env::get_backend_env,
+      backend_env::set_backend_env,
+      doctor::run_doctor_report,
+      metrics::get_backend_metrics,
+      metrics::start_backend_metrics_stream,
+      metrics::stop_backend_metrics_stream,
+      diagnostics::preview_log_anonymization,
+      diagnostics::export_diagnostics,
+      log_query::query_logs,
+      cli::get_cli_overrides,
+      frontend_check::frontend_handshake,
+      frontend_check::frontend_clear_cache_and_reload,
+      shutdown::quit_app,
+      shutdown::relaunch_app,
+      launch_at_login::get_launch_at_login,
+      launch_at_login::set_launch_at_login,
+      hotkey::set_summon_hotkey,
+      hotkey::get_summon_hotkey,
+      command_stats::get_command_stats,
+      notify::notify,
+      chat_windows::open_chat_window,
+      chat_windows::list_chat_windows,
+      capture::capture_screenshot,
+      capture::read_clipboard_context,
+      attachments::attach_files_to_backend,
+      attachments::set_attachment_max_bytes,
+      paths::get_app_paths,
+      paths::set_data_dir,
+      migrations::get_migration_report,
+      backup::backup_backend_data,
+      backup::restore_backend_data,
+      models::list_models,
+      models::download_model,
+      models::cancel_model_download,
+      models::delete_model,
+      profiles::list_profiles,
+      profiles::save_profile,
+      profiles::delete_profile,
+      profiles::switch_profile,
+      services::list_services,
+      crash::get_pending_crash_reports,
+      crash::submit_crash_report,
+      crash::dismiss_crash_report,
+      status_history::get_status_history,
+      health::get_health_config,
+      health::set_health_config,
+      connectivity::diagnose_connectivity,
+      port_owner::get_port_owner,
+      port_owner::kill_port_owner,
+      maintenance::get_maintenance_status,
+      maintenance::set_maintenance_schedule,
+      maintenance::begin_backend_activity,
+      maintenance::end_backend_activity,
+      idle::get_idle_suspend,
+      idle::set_idle_suspend,
+      idle::wake_backend,
+      network::get_network_state,
+      log_privacy::get_log_privacy,
+      log_privacy::set_log_encryption,
+      telemetry::get_telemetry_summary,
+      telemetry::set_telemetry_enabled,
+      telemetry::export_telemetry,
+    ]))
+    .on_window_event(|window, event| {
+      match event {
+        // With a tray icon to come back from, closing the main window only hides it. Otherwise it
+        // closes; the hooks run once the last window is gone and the app exits.
+        tauri::WindowEvent::CloseRequested { api, .. }
+          if window.label() == MAIN_WINDOW_LABEL && tray::exists(window.app_handle()) =>
+        {
+          api.prevent_close();
+          let _ = window.hide();
+        }
+        tauri::WindowEvent::Destroyed => chat_windows::on_destroyed(window.app_handle(), window.label()),
+        tauri::WindowEvent::Focused(true) => idle::on_interaction(window.app_handle()),
+        _ => {}
       }
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while running tauri application")
+    .run(|app, event| {
+      // Last window closed, app.exit, or OS session end / logoff.
+      if let tauri::RunEvent::ExitRequested { .. } | tauri::RunEvent::Exit = event {
+        shutdown::run_shutdown(app, "exit");
+      }
+    });
+}
+
+/// Emit every status change of `service` as `service-status` and persist its transition; the
+/// backend's also go out as `backend-status` and feed the splash. A single consumer thread per service keeps events in order
+/// and keeps window work out from under the state lock.
+fn start_status_events(app: &tauri::AppHandle, service: &'static str, state: &BackendState) {
+  let (tx, rx) = std::sync::mpsc::channel::<StatusChange>();
+  if let Ok(mut g) = state.inner.lock() {
+    g.status_tx = Some(tx);
+  }
+  let app = app.clone();
+  std::thread::spawn(move || {
+    for change in rx {
+      if let Some(ref t) = change.transition {
+        status_history::persist(service, t);
+        telemetry::record_transition(service, t);
+      }
+      let _ = app.emit(
+        services::SERVICE_STATUS_EVENT,
+        services::ServiceStatusChange {
+          service,
+          status: change.status.clone(),
+          reason: change.reason.clone(),
+        },
+      );
+      if service == services::BACKEND_SERVICE_NAME {
+        let _ = app.emit(BACKEND_STATUS_EVENT, &change);
+        splash::on_status(&app, &change);
+      }
+    }
+  });
+}
+
+/// Built-in teardown, in priority order: metrics stream and IPC, backend (keep-alive or stop), held-back
+/// log notes, lock file.
+fn register_shutdown_hooks(app: &tauri::AppHandle) {
+  let hooks = app.state::<shutdown::ShutdownHooks>();
+  let handle = app.clone();
+  hooks.register("metrics_stream", shutdown::PRIORITY_PRODUCERS, Duration::from_secs(1), move || {
+    metrics::stop_stream(&handle.state::<metrics::MetricsState>());
+  });
+  let state = app.state::<Arc<BackendState>>().inner().clone();
+  let handle = app.clone();
+  hooks.register("backend", shutdown::PRIORITY_BACKEND, Duration::from_secs(5), move || {
+    fast_start::on_exit(&handle, &state);
+  });
+  let handle = app.clone();
+  hooks.register("companions", shutdown::PRIORITY_BACKEND, Duration::from_secs(5), move || {
+    services::stop_companions(&handle.state::<services::ServiceRegistry>());
+  });
+  hooks.register("ipc", shutdown::PRIORITY_PRODUCERS, Duration::from_secs(1), ipc::stop);
+  hooks.register("log_flush", shutdown::PRIORITY_FINAL, Duration::from_secs(1), flush_logs);
+  hooks.register("single_instance_lock", shutdown::PRIORITY_FINAL, Duration::from_secs(1), remove_lock);
 }
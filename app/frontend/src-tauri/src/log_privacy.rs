@@ -0,0 +1,276 @@
+// Privacy of the local logs. Every line written to app.log, backend_autostart.log and the child logs
+// goes through protect(): recognizable prompt/response payloads and credentials are redacted, and with
+// settings.log_encryption on, the line is encrypted with AES-256-CBC and authenticated with
+// HMAC-SHA256 (keys kept in the OS keyring) and written as "ENC1:" + base64(iv || ciphertext || tag).
+// read_log decrypts such lines, so the diagnostics export and crash reports see plain text. A backend
+// kept alive past the app (backend_keep_alive) writes its log directly and is neither redacted nor
+// encrypted.
+
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use regex::Regex;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, RwLock};
+
+use crate::error::AppError;
+use crate::settings::{Settings, SettingsState};
+
+const ENCRYPTED_PREFIX: &str = "ENC1:";
+const KEY_SECRET_NAME: &str = "log-encryption-key";
+/// AES-256 key followed by the HMAC key.
+const KEY_LEN: usize = 64;
+const IV_LEN: usize = 16;
+const TAG_LEN: usize = 32;
+const REDACTED: &str = "<redacted>";
+const UNREADABLE_LINE: &str = "<encrypted line, key unavailable>";
+/// JSON / repr fields whose values are conversation content or credentials.
+const SENSITIVE_FIELDS: &str =
+  "prompt|response|completion|content|query|answer|api_key|apikey|token|access_token|secret|password|authorization";
+
+type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+type HmacSha256 = Hmac<sha2::Sha256>;
+
+static ENCRYPT: AtomicBool = AtomicBool::new(false);
+static KEY: RwLock<Option<[u8; KEY_LEN]>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogPrivacyStatus {
+  pub redaction: bool,
+  pub encryption: bool,
+}
+
+fn patterns() -> &'static [(Regex, &'static str)] {
+  static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+  PATTERNS.get_or_init(|| {
+    vec![
+      // "prompt": "...", also with escaped quotes inside
+      (
+        Regex::new(&format!(r#"(?i)("(?:{})"\s*:\s*)"(?:[^"\\]|\\.)*""#, SENSITIVE_FIELDS)).unwrap(),
+        r#"${1}"<redacted>""#,
+      ),
+      // Python reprs: 'prompt': '...'
+      (
+        Regex::new(&format!(r#"(?i)('(?:{})'\s*:\s*)'(?:[^'\\]|\\.)*'"#, SENSITIVE_FIELDS)).unwrap(),
+        "${1}'<redacted>'",
+      ),
+      // Query strings and key=value logging
+      (
+        Regex::new(r"(?i)\b((?:prompt|api[_-]?key|access_token|token|secret|password)=)[^\s&]+").unwrap(),
+        "${1}<redacted>",
+      ),
+      (Regex::new(r"(?i)\b(bearer\s+)[A-Za-z0-9._~+/=-]+").unwrap(), "${1}<redacted>"),
+      // Provider API keys (sk-..., sk-ant-...)
+      (Regex::new(r"\bsk-[A-Za-z0-9_-]{16,}").unwrap(), REDACTED),
+    ]
+  })
+}
+
+/// `line` with conversation payloads and credentials replaced by <redacted>.
+pub fn redact(line: &str) -> Cow<'_, str> {
+  let mut out = Cow::Borrowed(line);
+  for (re, replacement) in patterns() {
+    if re.is_match(&out) {
+      out = Cow::Owned(re.replace_all(&out, *replacement).into_owned());
+    }
+  }
+  out
+}
+
+fn mac(key: &[u8; KEY_LEN]) -> HmacSha256 {
+  HmacSha256::new_from_slice(&key[32..]).expect("HMAC takes any key length")
+}
+
+fn seal(key: &[u8; KEY_LEN], line: &str) -> Option<String> {
+  let mut iv = [0u8; IV_LEN];
+  getrandom::fill(&mut iv).ok()?;
+  let ciphertext = Aes256CbcEnc::new_from_slices(&key[..32], &iv)
+    .ok()?
+    .encrypt_padded_vec_mut::<Pkcs7>(line.as_bytes());
+  let mut sealed = iv.to_vec();
+  sealed.extend_from_slice(&ciphertext);
+  let mut tag = mac(key);
+  tag.update(&sealed);
+  sealed.extend_from_slice(&tag.finalize().into_bytes());
+  Some(format!("{}{}", ENCRYPTED_PREFIX, BASE64.encode(sealed)))
+}
+
+fn open(key: &[u8; KEY_LEN], encoded: &str) -> Option<String> {
+  let sealed = BASE64.decode(encoded.trim()).ok()?;
+  if sealed.len() < IV_LEN + TAG_LEN {
+    return None;
+  }
+  let (body, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+  let mut check = mac(key);
+  check.update(body);
+  check.verify_slice(tag).ok()?;
+  let (iv, ciphertext) = body.split_at(IV_LEN);
+  let plain = Aes256CbcDec::new_from_slices(&key[..32], iv)
+    .ok()?
+    .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+    .ok()?;
+  String::from_utf8(plain).ok()
+}
+
+/// The log key from the keyring; with `create`, a new random key is stored when there is none.
+fn load_key(create: bool) -> Result<Option<[u8; KEY_LEN]>, String> {
+  if let Some(key) = KEY.read().ok().and_then(|g| *g) {
+    return Ok(Some(key));
+  }
+  let key = match crate::secrets::get(KEY_SECRET_NAME)? {
+    Some(stored) => {
+      let bytes = BASE64.decode(stored.trim()).map_err(|e| format!("{}: {}", KEY_SECRET_NAME, e))?;
+      <[u8; KEY_LEN]>::try_from(bytes.as_slice()).map_err(|_| format!("{}: wrong key length", KEY_SECRET_NAME))?
+    }
+    None if create => {
+      let mut key = [0u8; KEY_LEN];
+      getrandom::fill(&mut key).map_err(|e| e.to_string())?;
+      crate::secrets::active_store().set(KEY_SECRET_NAME, &BASE64.encode(key))?;
+      key
+    }
+    None => return Ok(None),
+  };
+  if let Ok(mut g) = KEY.write() {
+    *g = Some(key);
+  }
+  Ok(Some(key))
+}
+
+/// Redact `line` and, when log encryption is on, encrypt it. The result is written as one log line.
+pub fn protect(line: &str) -> String {
+  let redacted = redact(line);
+  if ENCRYPT.load(Ordering::Relaxed) {
+    if let Some(sealed) = KEY.read().ok().and_then(|g| *g).and_then(|key| seal(&key, &redacted)) {
+      return sealed;
+    }
+  }
+  redacted.into_owned()
+}
+
+/// Decrypt the encrypted lines of a log's text; plain lines pass through unchanged.
+pub fn unprotect(text: &str) -> String {
+  if !text.contains(ENCRYPTED_PREFIX) {
+    return text.to_string();
+  }
+  let key = load_key(false).ok().flatten();
+  text
+    .lines()
+    .map(|line| match line.strip_prefix(ENCRYPTED_PREFIX) {
+      Some(encoded) => key
+        .and_then(|k| open(&k, encoded))
+        .unwrap_or_else(|| UNREADABLE_LINE.to_string()),
+      None => line.to_string(),
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn apply(enabled: bool) -> Result<(), String> {
+  if enabled {
+    load_key(true)?;
+  }
+  ENCRYPT.store(enabled, Ordering::Relaxed);
+  Ok(())
+}
+
+pub fn init(settings: &Settings) {
+  if let Err(e) = apply(settings.log_encryption) {
+    crate::app_log(&format!("log privacy: encryption unavailable ({}), writing redacted plain text", e));
+  }
+}
+
+#[tauri::command]
+pub fn get_log_privacy() -> LogPrivacyStatus {
+  LogPrivacyStatus {
+    redaction: true,
+    encryption: ENCRYPT.load(Ordering::Relaxed),
+  }
+}
+
+/// Turn encryption of new log lines on or off. The key stays in the keyring, so lines written while
+/// it was on remain readable in the diagnostics export.
+#[tauri::command]
+pub fn set_log_encryption(settings: tauri::State<SettingsState>, enabled: bool) -> Result<LogPrivacyStatus, AppError> {
+  apply(enabled)?;
+  settings.update(|s| s.log_encryption = enabled)?;
+  crate::app_log(&format!("log privacy: encryption {}", if enabled { "on" } else { "off" }));
+  Ok(get_log_privacy())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const KEY_A: [u8; KEY_LEN] = [7; KEY_LEN];
+  const KEY_B: [u8; KEY_LEN] = [9; KEY_LEN];
+
+  #[test]
+  fn redacts_json_fields_with_escaped_quotes() {
+    let line = r#"{"event":"chat","prompt":"say \"hi\" to me","tokens":12}"#;
+    assert_eq!(redact(line), r#"{"event":"chat","prompt":"<redacted>","tokens":12}"#);
+    let line = r#"{"Authorization" : "Bearer abc", "response":"ok"}"#;
+    assert_eq!(redact(line), r#"{"Authorization" : "<redacted>", "response":"<redacted>"}"#);
+  }
+
+  #[test]
+  fn redacts_python_reprs() {
+    let line = r"payload={'query': 'it\'s private', 'n': 3}";
+    assert_eq!(redact(line), r"payload={'query': '<redacted>', 'n': 3}");
+  }
+
+  #[test]
+  fn redacts_key_value_pairs_and_bearer_tokens() {
+    assert_eq!(
+      redact("GET /ask?prompt=hello%20there&lang=el api_key=abc123"),
+      "GET /ask?prompt=<redacted>&lang=el api_key=<redacted>"
+    );
+    assert_eq!(redact("auth header: bearer eyJhbGciOi.x-y_z"), "auth header: bearer <redacted>");
+  }
+
+  #[test]
+  fn redacts_provider_keys() {
+    assert_eq!(redact("using sk-ant-REDACTED now"), "using <redacted> now");
+    // Too short to be a key.
+    assert_eq!(redact("task sk-1234"), "task sk-1234");
+  }
+
+  #[test]
+  fn plain_lines_are_borrowed_unchanged() {
+    let line = "[1700000000] autostart: probing health";
+    assert!(matches!(redact(line), Cow::Borrowed(l) if l == line));
+  }
+
+  #[test]
+  fn seal_open_round_trip() {
+    let line = r#"{"status":"ok","note":"ünïcode \"quoted\""}"#;
+    let sealed = seal(&KEY_A, line).unwrap();
+    let encoded = sealed.strip_prefix(ENCRYPTED_PREFIX).unwrap();
+    assert_eq!(open(&KEY_A, encoded).as_deref(), Some(line));
+    // A fresh IV per line.
+    assert_ne!(seal(&KEY_A, line).unwrap(), sealed);
+  }
+
+  #[test]
+  fn open_rejects_wrong_key() {
+    let sealed = seal(&KEY_A, "secret line").unwrap();
+    assert_eq!(open(&KEY_B, sealed.strip_prefix(ENCRYPTED_PREFIX).unwrap()), None);
+  }
+
+  #[test]
+  fn open_rejects_tampering() {
+    let sealed = seal(&KEY_A, "secret line").unwrap();
+    let mut bytes = BASE64.decode(sealed.strip_prefix(ENCRYPTED_PREFIX).unwrap()).unwrap();
+    for i in [0, IV_LEN, bytes.len() - 1] {
+      bytes[i] ^= 1;
+      assert_eq!(open(&KEY_A, &BASE64.encode(&bytes)), None, "flipped byte {}", i);
+      bytes[i] ^= 1;
+    }
+    assert_eq!(open(&KEY_A, &BASE64.encode(&bytes[..IV_LEN + TAG_LEN - 1])), None);
+    assert_eq!(open(&KEY_A, "not base64!"), None);
+  }
+}
@@ -0,0 +1,278 @@
+// Filtered reads of the diagnostics logs for the in-app support view, so a question like "WARN and
+// above from the last hour" does not ship a whole file to the webview. Lines are decrypted like the
+// bundle (diagnostics::read_log) and parsed into entries: the time comes from the shell's "[secs]"
+// prefix, a JSON field (at_ms, ts, time, timestamp) or a leading "YYYY-MM-DD HH:MM:SS" local time;
+// the level from a JSON field or an uppercase level word (ERROR, WARNING, ...). Lines without a level
+// count as info (the level the shell logs at), and continuation lines such as traceback frames belong
+// to the entry before them.
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+use crate::cli::LogLevel;
+use crate::error::AppError;
+
+const DEFAULT_LIMIT: usize = 500;
+const MAX_LIMIT: usize = 5_000;
+/// Below this a numeric timestamp is taken as seconds, above it as milliseconds.
+const SECONDS_CUTOFF: f64 = 100_000_000_000.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+  /// 1-based line number of the entry's first line.
+  pub line: usize,
+  /// Unix milliseconds, when the line (or the entry it continues) carries a time.
+  pub at_ms: Option<u64>,
+  /// None when the line names no level (counted as info when filtering).
+  pub level: Option<LogLevel>,
+  pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogQueryResult {
+  pub file: String,
+  /// Matching entries, oldest first; the newest `limit` when there are more.
+  pub entries: Vec<LogEntry>,
+  /// Entries in the file.
+  pub scanned: usize,
+  /// More entries matched than `limit`.
+  pub truncated: bool,
+}
+
+fn level_word() -> &'static Regex {
+  static RE: OnceLock<Regex> = OnceLock::new();
+  RE.get_or_init(|| Regex::new(r"\b(CRITICAL|FATAL|ERROR|WARNING|WARN|INFO|DEBUG|TRACE)\b").expect("level regex"))
+}
+
+fn text_time() -> &'static Regex {
+  static RE: OnceLock<Regex> = OnceLock::new();
+  RE.get_or_init(|| Regex::new(r"^\[?(\d{4}-\d{2}-\d{2})[ T](\d{2}:\d{2}:\d{2})").expect("time regex"))
+}
+
+fn parse_level(word: &str) -> Option<LogLevel> {
+  match word.to_ascii_uppercase().as_str() {
+    "CRITICAL" | "FATAL" | "ERROR" => Some(LogLevel::Error),
+    "WARNING" | "WARN" => Some(LogLevel::Warn),
+    "INFO" => Some(LogLevel::Info),
+    "DEBUG" | "TRACE" => Some(LogLevel::Debug),
+    _ => None,
+  }
+}
+
+fn millis(value: &serde_json::Value) -> Option<u64> {
+  if let Some(n) = value.as_f64() {
+    return Some(if n < SECONDS_CUTOFF { (n * 1000.0) as u64 } else { n as u64 });
+  }
+  let text = value.as_str()?;
+  chrono::DateTime::parse_from_rfc3339(text)
+    .ok()
+    .and_then(|t| u64::try_from(t.timestamp_millis()).ok())
+}
+
+fn local_millis(date: &str, time: &str) -> Option<u64> {
+  let naive = NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M:%S").ok()?;
+  let local = Local.from_local_datetime(&naive).earliest()?;
+  u64::try_from(local.timestamp_millis()).ok()
+}
+
+/// Time and level a line states itself; (None, None) for a continuation line.
+fn parse_line(line: &str) -> (Option<u64>, Option<LogLevel>) {
+  let trimmed = line.trim_start();
+  if trimmed.starts_with('{') {
+    if let Ok(serde_json::Value::Object(map)) = serde_json::from_str::<serde_json::Value>(trimmed) {
+      let at_ms = map
+        .get("at_ms")
+        .and_then(serde_json::Value::as_u64)
+        .or_else(|| ["ts", "time", "timestamp"].iter().find_map(|k| map.get(*k).and_then(millis)));
+      let level = ["level", "levelname", "severity"]
+        .iter()
+        .find_map(|k| map.get(*k).and_then(serde_json::Value::as_str).and_then(parse_level));
+      return (at_ms, level);
+    }
+  }
+  let mut at_ms = None;
+  let mut rest = line;
+  if let Some((secs, tail)) = line.strip_prefix('[').and_then(|l| l.split_once(']')) {
+    if let Ok(secs) = secs.parse::<u64>() {
+      at_ms = Some(secs * 1000);
+      rest = tail;
+    }
+  }
+  if at_ms.is_none() {
+    at_ms = text_time().captures(line).and_then(|c| local_millis(&c[1], &c[2]));
+  }
+  let level = level_word().captures(rest).and_then(|c| parse_level(&c[1]));
+  (at_ms, level)
+}
+
+/// Indented lines and traceback headers continue the entry before them.
+fn continues(line: &str) -> bool {
+  line.starts_with([' ', '\t']) || line.starts_with("Traceback ")
+}
+
+fn entries(text: &str) -> Vec<LogEntry> {
+  let mut out: Vec<LogEntry> = Vec::new();
+  for (i, line) in text.lines().enumerate() {
+    if line.trim().is_empty() {
+      continue;
+    }
+    let (at_ms, level) = parse_line(line);
+    if at_ms.is_none() && level.is_none() && continues(line) {
+      if let Some(last) = out.last_mut() {
+        last.text.push('\n');
+        last.text.push_str(line);
+        continue;
+      }
+    }
+    out.push(LogEntry {
+      line: i + 1,
+      at_ms,
+      level,
+      text: line.to_string(),
+    });
+  }
+  out
+}
+
+/// Entries of the log named `file` (as in preview_log_anonymization) at `level` or more severe,
+/// with a time within [since, until] (Unix ms; entries without a time are left out when either is
+/// given) and containing `contains` (case-insensitive). At most `limit` (default 500), newest kept.
+#[tauri::command]
+pub fn query_logs(
+  file: String,
+  level: Option<LogLevel>,
+  since: Option<u64>,
+  until: Option<u64>,
+  contains: Option<String>,
+  limit: Option<usize>,
+) -> Result<LogQueryResult, AppError> {
+  let path = crate::diagnostics::log_path_by_name(&file).map_err(AppError::InvalidInput)?;
+  let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+  let needle = contains.map(|c| c.to_lowercase()).filter(|c| !c.is_empty());
+  let all = entries(&crate::diagnostics::read_log(&path));
+  let scanned = all.len();
+  let in_range = |at_ms: Option<u64>| match (since, until) {
+    (None, None) => true,
+    _ => at_ms.is_some_and(|t| since.is_none_or(|s| t >= s) && until.is_none_or(|u| t <= u)),
+  };
+  let mut matched = Vec::new();
+  let mut truncated = false;
+  for entry in all.into_iter().rev() {
+    let severe_enough = level.is_none_or(|min| entry.level.unwrap_or(LogLevel::Info) <= min);
+    let found = needle.as_deref().is_none_or(|n| entry.text.to_lowercase().contains(n));
+    if !(severe_enough && found && in_range(entry.at_ms)) {
+      continue;
+    }
+    if matched.len() == limit {
+      truncated = true;
+      break;
+    }
+    matched.push(entry);
+  }
+  matched.reverse();
+  Ok(LogQueryResult {
+    file,
+    entries: matched,
+    scanned,
+    truncated,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SAMPLE: &str = r#"[1700000000] autostart: probing health
+[1700000005] autostart: health failed -> NOT_READY (ERROR)
+{"at_ms":1700000010123,"level":"warning","msg":"slow start"}
+{"ts":1700000020,"levelname":"INFO","msg":"seconds timestamp"}
+{"time":"2023-11-14T22:13:40Z","severity":"debug","msg":"rfc3339"}
+2023-11-14 22:14:00,123 ERROR main: request failed
+Traceback (most recent call last):
+  File "main.py", line 3, in <module>
+    raise ValueError("bad")
+
+plain line without level
+{not json at all"#;
+
+  #[test]
+  fn parse_line_shell_prefix() {
+    assert_eq!(parse_line("[1700000000] autostart: probing health"), (Some(1_700_000_000_000), None));
+    assert_eq!(parse_line("[1700000000] WARN: port busy"), (Some(1_700_000_000_000), Some(LogLevel::Warn)));
+    // Not a seconds prefix: the level word is still found, the time is not.
+    assert_eq!(parse_line("[main] FATAL crash"), (None, Some(LogLevel::Error)));
+  }
+
+  #[test]
+  fn parse_line_json_fields() {
+    assert_eq!(
+      parse_line(r#"{"at_ms":1700000010123,"level":"warning"}"#),
+      (Some(1_700_000_010_123), Some(LogLevel::Warn))
+    );
+    assert_eq!(
+      parse_line(r#"{"ts":1700000020.5,"levelname":"CRITICAL"}"#),
+      (Some(1_700_000_020_500), Some(LogLevel::Error))
+    );
+    assert_eq!(parse_line(r#"{"timestamp":1700000030000}"#), (Some(1_700_000_030_000), None));
+    assert_eq!(
+      parse_line(r#"{"time":"2023-11-14T22:13:40Z","severity":"trace"}"#),
+      (Some(1_700_000_020_000), Some(LogLevel::Debug))
+    );
+  }
+
+  #[test]
+  fn parse_line_text_time_is_local() {
+    let expected = local_millis("2023-11-14", "22:14:00");
+    assert!(expected.is_some());
+    assert_eq!(parse_line("2023-11-14 22:14:00,123 ERROR main: x"), (expected, Some(LogLevel::Error)));
+    assert_eq!(parse_line("[2023-11-14T22:14:00] INFO ready"), (expected, Some(LogLevel::Info)));
+  }
+
+  #[test]
+  fn parse_line_continuations_and_plain_text() {
+    assert_eq!(parse_line("  File \"main.py\", line 3"), (None, None));
+    assert_eq!(parse_line("plain line without level"), (None, None));
+    // Lowercase words are not levels.
+    assert_eq!(parse_line("an error occurred"), (None, None));
+    assert_eq!(parse_line("{not json at all"), (None, None));
+  }
+
+  #[test]
+  fn entries_of_mixed_sample() {
+    let all = entries(SAMPLE);
+    let lines: Vec<usize> = all.iter().map(|e| e.line).collect();
+    assert_eq!(lines, [1, 2, 3, 4, 5, 6, 11, 12]);
+    let levels: Vec<Option<LogLevel>> = all.iter().map(|e| e.level).collect();
+    assert_eq!(
+      levels,
+      [
+        None,
+        Some(LogLevel::Error),
+        Some(LogLevel::Warn),
+        Some(LogLevel::Info),
+        Some(LogLevel::Debug),
+        Some(LogLevel::Error),
+        None,
+        None
+      ]
+    );
+    assert_eq!(all[2].at_ms, Some(1_700_000_010_123));
+    assert_eq!(all[3].at_ms, Some(1_700_000_020_000));
+    // The traceback header and its frames belong to the ERROR line before them.
+    let traceback = &all[5];
+    assert_eq!(traceback.text.lines().count(), 4);
+    assert!(traceback.text.starts_with("2023-11-14 22:14:00,123 ERROR"));
+    assert!(traceback.text.ends_with("raise ValueError(\"bad\")"));
+    assert_eq!(all[7].text, "{not json at all");
+  }
+
+  #[test]
+  fn leading_continuation_starts_an_entry() {
+    let all = entries("  orphan frame\n[1700000000] next");
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].text, "  orphan frame");
+    assert_eq!(all[1].at_ms, Some(1_700_000_000_000));
+  }
+}
@@ -0,0 +1,84 @@
+// Flood protection for the log writers (app.log, backend_autostart.log, child logs). A message equal to
+// the previous one in the same file is collapsed into "last message repeated N times", and each file
+// gets a per-second line budget; lines over budget are dropped and counted in a note once the next
+// second starts. A flapping backend or a tight retry loop can then no longer fill the disk.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const LINES_PER_SECOND: u32 = 50;
+
+struct Gate {
+  last: Option<String>,
+  repeats: u64,
+  window_start: Instant,
+  written: u32,
+  dropped: u64,
+}
+
+static GATES: Mutex<Option<HashMap<PathBuf, Gate>>> = Mutex::new(None);
+
+/// The lines to write to `path` for `msg`: none when it is a repeat or over budget, otherwise the
+/// message, preceded by any pending repeat / dropped notes.
+pub fn admit(path: &Path, msg: &str) -> Vec<String> {
+  let Ok(mut gates) = GATES.lock() else {
+    return vec![msg.to_string()];
+  };
+  let gate = gates.get_or_insert_with(HashMap::new).entry(path.to_path_buf()).or_insert_with(|| Gate {
+    last: None,
+    repeats: 0,
+    window_start: Instant::now(),
+    written: 0,
+    dropped: 0,
+  });
+  if gate.last.as_deref() == Some(msg) {
+    gate.repeats += 1;
+    return Vec::new();
+  }
+  let mut out = Vec::new();
+  if gate.repeats > 0 {
+    out.push(format!("last message repeated {} times", gate.repeats));
+    gate.repeats = 0;
+  }
+  gate.last = Some(msg.to_string());
+  if gate.window_start.elapsed() >= Duration::from_secs(1) {
+    if gate.dropped > 0 {
+      out.push(format!("{} lines dropped (over {} lines/s)", gate.dropped, LINES_PER_SECOND));
+    }
+    gate.window_start = Instant::now();
+    gate.written = 0;
+    gate.dropped = 0;
+  }
+  if gate.written >= LINES_PER_SECOND {
+    gate.dropped += 1;
+    return out;
+  }
+  gate.written += 1;
+  out.push(msg.to_string());
+  out
+}
+
+/// Repeat / dropped notes still held back, per file, for writing before exit.
+pub fn pending() -> Vec<(PathBuf, Vec<String>)> {
+  let Ok(mut gates) = GATES.lock() else {
+    return Vec::new();
+  };
+  let mut out = Vec::new();
+  for (path, gate) in gates.iter_mut().flatten() {
+    let mut notes = Vec::new();
+    if gate.repeats > 0 {
+      notes.push(format!("last message repeated {} times", gate.repeats));
+    }
+    if gate.dropped > 0 {
+      notes.push(format!("{} lines dropped (over {} lines/s)", gate.dropped, LINES_PER_SECOND));
+    }
+    gate.repeats = 0;
+    gate.dropped = 0;
+    if !notes.is_empty() {
+      out.push((path.clone(), notes));
+    }
+  }
+  out
+}
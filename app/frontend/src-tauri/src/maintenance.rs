@@ -0,0 +1,326 @@
+// Scheduled maintenance restart of the managed backend (it leaks memory over multi-day sessions).
+// settings.maintenance_restart picks a local time of day and/or a maximum uptime; a due restart waits
+// until no backend request is in flight (attachment uploads, plus chat streams the frontend declares
+// with begin/end_backend_activity), then goes through the same stop / rerun-autostart path as
+// backup and restore. `backend-maintenance` is emitted before and after.
+
+use chrono::{Local, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+use crate::BackendState;
+use crate::error::AppError;
+use crate::settings::SettingsState;
+
+pub const BACKEND_MAINTENANCE_EVENT: &str = "backend-maintenance";
+const CHECK_INTERVAL_SECS: u64 = 60;
+/// Activities older than this are assumed leaked (e.g. a window closed mid-stream) and ignored.
+const ACTIVITY_STALE_SECS: u64 = 30 * 60;
+const DAILY_AT_FORMAT: &str = "%H:%M";
+
+/// Stored in settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceSettings {
+  pub enabled: bool,
+  /// Local time of day, "HH:MM".
+  pub daily_at: Option<String>,
+  /// Restart once the backend has been READY this long.
+  pub max_uptime_hours: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceRun {
+  pub at_ms: u64,
+  pub reason: String,
+  /// Backend status after the restart.
+  pub status: Option<String>,
+  pub error: Option<AppError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityInfo {
+  pub id: u64,
+  pub kind: String,
+  pub age_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceStatus {
+  pub schedule: MaintenanceSettings,
+  /// Reason of a restart that is due but waiting for in-flight requests.
+  pub pending: Option<String>,
+  pub uptime_secs: Option<u64>,
+  pub activities: Vec<ActivityInfo>,
+  pub last_run: Option<MaintenanceRun>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct MaintenanceEvent<'a> {
+  /// "starting" | "finished"
+  phase: &'static str,
+  reason: &'a str,
+  status: Option<&'a str>,
+  error: Option<&'a AppError>,
+}
+
+struct Activity {
+  kind: String,
+  started: Instant,
+}
+
+#[derive(Default)]
+struct Scheduler {
+  last_tick: Option<NaiveDateTime>,
+  pending: Option<String>,
+  waiting_logged: bool,
+  last_run: Option<MaintenanceRun>,
+}
+
+#[derive(Default)]
+pub struct MaintenanceState {
+  next_id: AtomicU64,
+  activities: Mutex<HashMap<u64, Activity>>,
+  scheduler: Mutex<Scheduler>,
+}
+
+impl MaintenanceState {
+  fn begin(&self, kind: &str) -> u64 {
+    crate::idle::touch();
+    let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+    if let Ok(mut g) = self.activities.lock() {
+      g.insert(
+        id,
+        Activity {
+          kind: kind.to_string(),
+          started: Instant::now(),
+        },
+      );
+    }
+    id
+  }
+
+  fn end(&self, id: u64) -> bool {
+    crate::idle::touch();
+    self.activities.lock().map(|mut g| g.remove(&id).is_some()).unwrap_or(false)
+  }
+
+  fn activities(&self) -> Vec<ActivityInfo> {
+    let Ok(g) = self.activities.lock() else {
+      return Vec::new();
+    };
+    let mut out: Vec<ActivityInfo> = g
+      .iter()
+      .map(|(id, a)| ActivityInfo {
+        id: *id,
+        kind: a.kind.clone(),
+        age_secs: a.started.elapsed().as_secs(),
+      })
+      .collect();
+    out.sort_by_key(|a| a.id);
+    out
+  }
+
+  pub(crate) fn busy(&self) -> bool {
+    self.activities().iter().any(|a| a.age_secs < ACTIVITY_STALE_SECS)
+  }
+}
+
+/// Marks a backend request in flight for as long as it lives.
+pub struct ActivityGuard {
+  app: tauri::AppHandle,
+  id: u64,
+}
+
+impl Drop for ActivityGuard {
+  fn drop(&mut self) {
+    self.app.state::<MaintenanceState>().end(self.id);
+  }
+}
+
+pub fn activity(app: &tauri::AppHandle, kind: &str) -> ActivityGuard {
+  ActivityGuard {
+    app: app.clone(),
+    id: app.state::<MaintenanceState>().begin(kind),
+  }
+}
+
+fn parse_daily_at(value: &str) -> Result<NaiveTime, String> {
+  NaiveTime::parse_from_str(value.trim(), DAILY_AT_FORMAT).map_err(|_| format!("daily_at must be HH:MM: {:?}", value))
+}
+
+/// Time since the backend last became reachable (READY or DEGRADED), while it is.
+fn uptime(state: &BackendState) -> Option<Duration> {
+  let g = state.inner.lock().ok()?;
+  if !crate::is_up(&g.status) {
+    return None;
+  }
+  let since = g
+    .status_history
+    .iter()
+    .rev()
+    .find(|t| crate::is_up(&t.to) && !crate::is_up(&t.from))?
+    .at_ms;
+  Some(Duration::from_millis(crate::status_history::now_ms().saturating_sub(since)))
+}
+
+/// The user stopped the backend; scheduled restarts must not bring it back.
+fn stopped(state: &BackendState) -> bool {
+  state.inner.lock().is_ok_and(|g| g.status == "STOPPED")
+}
+
+/// Why a restart is due at `now`, given the previous check at `prev`.
+fn due(
+  schedule: &MaintenanceSettings,
+  prev: Option<NaiveDateTime>,
+  now: NaiveDateTime,
+  uptime: Option<Duration>,
+) -> Option<String> {
+  if let (Some(at), Some(prev)) = (schedule.daily_at.as_deref(), prev) {
+    if let Ok(time) = parse_daily_at(at) {
+      let target = now.date().and_time(time);
+      if prev < target && target <= now {
+        return Some(format!("daily at {}", at.trim()));
+      }
+    }
+  }
+  if let (Some(hours), Some(up)) = (schedule.max_uptime_hours, uptime) {
+    if up >= Duration::from_secs(hours * 3600) {
+      return Some(format!("uptime over {}h", hours));
+    }
+  }
+  None
+}
+
+fn emit(app: &tauri::AppHandle, phase: &'static str, reason: &str, result: Option<&Result<String, AppError>>) {
+  let _ = app.emit(
+    BACKEND_MAINTENANCE_EVENT,
+    MaintenanceEvent {
+      phase,
+      reason,
+      status: result.and_then(|r| r.as_deref().ok()),
+      error: result.and_then(|r| r.as_ref().err()),
+    },
+  );
+}
+
+async fn restart(app: &tauri::AppHandle, state: &Arc<BackendState>, reason: &str) -> Result<String, AppError> {
+  crate::app_log(&format!("maintenance: restarting backend ({})", reason));
+  emit(app, "starting", reason, None);
+  let stopped = crate::stop_owned_gracefully(state).await;
+  let restarted = crate::rerun_autostart(app, state).await;
+  let result = stopped.and(restarted);
+  crate::app_log(&format!("maintenance: restart finished: {:?}", result));
+  emit(app, "finished", reason, Some(&result));
+  result
+}
+
+async fn tick(app: &tauri::AppHandle) {
+  let schedule = app.state::<SettingsState>().get().maintenance_restart;
+  let state = app.state::<Arc<BackendState>>().inner().clone();
+  let maintenance = app.state::<MaintenanceState>();
+  let now = Local::now().naive_local();
+  let managed = crate::profiles::active().autostart && !crate::service_mode::is_enabled(app);
+
+  let reason = {
+    let Ok(mut s) = maintenance.scheduler.lock() else {
+      return;
+    };
+    let prev = s.last_tick.replace(now);
+    if !schedule.enabled || !managed || stopped(&state) || crate::idle::suspended(&state) {
+      s.pending = None;
+      return;
+    }
+    if s.pending.is_none() {
+      s.pending = due(&schedule, prev, now, uptime(&state));
+      s.waiting_logged = false;
+    }
+    let Some(reason) = s.pending.clone() else {
+      return;
+    };
+    if maintenance.busy() {
+      if !s.waiting_logged {
+        crate::app_log(&format!("maintenance: restart due ({}), waiting for in-flight requests", reason));
+        s.waiting_logged = true;
+      }
+      return;
+    }
+    s.pending = None;
+    reason
+  };
+
+  let result = restart(app, &state, &reason).await;
+  if let Ok(mut s) = maintenance.scheduler.lock() {
+    s.last_run = Some(MaintenanceRun {
+      at_ms: crate::status_history::now_ms(),
+      reason,
+      status: result.as_ref().ok().cloned(),
+      error: result.err(),
+    });
+  };
+}
+
+/// Check the schedule every CHECK_INTERVAL_SECS for the rest of the session.
+pub fn start(app: &tauri::AppHandle) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tick(&app).await;
+      tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+  });
+}
+
+#[tauri::command]
+pub fn get_maintenance_status(
+  app: tauri::AppHandle,
+  settings: tauri::State<SettingsState>,
+  maintenance: tauri::State<MaintenanceState>,
+) -> MaintenanceStatus {
+  let (pending, last_run) = maintenance
+    .scheduler
+    .lock()
+    .map(|s| (s.pending.clone(), s.last_run.clone()))
+    .unwrap_or_default();
+  MaintenanceStatus {
+    schedule: settings.get().maintenance_restart,
+    pending,
+    uptime_secs: uptime(&app.state::<Arc<BackendState>>()).map(|d| d.as_secs()),
+    activities: maintenance.activities(),
+    last_run,
+  }
+}
+
+/// Save the restart schedule; the scheduler picks it up on its next check.
+#[tauri::command]
+pub fn set_maintenance_schedule(
+  settings: tauri::State<SettingsState>,
+  schedule: MaintenanceSettings,
+) -> Result<MaintenanceSettings, AppError> {
+  if let Some(ref at) = schedule.daily_at {
+    parse_daily_at(at).map_err(AppError::InvalidInput)?;
+  }
+  if schedule.max_uptime_hours == Some(0) {
+    return Err(AppError::InvalidInput("max_uptime_hours must be at least 1".to_string()));
+  }
+  settings.update(|s| s.maintenance_restart = schedule.clone())?;
+  crate::app_log(&format!("maintenance: schedule {:?}", schedule));
+  Ok(schedule)
+}
+
+/// Mark a backend request (e.g. a chat stream) as in flight; maintenance restarts and idle suspend
+/// wait for it, and an idle-suspended backend is woken. Returns the id to pass to end_backend_activity.
+#[tauri::command]
+pub fn begin_backend_activity(app: tauri::AppHandle, maintenance: tauri::State<MaintenanceState>, kind: String) -> u64 {
+  crate::idle::on_interaction(&app);
+  maintenance.begin(&kind)
+}
+
+#[tauri::command]
+pub fn end_backend_activity(maintenance: tauri::State<MaintenanceState>, id: u64) -> bool {
+  maintenance.end(id)
+}
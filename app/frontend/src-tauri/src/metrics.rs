@@ -0,0 +1,183 @@
+// Resource metrics for the managed backend child (CPU, RSS, threads, handles, uptime), on demand
+// via get_backend_metrics or periodically as `backend-metrics` events.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{Emitter, Manager};
+
+use crate::BackendState;
+use crate::error::AppError;
+
+pub const BACKEND_METRICS_EVENT: &str = "backend-metrics";
+const METRICS_INTERVAL_MIN_MS: u64 = 1_000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendMetrics {
+  /// None when there is no managed child (not started, service mode, or reused external backend).
+  pub pid: Option<u32>,
+  /// CPU usage normalized to 0..=100 across all logical cores.
+  pub cpu_percent: Option<f32>,
+  pub rss_bytes: Option<u64>,
+  pub virtual_bytes: Option<u64>,
+  pub thread_count: Option<u32>,
+  /// Windows only.
+  pub handle_count: Option<u32>,
+  pub uptime_secs: Option<u64>,
+}
+
+impl BackendMetrics {
+  fn empty(pid: Option<u32>) -> Self {
+    Self {
+      pid,
+      cpu_percent: None,
+      rss_bytes: None,
+      virtual_bytes: None,
+      thread_count: None,
+      handle_count: None,
+      uptime_secs: None,
+    }
+  }
+}
+
+pub struct MetricsState {
+  sys: Mutex<(System, Option<Instant>)>,
+  /// Bumped on every start/stop so an older stream thread exits on its next tick.
+  stream_generation: AtomicU64,
+}
+
+impl Default for MetricsState {
+  fn default() -> Self {
+    Self {
+      sys: Mutex::new((System::new(), None)),
+      stream_generation: AtomicU64::new(0),
+    }
+  }
+}
+
+#[cfg(windows)]
+fn windows_counts(pid: u32) -> (Option<u32>, Option<u32>) {
+  use windows_sys::Win32::Foundation::{CloseHandle, INVALID_HANDLE_VALUE};
+  use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+  };
+  use windows_sys::Win32::System::Threading::{GetProcessHandleCount, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+  let mut handles = None;
+  let mut threads = None;
+  unsafe {
+    let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+    if !process.is_null() {
+      let mut count = 0u32;
+      if GetProcessHandleCount(process, &mut count) != 0 {
+        handles = Some(count);
+      }
+      CloseHandle(process);
+    }
+
+    let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
+    if snapshot != INVALID_HANDLE_VALUE {
+      let mut entry: THREADENTRY32 = std::mem::zeroed();
+      entry.dwSize = std::mem::size_of::<THREADENTRY32>() as u32;
+      let mut count = 0u32;
+      if Thread32First(snapshot, &mut entry) != 0 {
+        loop {
+          if entry.th32OwnerProcessID == pid {
+            count += 1;
+          }
+          if Thread32Next(snapshot, &mut entry) == 0 {
+            break;
+          }
+        }
+        threads = Some(count);
+      }
+      CloseHandle(snapshot);
+    }
+  }
+  (threads, handles)
+}
+
+fn managed_pid(state: &BackendState) -> Option<u32> {
+  let g = state.inner.lock().ok()?;
+  g.child.as_ref().map(|c| c.id()).or(g.adopted_pid)
+}
+
+/// Samples the process. CPU usage needs two refreshes at least MINIMUM_CPU_UPDATE_INTERVAL apart,
+/// so a cold (or stale) sampler refreshes twice.
+fn sample(metrics: &MetricsState, pid: Option<u32>) -> BackendMetrics {
+  let Some(raw_pid) = pid else {
+    return BackendMetrics::empty(None);
+  };
+  let pid = Pid::from_u32(raw_pid);
+  let kind = ProcessRefreshKind::nothing().with_cpu().with_memory().with_tasks();
+  let mut g = match metrics.sys.lock() {
+    Ok(g) => g,
+    Err(_) => return BackendMetrics::empty(Some(raw_pid)),
+  };
+  let (sys, last_refresh) = &mut *g;
+  let stale = last_refresh.is_none_or(|t| t.elapsed() > Duration::from_secs(5));
+  if stale {
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, kind);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+  }
+  sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, kind);
+  *last_refresh = Some(Instant::now());
+
+  let Some(process) = sys.process(pid) else {
+    return BackendMetrics::empty(Some(raw_pid));
+  };
+  let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) as f32;
+  #[cfg(windows)]
+  let (thread_count, handle_count) = windows_counts(raw_pid);
+  #[cfg(not(windows))]
+  let (thread_count, handle_count) = (process.tasks().map(|t| t.len() as u32), None);
+  BackendMetrics {
+    pid: Some(raw_pid),
+    cpu_percent: Some(process.cpu_usage() / cores),
+    rss_bytes: Some(process.memory()),
+    virtual_bytes: Some(process.virtual_memory()),
+    thread_count,
+    handle_count,
+    uptime_secs: Some(process.run_time()),
+  }
+}
+
+#[tauri::command]
+pub async fn get_backend_metrics(app: tauri::AppHandle) -> Result<BackendMetrics, AppError> {
+  let worker = app.clone();
+  let sampled = tauri::async_runtime::spawn_blocking(move || {
+    let pid = managed_pid(worker.state::<Arc<BackendState>>().inner());
+    sample(worker.state::<MetricsState>().inner(), pid)
+  });
+  crate::command_stats::track(&app, "get_backend_metrics", async {
+    sampled.await.map_err(|e| AppError::Internal(e.to_string()))
+  }).await
+}
+
+/// Emit `backend-metrics` every `interval_ms` (min 1000) until stop_backend_metrics_stream. Replaces any running stream.
+#[tauri::command]
+pub fn start_backend_metrics_stream(app: tauri::AppHandle, metrics: tauri::State<MetricsState>, interval_ms: u64) {
+  let interval = Duration::from_millis(interval_ms.max(METRICS_INTERVAL_MIN_MS));
+  let generation = metrics.stream_generation.fetch_add(1, Ordering::SeqCst) + 1;
+  std::thread::spawn(move || loop {
+    let metrics = app.state::<MetricsState>();
+    if metrics.stream_generation.load(Ordering::SeqCst) != generation {
+      return;
+    }
+    let pid = managed_pid(app.state::<Arc<BackendState>>().inner());
+    let _ = app.emit(BACKEND_METRICS_EVENT, sample(metrics.inner(), pid));
+    std::thread::sleep(interval);
+  });
+}
+
+/// Ends any running stream (its thread exits before the next sample).
+pub(crate) fn stop_stream(metrics: &MetricsState) {
+  metrics.stream_generation.fetch_add(1, Ordering::SeqCst);
+}
+
+#[tauri::command]
+pub fn stop_backend_metrics_stream(metrics: tauri::State<MetricsState>) {
+  stop_stream(&metrics);
+}
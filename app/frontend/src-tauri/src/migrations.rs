@@ -0,0 +1,209 @@
+// Versioned migrations of the data directory layout. <base>/data_version.json records the format
+// version; at startup (after the single-instance lock) every step newer than it runs in order, and
+// the version is written after each one, so an interrupted upgrade resumes at the failed step
+// instead of leaving a mix of layouts. Steps must be idempotent. A directory from a newer release
+// is left untouched.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::paths::AppPaths;
+
+const VERSION_FILE_NAME: &str = "data_version.json";
+
+struct Migration {
+  to: u32,
+  name: &'static str,
+  run: fn(&AppPaths) -> Result<(), String>,
+}
+
+/// Ordered by `to`; the last entry is the current data format version.
+const MIGRATIONS: &[Migration] = &[
+  Migration {
+    to: 1,
+    name: "logs_subdir",
+    run: move_root_logs,
+  },
+  Migration {
+    to: 2,
+    name: "settings_schema",
+    run: normalize_settings,
+  },
+];
+
+pub fn current_version() -> u32 {
+  MIGRATIONS.last().map(|m| m.to).unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VersionFile {
+  version: u32,
+  app_version: String,
+  updated_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationFailure {
+  pub step: &'static str,
+  pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+  pub from: u32,
+  pub to: u32,
+  pub current: u32,
+  pub applied: Vec<&'static str>,
+  pub failed: Option<MigrationFailure>,
+  /// The directory was written by a newer release; nothing was changed.
+  pub newer_than_app: bool,
+}
+
+static REPORT: OnceLock<MigrationReport> = OnceLock::new();
+
+fn now_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0)
+}
+
+fn read_version(base: &Path) -> Option<u32> {
+  let text = std::fs::read_to_string(base.join(VERSION_FILE_NAME)).ok()?;
+  serde_json::from_str::<VersionFile>(&text).ok().map(|v| v.version)
+}
+
+fn write_version(base: &Path, version: u32) -> Result<(), String> {
+  std::fs::create_dir_all(base).map_err(|e| e.to_string())?;
+  let file = VersionFile {
+    version,
+    app_version: env!("CARGO_PKG_VERSION").to_string(),
+    updated_at: now_secs(),
+  };
+  let text = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+  let tmp = base.join(format!("{}.tmp", VERSION_FILE_NAME));
+  std::fs::write(&tmp, text).map_err(|e| e.to_string())?;
+  std::fs::rename(&tmp, base.join(VERSION_FILE_NAME)).map_err(|e| e.to_string())
+}
+
+/// Directory holds data from a release without a version file (anything besides the lock/runtime dir).
+fn has_unversioned_data(paths: &AppPaths) -> bool {
+  std::fs::read_dir(&paths.base)
+    .map(|entries| entries.filter_map(|e| e.ok()).any(|e| e.path() != paths.runtime))
+    .unwrap_or(false)
+}
+
+/// v1: logs written directly under the data dir move into logs/. A name already taken in logs/
+/// keeps the old file as legacy-<name>.
+fn move_root_logs(paths: &AppPaths) -> Result<(), String> {
+  let entries = match std::fs::read_dir(&paths.base) {
+    Ok(e) => e,
+    Err(_) => return Ok(()),
+  };
+  std::fs::create_dir_all(&paths.logs).map_err(|e| e.to_string())?;
+  for entry in entries.filter_map(|e| e.ok()) {
+    let path = entry.path();
+    if !path.is_file() || path.extension().is_none_or(|x| x != "log") {
+      continue;
+    }
+    let name = entry.file_name().to_string_lossy().to_string();
+    let mut dest = paths.logs.join(&name);
+    if dest.exists() {
+      dest = paths.logs.join(format!("legacy-{}", name));
+    }
+    std::fs::rename(&path, &dest).map_err(|e| format!("{} -> {}: {}", path.display(), dest.display(), e))?;
+  }
+  Ok(())
+}
+
+/// v2: rewrite settings.json through the current Settings schema (fills new fields, drops unknown
+/// ones). The previous file is kept as settings.json.bak.
+fn normalize_settings(paths: &AppPaths) -> Result<(), String> {
+  let path = paths.base.join("settings.json");
+  let Ok(text) = std::fs::read_to_string(&path) else {
+    return Ok(());
+  };
+  let settings: crate::settings::Settings =
+    serde_json::from_str(&text).map_err(|e| format!("settings.json unreadable: {}", e))?;
+  let normalized = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+  if normalized == text {
+    return Ok(());
+  }
+  std::fs::write(paths.base.join("settings.json.bak"), &text).map_err(|e| e.to_string())?;
+  let tmp = paths.base.join("settings.json.tmp");
+  std::fs::write(&tmp, normalized).map_err(|e| e.to_string())?;
+  std::fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+fn migrate(paths: &AppPaths) -> MigrationReport {
+  let current = current_version();
+  let from = match read_version(&paths.base) {
+    Some(v) => v,
+    None if has_unversioned_data(paths) => 0,
+    // Fresh install: already in the current layout.
+    None => current,
+  };
+  let mut report = MigrationReport {
+    from,
+    to: from,
+    current,
+    applied: Vec::new(),
+    failed: None,
+    newer_than_app: from > current,
+  };
+  if report.newer_than_app {
+    return report;
+  }
+  for step in MIGRATIONS.iter().filter(|m| m.to > from) {
+    if let Err(error) = (step.run)(paths).and_then(|_| write_version(&paths.base, step.to)) {
+      report.failed = Some(MigrationFailure { step: step.name, error });
+      return report;
+    }
+    report.to = step.to;
+    report.applied.push(step.name);
+  }
+  if read_version(&paths.base).is_none() {
+    if let Err(error) = write_version(&paths.base, current) {
+      report.failed = Some(MigrationFailure { step: "write_version", error });
+    }
+  }
+  report
+}
+
+/// Run pending migrations once per session and log the outcome.
+pub(crate) fn run() -> &'static MigrationReport {
+  REPORT.get_or_init(|| {
+    let report = migrate(crate::paths::get());
+    if report.newer_than_app {
+      crate::app_log(&format!(
+        "migrations: data format {} is newer than this app ({}), leaving it as is",
+        report.from, report.current
+      ));
+    } else if let Some(ref f) = report.failed {
+      crate::app_log(&format!(
+        "migrations: step {} failed at version {}: {}",
+        f.step, report.to, f.error
+      ));
+    } else if !report.applied.is_empty() {
+      crate::app_log(&format!(
+        "migrations: {} -> {} ({})",
+        report.from,
+        report.to,
+        report.applied.join(", ")
+      ));
+    }
+    report
+  })
+}
+
+/// Outcome of this session's startup migrations (None if they have not run).
+pub(crate) fn report() -> Option<&'static MigrationReport> {
+  REPORT.get()
+}
+
+#[tauri::command]
+pub fn get_migration_report() -> Option<MigrationReport> {
+  report().cloned()
+}
@@ -0,0 +1,369 @@
+// Local model weights under <base>/models, recorded in models/manifest.json (name, file, size,
+// sha256). The dir is passed to the backend as AI_MENTOR_MODELS_DIR; the backend lists the manifest at
+// GET /api/v1/models (routes/api_v1/local_models.py). Downloads stream into <file>.part while
+// hashing, emit `model-download-progress`, and are only renamed into place and added to the manifest
+// once the SHA-256 matches.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{Emitter, Manager};
+
+use crate::error::AppError;
+
+pub const MODEL_DOWNLOAD_PROGRESS_EVENT: &str = "model-download-progress";
+const MODELS_DIR_NAME: &str = "models";
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+const PARTIAL_SUFFIX: &str = ".part";
+/// A download that delivers nothing for this long is abandoned.
+const STALL_TIMEOUT_SECS: u64 = 60;
+const PROGRESS_INTERVAL_MS: u64 = 250;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+  pub name: String,
+  pub file: String,
+  pub size: u64,
+  pub sha256: String,
+  pub source: Option<String>,
+  pub installed_at: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Manifest {
+  models: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInfo {
+  pub name: String,
+  pub file: String,
+  /// Size on disk (manifest size if the file is missing).
+  pub size: u64,
+  /// From the manifest; None for files that were copied in by hand.
+  pub sha256: Option<String>,
+  /// Listed in the manifest but the file is gone.
+  pub missing: bool,
+  pub downloading: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+  pub name: String,
+  pub downloaded: u64,
+  pub total: Option<u64>,
+  pub done: bool,
+}
+
+struct Download {
+  /// Target file; two downloads of the same file would share its .part file.
+  file: String,
+  cancel: Arc<AtomicBool>,
+}
+
+/// In-flight downloads by model name. Also serializes manifest writes.
+#[derive(Default)]
+pub struct ModelsState {
+  downloads: Mutex<HashMap<String, Download>>,
+  manifest: Mutex<()>,
+}
+
+pub fn models_dir() -> PathBuf {
+  crate::app_base_dir().join(MODELS_DIR_NAME)
+}
+
+fn manifest_path() -> PathBuf {
+  models_dir().join(MANIFEST_FILE_NAME)
+}
+
+fn read_manifest() -> Manifest {
+  std::fs::read_to_string(manifest_path())
+    .ok()
+    .and_then(|t| serde_json::from_str(&t).ok())
+    .unwrap_or_default()
+}
+
+fn write_manifest(manifest: &Manifest) -> Result<(), String> {
+  let path = manifest_path();
+  std::fs::create_dir_all(models_dir()).map_err(|e| e.to_string())?;
+  let tmp = path.with_extension("json.tmp");
+  let json = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+  std::fs::write(&tmp, json).map_err(|e| e.to_string())?;
+  std::fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+/// Names and file names stay within [A-Za-z0-9._-] so they cannot leave the models dir.
+fn validate_name(kind: &str, value: &str) -> Result<(), AppError> {
+  let valid = !value.is_empty()
+    && !value.starts_with('.')
+    && value.len() <= 128
+    && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+  if valid {
+    Ok(())
+  } else {
+    Err(AppError::InvalidInput(format!("invalid model {}: {:?}", kind, value)))
+  }
+}
+
+/// Last path segment of `url`, without query string.
+fn file_name_from_url(url: &str) -> Option<String> {
+  let path = url.split(['?', '#']).next()?;
+  path.rsplit('/').next().filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0)
+}
+
+fn emit_progress(app: &tauri::AppHandle, name: &str, downloaded: u64, total: Option<u64>, done: bool) {
+  let _ = app.emit(
+    MODEL_DOWNLOAD_PROGRESS_EVENT,
+    DownloadProgress {
+      name: name.to_string(),
+      downloaded,
+      total,
+      done,
+    },
+  );
+}
+
+/// Stream `url` into `part`, returning (bytes, lowercase hex sha256).
+async fn fetch(
+  app: &tauri::AppHandle,
+  name: &str,
+  url: &str,
+  part: &Path,
+  cancel: &AtomicBool,
+) -> Result<(u64, String), AppError> {
+  let mut resp = crate::http_client().get(url).send().await?.error_for_status()?;
+  let total = resp.content_length();
+  let mut out = std::fs::File::create(part).map_err(|e| AppError::Io(format!("{}: {}", part.display(), e)))?;
+  let mut hasher = Sha256::new();
+  let mut downloaded = 0u64;
+  let mut last = Instant::now();
+  loop {
+    if cancel.load(Ordering::SeqCst) {
+      return Err(AppError::Cancelled);
+    }
+    let chunk = tokio::time::timeout(Duration::from_secs(STALL_TIMEOUT_SECS), resp.chunk())
+      .await
+      .map_err(|_| AppError::Network(format!("no data for {}s", STALL_TIMEOUT_SECS)))??;
+    let Some(chunk) = chunk else {
+      break;
+    };
+    out.write_all(&chunk)?;
+    hasher.update(&chunk);
+    downloaded += chunk.len() as u64;
+    if last.elapsed() >= Duration::from_millis(PROGRESS_INTERVAL_MS) {
+      last = Instant::now();
+      emit_progress(app, name, downloaded, total, false);
+    }
+  }
+  out.sync_all()?;
+  if let Some(expected) = total {
+    if downloaded != expected {
+      return Err(AppError::Network(format!("truncated download: {} of {} bytes", downloaded, expected)));
+    }
+  }
+  let digest: String = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+  Ok((downloaded, digest))
+}
+
+/// Installed models: manifest entries plus any other files in the models dir.
+#[tauri::command]
+pub fn list_models(models: tauri::State<ModelsState>) -> Result<Vec<ModelInfo>, AppError> {
+  let downloading: Vec<String> = models
+    .downloads
+    .lock()
+    .map_err(|e| e.to_string())?
+    .keys()
+    .cloned()
+    .collect();
+  let dir = models_dir();
+  let manifest = read_manifest();
+  let mut out: Vec<ModelInfo> = manifest
+    .models
+    .iter()
+    .map(|m| {
+      let on_disk = std::fs::metadata(dir.join(&m.file)).ok();
+      ModelInfo {
+        name: m.name.clone(),
+        file: m.file.clone(),
+        size: on_disk.as_ref().map(|md| md.len()).unwrap_or(m.size),
+        sha256: Some(m.sha256.clone()),
+        missing: on_disk.is_none(),
+        downloading: downloading.contains(&m.name),
+      }
+    })
+    .collect();
+  if let Ok(entries) = std::fs::read_dir(&dir) {
+    for entry in entries.filter_map(|e| e.ok()) {
+      let file = entry.file_name().to_string_lossy().to_string();
+      let tracked = manifest.models.iter().any(|m| m.file == file);
+      if tracked || file == MANIFEST_FILE_NAME || file.ends_with(PARTIAL_SUFFIX) || file.ends_with(".tmp") {
+        continue;
+      }
+      let Ok(meta) = entry.metadata() else {
+        continue;
+      };
+      if meta.is_file() {
+        out.push(ModelInfo {
+          name: file.clone(),
+          file,
+          size: meta.len(),
+          sha256: None,
+          missing: false,
+          downloading: false,
+        });
+      }
+    }
+  }
+  out.sort_by(|a, b| a.name.cmp(&b.name));
+  Ok(out)
+}
+
+/// Download `url` as model `name`, verify it against `sha256` (hex) and register it in the manifest.
+/// `file_name` defaults to the last URL segment. Replaces an existing model of the same name.
+#[tauri::command]
+pub async fn download_model(
+  app: tauri::AppHandle,
+  name: String,
+  url: String,
+  sha256: String,
+  file_name: Option<String>,
+) -> Result<ManifestEntry, AppError> {
+  crate::command_stats::track(&app, "download_model", async {
+    validate_name("name", &name)?;
+    let file = file_name
+      .or_else(|| file_name_from_url(&url))
+      .ok_or_else(|| AppError::InvalidInput("file_name is required for this URL".to_string()))?;
+    validate_name("file name", &file)?;
+    if file == MANIFEST_FILE_NAME {
+      return Err(AppError::InvalidInput(format!("reserved file name: {}", file)));
+    }
+    let expected = sha256.trim().to_ascii_lowercase();
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+      return Err(AppError::InvalidInput("sha256 must be 64 hex characters".to_string()));
+    }
+
+    let state = app.state::<ModelsState>();
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+      let mut downloads = state.downloads.lock().map_err(|e| e.to_string())?;
+      if downloads.contains_key(&name) {
+        return Err(AppError::LockHeld(format!("{} is already downloading", name)));
+      }
+      if let Some((other, _)) = downloads.iter().find(|(_, d)| d.file == file) {
+        return Err(AppError::LockHeld(format!("{} is already being downloaded as {}", file, other)));
+      }
+      // Downloading would overwrite another model's weights and leave its manifest entry wrong.
+      if let Some(owner) = read_manifest().models.into_iter().find(|m| m.file == file && m.name != name) {
+        return Err(AppError::InvalidInput(format!("{} already belongs to model {}", file, owner.name)));
+      }
+      downloads.insert(
+        name.clone(),
+        Download {
+          file: file.clone(),
+          cancel: cancel.clone(),
+        },
+      );
+    }
+
+    let dir = models_dir();
+    let part = dir.join(format!("{}{}", file, PARTIAL_SUFFIX));
+    let result = async {
+      std::fs::create_dir_all(&dir)?;
+      crate::app_log(&format!("models: downloading {} from {}", name, url));
+      let (size, actual) = fetch(&app, &name, &url, &part, &cancel).await?;
+      if actual != expected {
+        return Err(AppError::Network(format!(
+          "checksum mismatch for {}: expected {}, got {}",
+          name, expected, actual
+        )));
+      }
+      std::fs::rename(&part, dir.join(&file))?;
+      let entry = ManifestEntry {
+        name: name.clone(),
+        file: file.clone(),
+        size,
+        sha256: actual,
+        source: Some(url.clone()),
+        installed_at: now_secs(),
+      };
+      let _guard = state.manifest.lock().map_err(|e| e.to_string())?;
+      let mut manifest = read_manifest();
+      manifest.models.retain(|m| m.name != name);
+      manifest.models.push(entry.clone());
+      write_manifest(&manifest)?;
+      Ok(entry)
+    }
+    .await;
+
+    if let Ok(mut downloads) = state.downloads.lock() {
+      downloads.remove(&name);
+    }
+    match result {
+      Ok(entry) => {
+        emit_progress(&app, &name, entry.size, Some(entry.size), true);
+        crate::app_log(&format!("models: installed {} ({} bytes)", name, entry.size));
+        Ok(entry)
+      }
+      Err(e) => {
+        let _ = std::fs::remove_file(&part);
+        crate::app_log(&format!("models: download of {} failed: {}", name, e));
+        Err(e)
+      }
+    }
+  })
+  .await
+}
+
+/// Ask a running download to stop; it fails with "cancelled" and its partial file is removed.
+#[tauri::command]
+pub fn cancel_model_download(models: tauri::State<ModelsState>, name: String) -> Result<bool, AppError> {
+  let downloads = models.downloads.lock().map_err(|e| e.to_string())?;
+  Ok(match downloads.get(&name) {
+    Some(download) => {
+      download.cancel.store(true, Ordering::SeqCst);
+      true
+    }
+    None => false,
+  })
+}
+
+/// Delete a model's file and manifest entry. `name` may also be an untracked file in the models dir.
+#[tauri::command]
+pub fn delete_model(models: tauri::State<ModelsState>, name: String) -> Result<(), AppError> {
+  validate_name("name", &name)?;
+  let downloading = {
+    let downloads = models.downloads.lock().map_err(|e| e.to_string())?;
+    downloads.contains_key(&name) || downloads.values().any(|d| d.file == name)
+  };
+  if downloading {
+    return Err(AppError::LockHeld(format!("{} is downloading; cancel it first", name)));
+  }
+  let _guard = models.manifest.lock().map_err(|e| e.to_string())?;
+  let mut manifest = read_manifest();
+  let file = match manifest.models.iter().position(|m| m.name == name) {
+    Some(i) => manifest.models.remove(i).file,
+    None if name != MANIFEST_FILE_NAME => name.clone(),
+    None => return Err(AppError::InvalidInput(format!("reserved file name: {}", name))),
+  };
+  let path = models_dir().join(&file);
+  match std::fs::remove_file(&path) {
+    Ok(()) => {}
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+    Err(e) => return Err(AppError::Io(format!("{}: {}", path.display(), e))),
+  }
+  write_manifest(&manifest)?;
+  crate::app_log(&format!("models: deleted {}", name));
+  Ok(())
+}
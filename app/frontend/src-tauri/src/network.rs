@@ -0,0 +1,136 @@
+// Internet reachability for the cloud features. A background loop fetches a URL that answers
+// "204 No Content" (settings.network_check_url; None = DEFAULT_CHECK_URL, "" = disabled) without
+// following redirects: the expected empty answer means online, any other answer (a redirect or a
+// login page) a captive portal, and no answer offline. Changes go out as `network-state-changed`.
+
+use serde::Serialize;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+use crate::error::AppError;
+use crate::settings::SettingsState;
+
+pub const NETWORK_STATE_EVENT: &str = "network-state-changed";
+const DEFAULT_CHECK_URL: &str = "http://connectivitycheck.gstatic.com/generate_204";
+const CHECK_INTERVAL_SECS: u64 = 30;
+/// Used while offline or behind a portal, so recovery shows up quickly.
+const RECHECK_INTERVAL_SECS: u64 = 5;
+const CHECK_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Connectivity {
+  /// Not checked yet, or checking is disabled.
+  Unknown,
+  Online,
+  Offline,
+  CaptivePortal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkState {
+  pub state: Connectivity,
+  /// When `state` was entered (ms since the epoch).
+  pub since_ms: Option<u64>,
+  pub checked_at_ms: Option<u64>,
+  /// Error or unexpected answer behind an offline / captive portal result.
+  pub detail: Option<String>,
+}
+
+static STATE: Mutex<NetworkState> = Mutex::new(NetworkState {
+  state: Connectivity::Unknown,
+  since_ms: None,
+  checked_at_ms: None,
+  detail: None,
+});
+
+/// No redirects: a portal's redirect to its login page is the signal.
+fn client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(|| {
+    reqwest::Client::builder()
+      .redirect(reqwest::redirect::Policy::none())
+      .timeout(Duration::from_secs(CHECK_TIMEOUT_SECS))
+      .build()
+      .unwrap_or_default()
+  })
+}
+
+fn check_url(app: &tauri::AppHandle) -> Option<String> {
+  match app.state::<SettingsState>().get().network_check_url {
+    None => Some(DEFAULT_CHECK_URL.to_string()),
+    Some(url) if url.trim().is_empty() => None,
+    Some(url) => Some(url.trim().to_string()),
+  }
+}
+
+async fn probe(url: &str) -> (Connectivity, Option<String>) {
+  match client().get(url).send().await {
+    Ok(res) if res.status() == reqwest::StatusCode::NO_CONTENT => (Connectivity::Online, None),
+    Ok(res) if res.status().is_success() && res.content_length() == Some(0) => (Connectivity::Online, None),
+    Ok(res) => (Connectivity::CaptivePortal, Some(format!("{} answered HTTP {}", url, res.status()))),
+    Err(e) => (Connectivity::Offline, Some(e.to_string())),
+  }
+}
+
+pub fn current() -> NetworkState {
+  STATE.lock().map(|g| g.clone()).unwrap_or_else(|e| e.into_inner().clone())
+}
+
+/// Check now, record the result, and emit `network-state-changed` when the state changed.
+async fn check(app: &tauri::AppHandle) -> NetworkState {
+  let (state, detail) = match check_url(app) {
+    Some(url) => probe(&url).await,
+    None => (Connectivity::Unknown, Some("network check disabled".to_string())),
+  };
+  let now = crate::status_history::now_ms();
+  let (snapshot, changed) = {
+    let mut g = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let changed = g.state != state;
+    if changed {
+      g.since_ms = Some(now);
+    }
+    g.state = state;
+    g.checked_at_ms = Some(now);
+    g.detail = detail;
+    (g.clone(), changed)
+  };
+  if changed {
+    crate::app_log(&format!(
+      "network: {:?}{}",
+      snapshot.state,
+      snapshot.detail.as_deref().map(|d| format!(" ({})", d)).unwrap_or_default()
+    ));
+    let _ = app.emit(NETWORK_STATE_EVENT, &snapshot);
+  }
+  snapshot
+}
+
+/// Check for the rest of the session: every CHECK_INTERVAL_SECS, RECHECK_INTERVAL_SECS while not online.
+pub fn start(app: &tauri::AppHandle) {
+  let app = app.clone();
+  tauri::async_runtime::spawn(async move {
+    loop {
+      let state = check(&app).await.state;
+      let secs = match state {
+        Connectivity::Offline | Connectivity::CaptivePortal => RECHECK_INTERVAL_SECS,
+        Connectivity::Online | Connectivity::Unknown => CHECK_INTERVAL_SECS,
+      };
+      tokio::time::sleep(Duration::from_secs(secs)).await;
+    }
+  });
+}
+
+/// Last known state; `refresh` checks again first.
+#[tauri::command]
+pub async fn get_network_state(app: tauri::AppHandle, refresh: Option<bool>) -> Result<NetworkState, AppError> {
+  crate::command_stats::track(&app, "get_network_state", async {
+    if refresh.unwrap_or(false) {
+      Ok(check(&app).await)
+    } else {
+      Ok(current())
+    }
+  })
+  .await
+}
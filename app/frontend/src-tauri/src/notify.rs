@@ -0,0 +1,65 @@
+// Native notifications for finished mentor jobs. Clicking one summons the main window (or the chat
+// window of `action.session_id`, when open) and emits `notification-action` there with the caller's
+// action payload so the frontend can route (e.g. open the review). Each notification gets a thread
+// that waits for the click or dismissal.
+
+use serde::Serialize;
+
+use crate::error::AppError;
+pub const NOTIFICATION_ACTION_EVENT: &str = "notification-action";
+/// Action id reported when the notification body is clicked.
+const DEFAULT_ACTION: &str = "default";
+const CLOSED_ACTION: &str = "__closed";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationAction {
+  pub title: String,
+  pub action: Option<serde_json::Value>,
+}
+
+/// Show a notification; a click focuses the main window and emits NOTIFICATION_ACTION_EVENT.
+pub(crate) fn show(app: &tauri::AppHandle, title: &str, body: &str, action: Option<serde_json::Value>) -> Result<(), String> {
+  let mut n = notify_rust::Notification::new();
+  n.summary(title).body(body).appname("AI Mentor");
+  #[cfg(windows)]
+  n.app_id(&app.config().identifier);
+  // XDG servers only report body clicks for notifications that declare a default action.
+  #[cfg(all(unix, not(target_os = "macos")))]
+  n.action(DEFAULT_ACTION, "Open");
+  let handle = n.show().map_err(|e| e.to_string())?;
+  crate::app_log(&format!("notify: shown {:?}", title));
+
+  let app = app.clone();
+  let title = title.to_string();
+  std::thread::spawn(move || {
+    handle.wait_for_action(|clicked| {
+      if clicked == CLOSED_ACTION {
+        return;
+      }
+      if clicked != DEFAULT_ACTION {
+        crate::app_log(&format!("notify: unexpected action {:?}", clicked));
+      }
+      let session = action
+        .as_ref()
+        .and_then(|a| a.get("session_id"))
+        .and_then(|s| s.as_str())
+        .map(str::to_string)
+        .unwrap_or_default();
+      match crate::chat_windows::window_for_session(&app, &session) {
+        Some(window) => {
+          let _ = window.unminimize();
+          let _ = window.set_focus();
+        }
+        None => crate::hotkey::summon(&app),
+      }
+      crate::chat_windows::emit_to_session(&app, &session, NOTIFICATION_ACTION_EVENT, NotificationAction { title, action });
+    });
+  });
+  Ok(())
+}
+
+/// Native notification; `action` is echoed back in `notification-action` when it is clicked.
+#[tauri::command]
+pub fn notify(app: tauri::AppHandle, title: String, body: String, action: Option<serde_json::Value>) -> Result<(), AppError> {
+  Ok(show(&app, &title, &body, action)?)
+}
@@ -0,0 +1,154 @@
+// Resolved data locations. Everything the shell writes lives under AppPaths::base, chosen once per
+// session in this order:
+//   1. --data-dir
+//   2. portable mode: a `portable` marker file next to the exe -> <exe dir>/data
+//   3. settings.data_dir, read from settings.json in the default location
+//   4. %LOCALAPPDATA%\AI_Mentor
+// A changed settings.data_dir applies from the next launch; existing data is not moved.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::error::AppError;
+use crate::settings::SettingsState;
+
+pub const PORTABLE_MARKER_NAME: &str = "portable";
+const PORTABLE_DATA_DIR_NAME: &str = "data";
+const DEFAULT_DIR_NAME: &str = "AI_Mentor";
+const SETTINGS_FILE_NAME: &str = "settings.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataDirSource {
+  Cli,
+  Portable,
+  Settings,
+  Default,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppPaths {
+  pub source: DataDirSource,
+  pub base: PathBuf,
+  pub logs: PathBuf,
+  /// Per-session files (single-instance lock).
+  pub runtime: PathBuf,
+}
+
+impl AppPaths {
+  fn at(base: PathBuf, source: DataDirSource) -> Self {
+    Self {
+      source,
+      logs: base.join("logs"),
+      runtime: base.join("runtime"),
+      base,
+    }
+  }
+
+  pub fn resolve() -> Self {
+    if let Some(ref dir) = crate::cli::args().data_dir {
+      return Self::at(dir.clone(), DataDirSource::Cli);
+    }
+    if let Some(dir) = portable_dir() {
+      return Self::at(dir, DataDirSource::Portable);
+    }
+    if let Some(dir) = settings_override() {
+      return Self::at(dir, DataDirSource::Settings);
+    }
+    Self::at(default_base_dir(), DataDirSource::Default)
+  }
+}
+
+pub fn local_app_data() -> PathBuf {
+  std::env::var_os("LOCALAPPDATA")
+    .map(PathBuf::from)
+    .unwrap_or_else(|| std::env::var_os("USERPROFILE").map(PathBuf::from).unwrap_or_default())
+}
+
+pub fn default_base_dir() -> PathBuf {
+  local_app_data().join(DEFAULT_DIR_NAME)
+}
+
+/// <exe dir>/data when the portable marker sits next to the executable.
+fn portable_dir() -> Option<PathBuf> {
+  let exe = std::env::current_exe().ok()?;
+  let dir = exe.parent()?;
+  dir.join(PORTABLE_MARKER_NAME).is_file().then(|| dir.join(PORTABLE_DATA_DIR_NAME))
+}
+
+fn default_settings_path() -> PathBuf {
+  default_base_dir().join(SETTINGS_FILE_NAME)
+}
+
+/// `data_dir` from the default-location settings.json. Read raw: the settings store itself lives
+/// under the directory being resolved.
+fn settings_override() -> Option<PathBuf> {
+  let text = std::fs::read_to_string(default_settings_path()).ok()?;
+  let value: serde_json::Value = serde_json::from_str(&text).ok()?;
+  let dir = value.get("data_dir")?.as_str()?.trim();
+  (!dir.is_empty()).then(|| PathBuf::from(dir))
+}
+
+/// Write `data_dir` into the default-location settings.json, keeping every other field.
+fn write_settings_override(dir: Option<&Path>) -> Result<(), String> {
+  let path = default_settings_path();
+  let mut value: serde_json::Value = std::fs::read_to_string(&path)
+    .ok()
+    .and_then(|t| serde_json::from_str(&t).ok())
+    .unwrap_or_else(|| serde_json::json!({}));
+  let obj = value.as_object_mut().ok_or("settings.json is not an object")?;
+  match dir {
+    Some(d) => obj.insert("data_dir".to_string(), serde_json::json!(d)),
+    None => obj.remove("data_dir"),
+  };
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+  }
+  let text = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+  std::fs::write(&path, text).map_err(|e| e.to_string())
+}
+
+static PATHS: OnceLock<AppPaths> = OnceLock::new();
+
+/// Paths for this session, resolved on first use (after CLI parsing).
+pub fn get() -> &'static AppPaths {
+  PATHS.get_or_init(AppPaths::resolve)
+}
+
+#[tauri::command]
+pub fn get_app_paths() -> AppPaths {
+  get().clone()
+}
+
+/// Move data to `dir` (absolute) from the next launch; None returns to the default location.
+/// Not available when --data-dir or portable mode decides the location.
+#[tauri::command]
+pub fn set_data_dir(settings: tauri::State<SettingsState>, dir: Option<PathBuf>) -> Result<(), AppError> {
+  match get().source {
+    DataDirSource::Cli => {
+      return Err(AppError::Unsupported("data directory is set by --data-dir for this session".to_string()))
+    }
+    DataDirSource::Portable => {
+      return Err(AppError::Unsupported("data directory is fixed in portable mode".to_string()))
+    }
+    DataDirSource::Settings | DataDirSource::Default => {}
+  }
+  if let Some(ref d) = dir {
+    if !d.is_absolute() {
+      return Err(AppError::InvalidInput(format!("data directory must be absolute: {}", d.display())));
+    }
+    std::fs::create_dir_all(d).map_err(|e| AppError::Io(format!("{}: {}", d.display(), e)))?;
+  }
+  // In the default location the live settings are this same file; keep the in-memory copy in step
+  // so a later save does not drop the field.
+  if get().source == DataDirSource::Default {
+    settings.update(|s| s.data_dir = dir.clone())?;
+  }
+  write_settings_override(dir.as_deref())?;
+  crate::app_log(&format!(
+    "paths: data dir from next launch: {}",
+    dir.as_deref().unwrap_or(&default_base_dir()).display()
+  ));
+  Ok(())
+}
@@ -0,0 +1,214 @@
+// Which process listens on a TCP port: GetExtendedTcpTable on Windows, /proc/net/tcp{,6} plus the
+// /proc/<pid>/fd socket links on Linux, lsof on macOS. Name and path come from sysinfo. The owner of
+// the backend port is attached to NOT_READY:PORT_IN_USE_NO_HEALTH, and kill_port_owner can end it
+// when the user explicitly confirms.
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+
+use crate::BackendState;
+use crate::error::AppError;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PortOwner {
+  pub port: u16,
+  /// None when the OS does not reveal it (e.g. another user's process on Linux).
+  pub pid: Option<u32>,
+  pub name: Option<String>,
+  pub exe: Option<PathBuf>,
+}
+
+#[cfg(windows)]
+fn listening_pids(port: u16) -> Vec<u32> {
+  use windows_sys::Win32::NetworkManagement::IpHelper::{
+    GetExtendedTcpTable, MIB_TCPROW_OWNER_PID, MIB_TCPTABLE_OWNER_PID, TCP_TABLE_OWNER_PID_LISTENER,
+  };
+  use windows_sys::Win32::Networking::WinSock::AF_INET;
+
+  let mut size: u32 = 0;
+  // SAFETY: a null table with size 0 only asks for the required buffer size.
+  unsafe {
+    GetExtendedTcpTable(std::ptr::null_mut(), &mut size, 0, AF_INET as u32, TCP_TABLE_OWNER_PID_LISTENER, 0);
+  }
+  if size == 0 {
+    return Vec::new();
+  }
+  // u32 elements keep the table 4-byte aligned.
+  let mut buf = vec![0u32; (size as usize).div_ceil(4)];
+  // SAFETY: buf holds at least `size` bytes.
+  let ret = unsafe {
+    GetExtendedTcpTable(buf.as_mut_ptr().cast(), &mut size, 0, AF_INET as u32, TCP_TABLE_OWNER_PID_LISTENER, 0)
+  };
+  if ret != 0 {
+    return Vec::new();
+  }
+  // SAFETY: on success the buffer holds a MIB_TCPTABLE_OWNER_PID with dwNumEntries rows.
+  let rows: &[MIB_TCPROW_OWNER_PID] = unsafe {
+    let table = &*(buf.as_ptr() as *const MIB_TCPTABLE_OWNER_PID);
+    std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize)
+  };
+  rows
+    .iter()
+    // The port is in network byte order in the low 16 bits.
+    .filter(|r| u16::from_be(r.dwLocalPort as u16) == port)
+    .map(|r| r.dwOwningPid)
+    .collect()
+}
+
+/// Inodes of LISTEN sockets on `port` from /proc/net/tcp and tcp6.
+#[cfg(target_os = "linux")]
+fn listening_inodes(port: u16) -> Vec<String> {
+  const TCP_LISTEN: &str = "0A";
+  let mut inodes = Vec::new();
+  for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+    let Ok(text) = std::fs::read_to_string(table) else {
+      continue;
+    };
+    for line in text.lines().skip(1) {
+      let cols: Vec<&str> = line.split_whitespace().collect();
+      if cols.len() < 10 || cols[3] != TCP_LISTEN {
+        continue;
+      }
+      let local_port = cols[1].rsplit(':').next().and_then(|p| u16::from_str_radix(p, 16).ok());
+      if local_port == Some(port) {
+        inodes.push(cols[9].to_string());
+      }
+    }
+  }
+  inodes
+}
+
+#[cfg(target_os = "linux")]
+fn listening_pids(port: u16) -> Vec<u32> {
+  let links: Vec<String> = listening_inodes(port).iter().map(|i| format!("socket:[{}]", i)).collect();
+  if links.is_empty() {
+    return Vec::new();
+  }
+  let Ok(procs) = std::fs::read_dir("/proc") else {
+    return Vec::new();
+  };
+  let mut pids = Vec::new();
+  for entry in procs.filter_map(|e| e.ok()) {
+    let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else {
+      continue;
+    };
+    // Other users' fds are unreadable; those owners stay unknown.
+    let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+      continue;
+    };
+    let owns = fds
+      .filter_map(|fd| fd.ok())
+      .filter_map(|fd| std::fs::read_link(fd.path()).ok())
+      .any(|target| links.iter().any(|l| target.as_os_str() == l.as_str()));
+    if owns {
+      pids.push(pid);
+    }
+  }
+  pids
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn listening_pids(port: u16) -> Vec<u32> {
+  let out = std::process::Command::new("lsof")
+    .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-t"])
+    .output();
+  match out {
+    Ok(o) => String::from_utf8_lossy(&o.stdout)
+      .lines()
+      .filter_map(|l| l.trim().parse().ok())
+      .collect(),
+    Err(_) => Vec::new(),
+  }
+}
+
+/// The process listening on `port`, if anything is. The lowest PID wins when several share it.
+pub fn find(port: u16) -> Option<PortOwner> {
+  let mut pids = listening_pids(port);
+  pids.sort_unstable();
+  pids.dedup();
+  let Some(&pid) = pids.first() else {
+    // Port taken but the owner is hidden from us.
+    let taken = std::net::TcpListener::bind((crate::BACKEND_HOST, port)).is_err();
+    return taken.then_some(PortOwner {
+      port,
+      pid: None,
+      name: None,
+      exe: None,
+    });
+  };
+  let mut sys = System::new();
+  sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), true);
+  let process = sys.process(Pid::from_u32(pid));
+  Some(PortOwner {
+    port,
+    pid: Some(pid),
+    name: process.map(|p| p.name().to_string_lossy().to_string()),
+    exe: process.and_then(|p| p.exe().map(|e| e.to_path_buf())),
+  })
+}
+
+/// find() off the async runtime (lsof and the /proc scan can take a moment).
+pub(crate) async fn find_async(port: u16) -> Option<PortOwner> {
+  tauri::async_runtime::spawn_blocking(move || find(port)).await.ok().flatten()
+}
+
+impl std::fmt::Display for PortOwner {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.pid {
+      Some(pid) => write!(f, "{} (PID {})", self.name.as_deref().unwrap_or("unknown"), pid),
+      None => write!(f, "an unknown process"),
+    }
+  }
+}
+
+/// Owner of the backend port right now.
+#[tauri::command]
+pub fn get_port_owner() -> Option<PortOwner> {
+  find(crate::backend_port())
+}
+
+/// Kill the process holding the backend port. Requires `confirm: true` and the `pid` the user was
+/// shown, so a port that changed hands in between is not touched. Resolves with the killed owner.
+#[tauri::command]
+pub fn kill_port_owner(
+  state: tauri::State<Arc<BackendState>>,
+  pid: u32,
+  confirm: bool,
+) -> Result<PortOwner, AppError> {
+  if !confirm {
+    return Err(AppError::ConfirmationRequired("confirmation required".to_string()));
+  }
+  let owner = find(crate::backend_port())
+    .ok_or_else(|| AppError::ResourceNotFound("nothing is listening on the backend port".to_string()))?;
+  if owner.pid != Some(pid) {
+    return Err(AppError::PortInUse(format!(
+      "port {} is now held by {:?}, not PID {}",
+      owner.port, owner.pid, pid
+    )));
+  }
+  if pid == std::process::id() {
+    return Err(AppError::InvalidInput("refusing to kill AI Mentor itself".to_string()));
+  }
+  let mut sys = System::new();
+  sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), true);
+  let process = sys
+    .process(Pid::from_u32(pid))
+    .ok_or_else(|| AppError::ResourceNotFound(format!("PID {} already exited", pid)))?;
+  if !process.kill() {
+    return Err(AppError::Internal(format!("could not kill PID {} (insufficient rights?)", pid)));
+  }
+  crate::app_log(&format!(
+    "port owner: killed PID {} ({}) on port {}",
+    pid,
+    owner.name.as_deref().unwrap_or("?"),
+    owner.port
+  ));
+  if let Ok(mut g) = state.inner.lock() {
+    if g.adopted_pid == Some(pid) {
+      g.adopted_pid = None;
+    }
+  }
+  Ok(owner)
+}
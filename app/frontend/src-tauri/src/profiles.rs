@@ -0,0 +1,335 @@
+// Backend connection profiles. The built-in `local` profile is the bundled sidecar on 127.0.0.1
+// (--port or 8000); settings.backend_profiles adds named targets (dev, staging, remote, ...). The
+// active profile decides api_base() and whether autostart manages a local process: a profile with
+// autostart off is only health-checked. Autostart needs a plain-http loopback URL, whose port the
+// sidecar is then started on.
+//
+// Remote mode is a profile without autostart on an https URL (e.g. a team's shared server): no
+// process or port on this machine is touched, requests go through client_for() with certificate
+// verification, and the profile can name keyring secrets holding a bearer token and a PEM client
+// certificate. The readiness watch keeps checking the endpoint and marks it NOT_READY:UNREACHABLE
+// while it does not answer.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tauri::Emitter;
+
+use crate::BackendState;
+use crate::error::AppError;
+use crate::settings::{Settings, SettingsState};
+
+pub const LOCAL_PROFILE_NAME: &str = "local";
+pub const BACKEND_PROFILE_EVENT: &str = "backend-profile";
+pub const NOT_READY_REASON_UNREACHABLE: &str = "UNREACHABLE";
+pub const NOT_READY_REASON_CREDENTIALS: &str = "CREDENTIALS_UNAVAILABLE";
+const LOOPBACK_HOSTS: &[&str] = &["127.0.0.1", "localhost", "[::1]"];
+
+/// A profile as stored in settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendProfile {
+  pub name: String,
+  pub base_url: String,
+  /// Spawn and supervise the sidecar for this target (loopback http URLs only).
+  #[serde(default)]
+  pub autostart: bool,
+  /// Keyring secret holding a token sent as `Authorization: Bearer <token>`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub token_secret: Option<String>,
+  /// Keyring secret holding a PEM client certificate and its private key.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub client_cert_secret: Option<String>,
+}
+
+/// A validated profile the app can connect to.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedProfile {
+  pub name: String,
+  /// Without trailing slash.
+  pub base_url: String,
+  pub autostart: bool,
+  /// Port on this machine the backend listens on; None for a remote target.
+  pub local_port: Option<u16>,
+  pub token_secret: Option<String>,
+  pub client_cert_secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileInfo {
+  #[serde(flatten)]
+  pub profile: ResolvedProfile,
+  pub active: bool,
+  pub builtin: bool,
+}
+
+static ACTIVE: RwLock<Option<ResolvedProfile>> = RwLock::new(None);
+/// Client for the active profile, or why its credentials could not be loaded.
+static CLIENT: RwLock<Option<Result<reqwest::Client, String>>> = RwLock::new(None);
+
+fn local_profile() -> ResolvedProfile {
+  let port = crate::cli::args().port.unwrap_or(crate::DEFAULT_BACKEND_PORT);
+  ResolvedProfile {
+    name: LOCAL_PROFILE_NAME.to_string(),
+    base_url: format!("http://{}:{}", crate::BACKEND_HOST, port),
+    autostart: true,
+    local_port: Some(port),
+    token_secret: None,
+    client_cert_secret: None,
+  }
+}
+
+fn secret_name(name: &Option<String>) -> Option<String> {
+  name.as_deref().map(str::trim).filter(|n| !n.is_empty()).map(str::to_string)
+}
+
+/// Validate a stored profile: http(s) URL with a host; autostart only for loopback http; credentials
+/// only over https or loopback.
+pub fn resolve(profile: &BackendProfile) -> Result<ResolvedProfile, AppError> {
+  let name = profile.name.trim();
+  if name.is_empty() {
+    return Err(AppError::InvalidInput("profile name is empty".to_string()));
+  }
+  let url = reqwest::Url::parse(profile.base_url.trim())
+    .map_err(|e| AppError::InvalidInput(format!("{}: {}", profile.base_url, e)))?;
+  if !matches!(url.scheme(), "http" | "https") {
+    return Err(AppError::InvalidInput(format!("{}: only http and https are supported", profile.base_url)));
+  }
+  let host = url.host_str().ok_or_else(|| AppError::InvalidInput(format!("{}: missing host", profile.base_url)))?;
+  let loopback = LOOPBACK_HOSTS.contains(&host);
+  let local_port = loopback.then(|| url.port_or_known_default()).flatten();
+  if profile.autostart && (url.scheme() != "http" || local_port.is_none()) {
+    return Err(AppError::InvalidInput(format!(
+      "{}: autostart needs an http URL on 127.0.0.1/localhost",
+      profile.base_url
+    )));
+  }
+  let token_secret = secret_name(&profile.token_secret);
+  let client_cert_secret = secret_name(&profile.client_cert_secret);
+  let credentials = token_secret.is_some() || client_cert_secret.is_some();
+  if credentials && url.scheme() != "https" && !loopback {
+    return Err(AppError::InvalidInput(format!(
+      "{}: a token or client certificate is only sent over https",
+      profile.base_url
+    )));
+  }
+  Ok(ResolvedProfile {
+    name: name.to_string(),
+    base_url: url.as_str().trim_end_matches('/').to_string(),
+    autostart: profile.autostart,
+    local_port,
+    token_secret,
+    client_cert_secret,
+  })
+}
+
+fn find(settings: &Settings, name: &str) -> Result<ResolvedProfile, AppError> {
+  if name == LOCAL_PROFILE_NAME {
+    return Ok(local_profile());
+  }
+  let profile = settings
+    .backend_profiles
+    .iter()
+    .find(|p| p.name == name)
+    .ok_or_else(|| AppError::ResourceNotFound(format!("unknown profile: {}", name)))?;
+  resolve(profile)
+}
+
+/// The profile in use this session (local until init runs).
+pub fn active() -> ResolvedProfile {
+  ACTIVE
+    .read()
+    .ok()
+    .and_then(|g| g.clone())
+    .unwrap_or_else(local_profile)
+}
+
+fn set_active(profile: ResolvedProfile) {
+  refresh_client(&profile);
+  if let Ok(mut g) = ACTIVE.write() {
+    *g = Some(profile);
+  }
+}
+
+fn secret(name: &str) -> Result<String, AppError> {
+  crate::secrets::get(name)
+    .map_err(AppError::Internal)?
+    .ok_or_else(|| AppError::ResourceNotFound(format!("secret {} is not set", name)))
+}
+
+fn build_client(profile: &ResolvedProfile) -> Result<reqwest::Client, AppError> {
+  let mut builder = reqwest::Client::builder();
+  if let Some(name) = &profile.client_cert_secret {
+    let identity = reqwest::Identity::from_pem(secret(name)?.as_bytes())
+      .map_err(|e| AppError::InvalidInput(format!("client certificate {}: {}", name, e)))?;
+    builder = builder.identity(identity);
+  }
+  if let Some(name) = &profile.token_secret {
+    let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", secret(name)?.trim()))
+      .map_err(|_| AppError::InvalidInput(format!("token {} is not a valid header value", name)))?;
+    value.set_sensitive(true);
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(reqwest::header::AUTHORIZATION, value);
+    builder = builder.default_headers(headers);
+  }
+  Ok(builder.build()?)
+}
+
+/// Re-read the profile's credentials from the keyring (they may have been set since).
+fn refresh_client(profile: &ResolvedProfile) {
+  let built = build_client(profile).map_err(|e| {
+    crate::app_log(&format!("profiles: {} credentials: {}", profile.name, e));
+    e.to_string()
+  });
+  if let Ok(mut g) = CLIENT.write() {
+    *g = Some(built);
+  }
+}
+
+/// Client for `url`: with the active profile's bearer token and client certificate when `url` is
+/// on its backend, the plain shared client otherwise, so the token never reaches other hosts.
+pub fn client_for(url: &str) -> reqwest::Client {
+  let on_backend = url
+    .strip_prefix(active().base_url.as_str())
+    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+  match CLIENT.read().ok().and_then(|g| g.clone()) {
+    Some(Ok(client)) if on_backend => client,
+    _ => crate::http_client().clone(),
+  }
+}
+
+fn credentials_error() -> Option<String> {
+  CLIENT.read().ok().and_then(|g| g.clone()).and_then(Result::err)
+}
+
+/// Unreachable after a probe flow or a failed readiness check: the readiness watch keeps checking.
+pub(crate) fn watch_unreachable(status: &str, reason: Option<&str>) -> bool {
+  status == "NOT_READY" && reason == Some(NOT_READY_REASON_UNREACHABLE)
+}
+
+/// Select settings.active_profile at startup; an unknown or invalid one falls back to local.
+pub fn init(settings: &Settings) {
+  let name = settings.active_profile.as_deref().unwrap_or(LOCAL_PROFILE_NAME);
+  let profile = find(settings, name).unwrap_or_else(|e| {
+    crate::app_log(&format!("profiles: {}, using {}", e, LOCAL_PROFILE_NAME));
+    local_profile()
+  });
+  crate::app_log(&format!("profiles: active {} ({})", profile.name, profile.base_url));
+  set_active(profile);
+}
+
+/// Flow for a profile without autostart: the backend is someone else's, only wait for its health.
+pub async fn run_probe_flow(state: Arc<BackendState>, generation: u64) {
+  let target = active();
+  crate::backend_autostart_log(&format!("profiles: probing {} ({})", target.name, target.base_url));
+  refresh_client(&target);
+  if let Some(e) = credentials_error() {
+    crate::backend_autostart_log(&format!("profiles: {} credentials unavailable: {}", target.name, e));
+    if let Some(mut g) = state.lock_current(generation) {
+      g.set_status("NOT_READY", Some(NOT_READY_REASON_CREDENTIALS.to_string()));
+    }
+    return;
+  }
+  if let Some(mut g) = state.lock_current(generation) {
+    g.set_status("STARTING", None);
+  }
+  let ok = crate::wait_for_health(state.probe.as_ref(), crate::health::config().timeout_ms).await;
+  let readiness = if ok { Some(state.probe.readiness().await) } else { None };
+  let Some(mut g) = state.lock_current(generation) else {
+    return;
+  };
+  if let Some(readiness) = readiness {
+    let (status, reason) = crate::health::status_for(readiness).unwrap_or(("READY", None));
+    g.set_status(status, reason);
+  } else {
+    g.set_status("NOT_READY", Some(NOT_READY_REASON_UNREACHABLE.to_string()));
+  }
+  drop(g);
+  crate::app_log(&format!("profiles: {} {}", target.name, if ok { "READY" } else { "unreachable" }));
+}
+
+#[tauri::command]
+pub fn list_profiles(settings: tauri::State<SettingsState>) -> Vec<ProfileInfo> {
+  let current = active().name;
+  let mut out = vec![ProfileInfo {
+    profile: local_profile(),
+    active: current == LOCAL_PROFILE_NAME,
+    builtin: true,
+  }];
+  for stored in settings.get().backend_profiles {
+    match resolve(&stored) {
+      Ok(profile) => out.push(ProfileInfo {
+        active: profile.name == current,
+        profile,
+        builtin: false,
+      }),
+      Err(e) => crate::app_log(&format!("profiles: skipping {}: {}", stored.name, e)),
+    }
+  }
+  out
+}
+
+/// Add or replace a stored profile. Takes effect for the active profile on the next switch.
+#[tauri::command]
+pub fn save_profile(settings: tauri::State<SettingsState>, profile: BackendProfile) -> Result<ResolvedProfile, AppError> {
+  let resolved = resolve(&profile)?;
+  if resolved.name == LOCAL_PROFILE_NAME {
+    return Err(AppError::InvalidInput(format!("{} is built in", LOCAL_PROFILE_NAME)));
+  }
+  let stored = BackendProfile {
+    name: resolved.name.clone(),
+    base_url: resolved.base_url.clone(),
+    autostart: resolved.autostart,
+    token_secret: resolved.token_secret.clone(),
+    client_cert_secret: resolved.client_cert_secret.clone(),
+  };
+  settings.update(|s| {
+    s.backend_profiles.retain(|p| p.name != stored.name);
+    s.backend_profiles.push(stored);
+  })?;
+  Ok(resolved)
+}
+
+#[tauri::command]
+pub fn delete_profile(settings: tauri::State<SettingsState>, name: String) -> Result<(), AppError> {
+  if name == LOCAL_PROFILE_NAME {
+    return Err(AppError::InvalidInput(format!("{} is built in", LOCAL_PROFILE_NAME)));
+  }
+  if active().name == name {
+    return Err(AppError::InvalidInput(format!("{} is active; switch to another profile first", name)));
+  }
+  settings.update(|s| s.backend_profiles.retain(|p| p.name != name))?;
+  Ok(())
+}
+
+/// Stop the backend this app owns, make `name` the active (and persisted) profile, emit
+/// `backend-profile`, and run autostart or a health check against the new target. Resolves with
+/// the resulting status like retry_backend_start.
+#[tauri::command]
+pub async fn switch_profile(
+  app: tauri::AppHandle,
+  state: tauri::State<'_, Arc<BackendState>>,
+  settings: tauri::State<'_, SettingsState>,
+  name: String,
+) -> Result<String, AppError> {
+  crate::command_stats::track(&app, "switch_profile", async {
+    let profile = find(&settings.get(), &name)?;
+    crate::stop_owned_backend(&state)?;
+    let stored = (name != LOCAL_PROFILE_NAME).then(|| name.clone());
+    settings.update(|s| s.active_profile = stored)?;
+    crate::app_log(&format!(
+      "profiles: switching {} -> {} ({})",
+      active().name,
+      profile.name,
+      profile.base_url
+    ));
+    set_active(profile.clone());
+    let _ = app.emit(BACKEND_PROFILE_EVENT, &profile);
+    let state = state.inner();
+    if profile.autostart {
+      crate::rerun_autostart(&app, state).await
+    } else {
+      let handle = crate::spawn_flow(state, |gen| run_probe_flow(state.clone(), gen));
+      crate::await_flow(state, handle).await
+    }
+  })
+  .await
+}
@@ -0,0 +1,134 @@
+// Secrets (provider API keys etc.) in the OS keyring: Credential Manager / Keychain / Secret Service.
+// Values never touch settings.json; settings only records which secrets are injected into the backend env.
+
+use crate::error::AppError;
+use crate::settings::{Settings, SettingsState};
+
+const KEYRING_SERVICE: &str = "com.ai.mentor.desktop";
+const SECRET_NAME_MAX_LEN: usize = 64;
+
+fn validate_name(name: &str) -> Result<(), AppError> {
+  if name.is_empty() || name.len() > SECRET_NAME_MAX_LEN {
+    return Err(AppError::InvalidInput(format!(
+      "secret name must be 1..={} characters",
+      SECRET_NAME_MAX_LEN
+    )));
+  }
+  if !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')) {
+    return Err(AppError::InvalidInput("secret name may only contain A-Z, a-z, 0-9, '_', '-', '.'".to_string()));
+  }
+  Ok(())
+}
+
+/// Env var names: uppercase letters, digits, '_' (not starting with a digit).
+pub(crate) fn validate_env_var(var: &str) -> Result<(), AppError> {
+  let valid = !var.is_empty()
+    && !var.starts_with(|c: char| c.is_ascii_digit())
+    && var.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+  if !valid {
+    return Err(AppError::InvalidInput(format!("invalid environment variable name: {}", var)));
+  }
+  Ok(())
+}
+
+/// Where secret values live. Command code only talks to this trait so managed builds can swap the keyring out.
+pub trait SecretStore: Send + Sync {
+  /// Ok(None) when the secret has never been set.
+  fn get(&self, name: &str) -> Result<Option<String>, String>;
+  fn set(&self, name: &str, value: &str) -> Result<(), String>;
+  /// Deleting an absent secret is not an error.
+  fn delete(&self, name: &str) -> Result<(), String>;
+}
+
+/// Platform keyring: Credential Manager / Keychain / Secret Service.
+pub struct KeyringStore;
+
+impl KeyringStore {
+  fn entry(name: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, name).map_err(|e| e.to_string())
+  }
+}
+
+impl SecretStore for KeyringStore {
+  fn get(&self, name: &str) -> Result<Option<String>, String> {
+    match Self::entry(name)?.get_password() {
+      Ok(v) => Ok(Some(v)),
+      Err(keyring::Error::NoEntry) => Ok(None),
+      Err(e) => Err(e.to_string()),
+    }
+  }
+
+  fn set(&self, name: &str, value: &str) -> Result<(), String> {
+    Self::entry(name)?.set_password(value).map_err(|e| e.to_string())
+  }
+
+  fn delete(&self, name: &str) -> Result<(), String> {
+    match Self::entry(name)?.delete_credential() {
+      Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+      Err(e) => Err(e.to_string()),
+    }
+  }
+}
+
+pub fn active_store() -> Box<dyn SecretStore> {
+  Box::new(KeyringStore)
+}
+
+pub(crate) fn get(name: &str) -> Result<Option<String>, String> {
+  active_store().get(name)
+}
+
+/// (env var, value) pairs for secrets configured for injection into the spawned backend.
+/// Missing or unreadable secrets are skipped and logged by name only.
+pub(crate) fn backend_env(settings: &Settings) -> Vec<(String, String)> {
+  let mut vars = Vec::new();
+  for (var, name) in &settings.backend_secret_env {
+    match get(name) {
+      Ok(Some(value)) => vars.push((var.clone(), value)),
+      Ok(None) => crate::backend_autostart_log(&format!("secrets: {} not set, {} not injected", name, var)),
+      Err(e) => crate::backend_autostart_log(&format!("secrets: {} unreadable ({}), {} not injected", name, e, var)),
+    }
+  }
+  vars
+}
+
+/// Store a secret in the OS keyring. With `inject_env` (e.g. "OPENAI_API_KEY") the secret is also
+/// passed to the backend process under that variable the next time it is spawned.
+#[tauri::command]
+pub fn secret_set(
+  settings: tauri::State<SettingsState>,
+  name: String,
+  value: String,
+  inject_env: Option<String>,
+) -> Result<(), AppError> {
+  validate_name(&name)?;
+  if let Some(ref var) = inject_env {
+    validate_env_var(var)?;
+  }
+  active_store().set(&name, &value)?;
+  if let Some(var) = inject_env {
+    settings.update(|s| {
+      s.backend_secret_env.retain(|_, n| n != &name);
+      s.backend_secret_env.insert(var, name.clone());
+    })?;
+  }
+  crate::app_log(&format!("secrets: set {}", name));
+  Ok(())
+}
+
+/// Read a secret; None when it has never been set.
+#[tauri::command]
+pub fn secret_get(name: String) -> Result<Option<String>, AppError> {
+  validate_name(&name)?;
+  Ok(get(&name)?)
+}
+
+/// Delete a secret (no-op if absent) and stop injecting it into the backend env.
+#[tauri::command]
+pub fn secret_delete(settings: tauri::State<SettingsState>, name: String) -> Result<(), AppError> {
+  validate_name(&name)?;
+  active_store().delete(&name)?;
+  settings.update(|s| s.backend_secret_env.retain(|_, n| n != &name))?;
+  crate::app_log(&format!("secrets: deleted {}", name));
+  Ok(())
+}
@@ -0,0 +1,494 @@
+// Service mode: the backend is managed by the OS (Windows service, launchd agent, systemd user unit)
+// instead of being spawned as our child. When enabled, autostart/retry only start the service and wait for health.
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+use tauri::Manager;
+
+use crate::BackendState;
+use crate::error::AppError;
+use crate::settings::SettingsState;
+
+#[cfg(target_os = "windows")]
+const WINDOWS_SERVICE_NAME: &str = "AI_Mentor_Backend";
+#[cfg(target_os = "windows")]
+const WINDOWS_SERVICE_HOST_NAME: &str = "ai-mentor-service";
+/// Anything smaller is the dev-tree stub from bin/, not a PyInstaller build of the host.
+#[cfg(target_os = "windows")]
+const MIN_SERVICE_HOST_BYTES: u64 = 100 * 1024;
+/// Written to <base>\service; the host merges it into the backend's environment.
+#[cfg(target_os = "windows")]
+const WINDOWS_SERVICE_ENV_FILE: &str = "backend_env.json";
+#[cfg(target_os = "macos")]
+const LAUNCHD_LABEL: &str = "com.ai.mentor.backend";
+#[cfg(all(unix, not(target_os = "macos")))]
+const SYSTEMD_UNIT_NAME: &str = "ai-mentor-backend.service";
+#[cfg_attr(windows, allow(dead_code))]
+const SERVICE_LOG_NAME: &str = "backend_service.log";
+const SERVICE_START_TIMEOUT_MS: u64 = 30_000;
+const NOT_READY_REASON_SERVICE_NOT_RUNNING: &str = "SERVICE_NOT_RUNNING";
+const NOT_READY_REASON_SERVICE_STOPPED: &str = "SERVICE_STOPPED";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendServiceStatus {
+  /// Service mode setting: autostart defers to the OS service instead of spawning a child.
+  pub mode_enabled: bool,
+  /// "windows-service" | "launchd" | "systemd"
+  pub manager: &'static str,
+  pub installed: bool,
+  pub running: bool,
+}
+
+/// True when the user installed the backend as an OS service (settings.service_mode).
+pub fn is_enabled(app: &tauri::AppHandle) -> bool {
+  app.state::<SettingsState>().inner.lock().is_ok_and(|s| s.service_mode)
+}
+
+pub(crate) fn run_cmd(program: &str, args: &[&str]) -> Result<std::process::Output, String> {
+  let mut cmd = std::process::Command::new(program);
+  cmd.args(args);
+  #[cfg(windows)]
+  cmd.creation_flags(crate::CREATE_NO_WINDOW);
+  cmd.output().map_err(|e| format!("{}: {}", program, e))
+}
+
+#[cfg_attr(windows, allow(dead_code))]
+pub(crate) fn run_ok(program: &str, args: &[&str]) -> Result<(), String> {
+  let out = run_cmd(program, args)?;
+  if out.status.success() {
+    return Ok(());
+  }
+  Err(format!(
+    "{} {} failed: {}",
+    program,
+    args.join(" "),
+    String::from_utf8_lossy(&out.stderr).trim()
+  ))
+}
+
+#[cfg_attr(windows, allow(dead_code))]
+fn service_log_path() -> PathBuf {
+  crate::logs_dir().join(SERVICE_LOG_NAME)
+}
+
+/// Environment for the service-managed backend: what a spawned child gets (port, base/models dirs,
+/// configured env, secrets), minus this session's shutdown token since the service outlives the app.
+fn service_env(app: &tauri::AppHandle) -> Vec<(String, String)> {
+  let settings = app.state::<SettingsState>().get();
+  crate::backend_launch_env(&settings)
+    .into_iter()
+    .filter(|(var, _)| var != crate::BACKEND_SHUTDOWN_TOKEN_VAR)
+    .collect()
+}
+
+/// The unit/plist carries secrets, so keep it private to the user.
+#[cfg(unix)]
+fn write_private(path: &std::path::Path, contents: &str) -> Result<(), String> {
+  use std::os::unix::fs::PermissionsExt;
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+  }
+  std::fs::write(path, contents).map_err(|e| e.to_string())?;
+  std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).map_err(|e| e.to_string())
+}
+
+/// Text for a plist <string>/<key> (also used by launch_at_login).
+#[cfg(target_os = "macos")]
+pub(crate) fn xml_escape(s: &str) -> String {
+  s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg_attr(windows, allow(dead_code))]
+pub(crate) fn home_dir() -> PathBuf {
+  std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default()
+}
+
+/// Executable the OS service runs. On Windows this is the service host (pywin32 wrapper) that
+/// supervises ai-mentor-backend.exe next to it; elsewhere launchd/systemd run the backend directly.
+/// The release build bundles the host as a sidecar (see bin/README.md); a dev tree only has a stub,
+/// so a host that is missing or stub-sized means service mode is not available in this build.
+fn service_exe_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+  #[cfg(target_os = "windows")]
+  {
+    let is_host = |p: &PathBuf| p.metadata().is_ok_and(|m| m.is_file() && m.len() >= MIN_SERVICE_HOST_BYTES);
+    let bundled = crate::sidecar::resolve(app, WINDOWS_SERVICE_HOST_NAME).ok().filter(is_host);
+    let installed = crate::app_base_dir().join("service").join(format!("{}.exe", WINDOWS_SERVICE_HOST_NAME));
+    bundled.or_else(|| Some(installed).filter(is_host)).ok_or_else(|| {
+      AppError::Unsupported(format!(
+        "backend service is not available in this build: {}.exe is not bundled (see packaging/windows_service)",
+        WINDOWS_SERVICE_HOST_NAME
+      ))
+    })
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    let exe = crate::backend_exe_path(app)?;
+    if !exe.is_file() {
+      return Err(AppError::ResourceNotFound(format!("backend exe not found: {}", exe.display())));
+    }
+    Ok(exe)
+  }
+}
+
+// --- Windows service (sc.exe) ---
+
+#[cfg(target_os = "windows")]
+mod platform {
+  use super::*;
+
+  pub const MANAGER: &str = "windows-service";
+
+  /// sc create, then point the service at our base dir through its registry Environment value (the
+  /// service runs as LocalSystem, whose LOCALAPPDATA is not ours). The rest of the env, secrets
+  /// included, goes to a file under <base>\service rather than the world-readable Services key.
+  pub fn install(exe: &std::path::Path, env: &[(String, String)], elevate: bool) -> Result<(), String> {
+    let bin_path = format!("\"{}\"", exe.display());
+    crate::elevate::run_checked(
+      "sc.exe",
+      &[
+        "create",
+        WINDOWS_SERVICE_NAME,
+        "binPath=",
+        &bin_path,
+        "start=",
+        "auto",
+        "DisplayName=",
+        "AI Mentor Backend",
+      ],
+      elevate,
+    )?;
+    let key = format!("HKLM\\SYSTEM\\CurrentControlSet\\Services\\{}", WINDOWS_SERVICE_NAME);
+    let base = format!("AI_MENTOR_BASE_DIR={}", crate::app_base_dir().display());
+    crate::elevate::run_checked(
+      "reg.exe",
+      &["add", &key, "/v", "Environment", "/t", "REG_MULTI_SZ", "/d", &base, "/f"],
+      elevate,
+    )?;
+    configure(exe, env)
+  }
+
+  /// Rewrite the env file the host reads on each service start.
+  pub fn configure(_exe: &std::path::Path, env: &[(String, String)]) -> Result<(), String> {
+    let dir = crate::app_base_dir().join("service");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let map: serde_json::Map<String, serde_json::Value> =
+      env.iter().map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone()))).collect();
+    let body = serde_json::to_string_pretty(&map).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(WINDOWS_SERVICE_ENV_FILE), body).map_err(|e| e.to_string())
+  }
+
+  pub fn uninstall(elevate: bool) -> Result<(), String> {
+    let _ = stop(elevate);
+    crate::elevate::run_checked("sc.exe", &["delete", WINDOWS_SERVICE_NAME], elevate)
+  }
+
+  pub fn start(elevate: bool) -> Result<(), String> {
+    if query()?.1 {
+      return Ok(());
+    }
+    crate::elevate::run_checked("sc.exe", &["start", WINDOWS_SERVICE_NAME], elevate)
+  }
+
+  pub fn stop(elevate: bool) -> Result<(), String> {
+    if !query()?.1 {
+      return Ok(());
+    }
+    crate::elevate::run_checked("sc.exe", &["stop", WINDOWS_SERVICE_NAME], elevate)
+  }
+
+  /// (installed, running). sc query exits 1060 when the service does not exist; STATE 4 = RUNNING.
+  pub fn query() -> Result<(bool, bool), String> {
+    let out = run_cmd("sc.exe", &["query", WINDOWS_SERVICE_NAME])?;
+    if !out.status.success() {
+      return Ok((false, false));
+    }
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let running = stdout
+      .lines()
+      .find(|l| l.trim_start().starts_with("STATE"))
+      .and_then(|l| l.split(':').nth(1))
+      .and_then(|v| v.split_whitespace().next())
+      == Some("4");
+    Ok((true, running))
+  }
+}
+
+// --- macOS launchd agent ---
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::*;
+  use std::fs;
+
+  pub const MANAGER: &str = "launchd";
+
+  fn plist_path() -> PathBuf {
+    home_dir().join("Library/LaunchAgents").join(format!("{}.plist", LAUNCHD_LABEL))
+  }
+
+  fn plist(exe: &std::path::Path, env: &[(String, String)]) -> String {
+    let log = service_log_path();
+    let vars: String = env
+      .iter()
+      .map(|(k, v)| format!("\n    <key>{}</key><string>{}</string>", xml_escape(k), xml_escape(v)))
+      .collect();
+    format!(
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key><string>{label}</string>
+  <key>ProgramArguments</key><array><string>{exe}</string></array>
+  <key>RunAtLoad</key><true/>
+  <key>KeepAlive</key><true/>
+  <key>StandardOutPath</key><string>{log}</string>
+  <key>StandardErrorPath</key><string>{log}</string>
+  <key>EnvironmentVariables</key><dict>{vars}
+  </dict>
+</dict>
+</plist>
+"#,
+      label = LAUNCHD_LABEL,
+      exe = xml_escape(&exe.display().to_string()),
+      log = xml_escape(&log.display().to_string()),
+      vars = vars
+    )
+  }
+
+  pub fn install(exe: &std::path::Path, env: &[(String, String)], _elevate: bool) -> Result<(), String> {
+    configure(exe, env)?;
+    start(false)
+  }
+
+  /// launchd reads the plist on load, so a rewrite applies from the next start.
+  pub fn configure(exe: &std::path::Path, env: &[(String, String)]) -> Result<(), String> {
+    write_private(&plist_path(), &plist(exe, env))
+  }
+
+  pub fn uninstall(_elevate: bool) -> Result<(), String> {
+    let _ = stop(false);
+    let _ = fs::remove_file(plist_path());
+    Ok(())
+  }
+
+  /// load -w (KeepAlive would respawn the agent after `launchctl stop`, so start/stop = load/unload).
+  pub fn start(_elevate: bool) -> Result<(), String> {
+    if query()?.1 {
+      return Ok(());
+    }
+    run_ok("launchctl", &["load", "-w", &plist_path().to_string_lossy()])
+  }
+
+  pub fn stop(_elevate: bool) -> Result<(), String> {
+    run_ok("launchctl", &["unload", "-w", &plist_path().to_string_lossy()])
+  }
+
+  pub fn query() -> Result<(bool, bool), String> {
+    let installed = plist_path().is_file();
+    let out = run_cmd("launchctl", &["list", LAUNCHD_LABEL])?;
+    let running = out.status.success() && String::from_utf8_lossy(&out.stdout).contains("\"PID\"");
+    Ok((installed, running))
+  }
+}
+
+// --- Linux systemd user unit ---
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+  use super::*;
+  use std::fs;
+
+  pub const MANAGER: &str = "systemd";
+
+  fn unit_path() -> PathBuf {
+    home_dir().join(".config/systemd/user").join(SYSTEMD_UNIT_NAME)
+  }
+
+  /// Quoted Environment= assignment: backslash and quote are escaped, % would start a specifier.
+  fn environment_line(var: &str, value: &str) -> String {
+    let assignment = format!("{}={}", var, value);
+    format!(
+      "Environment=\"{}\"\n",
+      assignment.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%")
+    )
+  }
+
+  fn unit(exe: &std::path::Path, env: &[(String, String)]) -> String {
+    let log = service_log_path();
+    let vars: String = env.iter().map(|(k, v)| environment_line(k, v)).collect();
+    format!(
+      "[Unit]\nDescription=AI Mentor Backend\n\n[Service]\nExecStart=\"{exe}\"\n{vars}Restart=on-failure\nStandardOutput=append:{log}\nStandardError=append:{log}\n\n[Install]\nWantedBy=default.target\n",
+      exe = exe.display(),
+      vars = vars,
+      log = log.display()
+    )
+  }
+
+  fn systemctl(args: &[&str]) -> Result<(), String> {
+    let mut full = vec!["--user"];
+    full.extend_from_slice(args);
+    run_ok("systemctl", &full)
+  }
+
+  pub fn install(exe: &std::path::Path, env: &[(String, String)], _elevate: bool) -> Result<(), String> {
+    configure(exe, env)?;
+    systemctl(&["enable", "--now", SYSTEMD_UNIT_NAME])
+  }
+
+  pub fn configure(exe: &std::path::Path, env: &[(String, String)]) -> Result<(), String> {
+    write_private(&unit_path(), &unit(exe, env))?;
+    systemctl(&["daemon-reload"])
+  }
+
+  pub fn uninstall(_elevate: bool) -> Result<(), String> {
+    let _ = systemctl(&["disable", "--now", SYSTEMD_UNIT_NAME]);
+    let _ = fs::remove_file(unit_path());
+    systemctl(&["daemon-reload"])
+  }
+
+  pub fn start(_elevate: bool) -> Result<(), String> {
+    systemctl(&["start", SYSTEMD_UNIT_NAME])
+  }
+
+  pub fn stop(_elevate: bool) -> Result<(), String> {
+    systemctl(&["stop", SYSTEMD_UNIT_NAME])
+  }
+
+  pub fn query() -> Result<(bool, bool), String> {
+    let installed = unit_path().is_file();
+    let out = run_cmd("systemctl", &["--user", "is-active", "--quiet", SYSTEMD_UNIT_NAME])?;
+    Ok((installed, out.status.success()))
+  }
+}
+
+pub(crate) fn current_status(app: &tauri::AppHandle) -> Result<BackendServiceStatus, String> {
+  let (installed, running) = platform::query()?;
+  Ok(BackendServiceStatus {
+    mode_enabled: is_enabled(app),
+    manager: platform::MANAGER,
+    installed,
+    running,
+  })
+}
+
+/// Service-mode replacement for spawn + health: never spawns a child. Probe health, otherwise ask
+/// the OS to start the service (no elevation prompt from the background flow) and wait for health.
+pub(crate) async fn run_service_flow(state: Arc<BackendState>, generation: u64) {
+  crate::backend_autostart_log("service mode: probing health");
+  if state.probe.healthy().await {
+    let readiness = state.probe.readiness().await;
+    let (status, reason) = crate::health::status_for(readiness).unwrap_or(("READY", None));
+    if let Some(mut g) = state.lock_current(generation) {
+      g.set_status(status, reason);
+    }
+    crate::app_log(&format!("backend service: {} (already running)", status));
+    return;
+  }
+
+  if let Err(e) = platform::start(false) {
+    crate::backend_autostart_log(&format!("service mode: start failed: {}", e));
+  }
+  if let Some(mut g) = state.lock_current(generation) {
+    g.set_status("STARTING", None);
+    g.begin_attempt();
+  }
+
+  let ok = crate::wait_for_health(state.probe.as_ref(), SERVICE_START_TIMEOUT_MS).await;
+  let readiness = if ok { Some(state.probe.readiness().await) } else { None };
+  let Some(mut g) = state.lock_current(generation) else {
+    crate::backend_autostart_log("service mode: attempt superseded, result ignored");
+    return;
+  };
+  let (status, reason) = match readiness {
+    Some(readiness) => crate::health::status_for(readiness).unwrap_or(("READY", None)),
+    None => ("NOT_READY", Some(NOT_READY_REASON_SERVICE_NOT_RUNNING.to_string())),
+  };
+  g.set_status(status, reason.clone());
+  g.end_attempt(ok);
+  drop(g);
+  crate::backend_autostart_log(if ok { "service mode: health OK" } else { "service mode: health timeout" });
+  match reason {
+    Some(reason) => crate::app_log(&format!("backend service: {} ({})", status, reason)),
+    None => crate::app_log(&format!("backend service: {}", status)),
+  }
+}
+
+/// Install the backend as an OS service, enable service mode, and hand over from any managed child.
+/// `elevate` allows a UAC prompt on Windows (creating a service requires admin).
+#[tauri::command]
+pub fn install_backend_service(
+  app: tauri::AppHandle,
+  state: tauri::State<Arc<BackendState>>,
+  settings: tauri::State<SettingsState>,
+  elevate: Option<bool>,
+) -> Result<BackendServiceStatus, AppError> {
+  let exe = service_exe_path(&app)?;
+  platform::install(&exe, &service_env(&app), elevate.unwrap_or(false))?;
+  settings.update(|s| s.service_mode = true)?;
+  crate::app_log(&format!("backend service: installed exe={}", exe.display()));
+
+  let mut g = state.inner.lock().map_err(|e| e.to_string())?;
+  g.supersede();
+  if let Some(mut child) = g.child.take() {
+    let _ = child.kill();
+  }
+  drop(g);
+
+  let state_clone = state.inner().clone();
+  crate::spawn_flow(&state_clone, |gen| run_service_flow(state_clone.clone(), gen));
+  Ok(current_status(&app)?)
+}
+
+/// Stop and remove the OS service and disable service mode (autostart spawns a child again).
+#[tauri::command]
+pub fn uninstall_backend_service(
+  app: tauri::AppHandle,
+  state: tauri::State<Arc<BackendState>>,
+  settings: tauri::State<SettingsState>,
+  elevate: Option<bool>,
+) -> Result<BackendServiceStatus, AppError> {
+  platform::uninstall(elevate.unwrap_or(false))?;
+  settings.update(|s| s.service_mode = false)?;
+  if let Ok(mut g) = state.inner.lock() {
+    g.supersede();
+    g.set_status("NOT_READY", None);
+  }
+  crate::app_log("backend service: uninstalled");
+  Ok(current_status(&app)?)
+}
+
+/// Refreshes the service's environment from the current settings, then starts it.
+#[tauri::command]
+pub fn start_backend_service(
+  app: tauri::AppHandle,
+  state: tauri::State<Arc<BackendState>>,
+  elevate: Option<bool>,
+) -> Result<(), AppError> {
+  if platform::query()?.0 {
+    platform::configure(&service_exe_path(&app)?, &service_env(&app))?;
+  }
+  platform::start(elevate.unwrap_or(false))?;
+  let state_clone = state.inner().clone();
+  crate::spawn_flow(&state_clone, |gen| run_service_flow(state_clone.clone(), gen));
+  Ok(())
+}
+
+#[tauri::command]
+pub fn stop_backend_service(state: tauri::State<Arc<BackendState>>, elevate: Option<bool>) -> Result<(), AppError> {
+  platform::stop(elevate.unwrap_or(false))?;
+  if let Ok(mut g) = state.inner.lock() {
+    g.supersede();
+    g.set_status("NOT_READY", Some(NOT_READY_REASON_SERVICE_STOPPED.to_string()));
+  }
+  crate::app_log("backend service: stopped");
+  Ok(())
+}
+
+#[tauri::command]
+pub fn get_backend_service_status(app: tauri::AppHandle) -> Result<BackendServiceStatus, AppError> {
+  Ok(current_status(&app)?)
+}
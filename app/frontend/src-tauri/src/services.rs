@@ -0,0 +1,276 @@
+// Registry of managed sidecars. `backend` is the primary service: its BackendState is also managed on
+// its own (the single-backend commands and the other modules use it) and keeps profiles, service mode
+// and fast start. Companions such as the vector store are plain children of this app with their own
+// binary, port, health URL, child log and status; they start after the backend and stop on exit.
+// Commands taking an optional `service` default to the backend.
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::Manager;
+
+use crate::error::AppError;
+use crate::launcher::{CommandLauncher, HttpHealthProbe, LaunchSpec};
+use crate::BackendState;
+
+pub const BACKEND_SERVICE_NAME: &str = "backend";
+pub const SERVICE_STATUS_EVENT: &str = "service-status";
+const NOT_READY_REASON_NOT_INSTALLED: &str = "NOT_INSTALLED";
+
+/// A companion process started next to the backend.
+pub struct CompanionSpec {
+  pub name: &'static str,
+  /// Bundled binary name, resolved by sidecar::resolve.
+  pub binary: &'static str,
+  pub port: u16,
+  pub health_path: &'static str,
+  pub child_log_name: &'static str,
+  /// Passed to the backend so it can reach this companion.
+  pub port_env: &'static str,
+}
+
+pub const COMPANIONS: &[CompanionSpec] = &[CompanionSpec {
+  name: "vector-store",
+  binary: "ai-mentor-vector-store",
+  port: 8001,
+  health_path: "/health",
+  child_log_name: "vector_store_child.log",
+  port_env: "AI_MENTOR_VECTOR_STORE_PORT",
+}];
+
+impl CompanionSpec {
+  fn health_url(&self) -> String {
+    format!("http://{}:{}{}", crate::BACKEND_HOST, self.port, self.health_path)
+  }
+
+  fn exe_path(&self, app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    crate::sidecar::resolve(app, self.binary)
+  }
+
+  fn child_log_path(&self) -> PathBuf {
+    crate::logs_dir().join(self.child_log_name)
+  }
+
+  fn launch_spec(&self, exe: PathBuf) -> LaunchSpec {
+    LaunchSpec {
+      exe,
+      env: vec![
+        ("AI_MENTOR_PORT".to_string(), self.port.to_string()),
+        ("AI_MENTOR_BASE_DIR".to_string(), crate::app_base_dir().display().to_string()),
+      ],
+      log_path: self.child_log_path(),
+      // Companions are always stopped on exit (stop_companions), so their output can be piped.
+      direct_output: false,
+    }
+  }
+}
+
+/// Env vars pointing the backend at its companions.
+pub fn companion_env() -> Vec<(String, String)> {
+  COMPANIONS
+    .iter()
+    .map(|c| (c.port_env.to_string(), c.port.to_string()))
+    .collect()
+}
+
+pub struct ManagedService {
+  pub name: &'static str,
+  pub state: Arc<BackendState>,
+  /// None for the backend.
+  companion: Option<&'static CompanionSpec>,
+}
+
+impl ManagedService {
+  pub fn child_log_path(&self) -> PathBuf {
+    match self.companion {
+      None => crate::backend_child_log_path(),
+      Some(spec) => spec.child_log_path(),
+    }
+  }
+
+  #[cfg_attr(not(windows), allow(dead_code))]
+  fn image_name(&self, app: &tauri::AppHandle) -> String {
+    let exe = match self.companion {
+      None => crate::backend_exe_path(app),
+      Some(spec) => spec.exe_path(app),
+    };
+    let binary = self.companion.map(|c| c.binary).unwrap_or(crate::BACKEND_BINARY_NAME);
+    exe
+      .ok()
+      .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+      .unwrap_or_else(|| format!("{}{}", binary, std::env::consts::EXE_SUFFIX))
+  }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceInfo {
+  pub name: &'static str,
+  /// Same format as get_backend_status.
+  pub status: String,
+  pub port: u16,
+  pub health_url: String,
+  pub child_log_path: PathBuf,
+  pub exe_path: Option<PathBuf>,
+}
+
+/// Payload of the `service-status` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceStatusChange {
+  pub service: &'static str,
+  pub status: String,
+  pub reason: Option<String>,
+}
+
+pub struct ServiceRegistry {
+  services: Vec<ManagedService>,
+}
+
+impl ServiceRegistry {
+  pub fn new(backend: Arc<BackendState>) -> Self {
+    let mut services = vec![ManagedService {
+      name: BACKEND_SERVICE_NAME,
+      state: backend,
+      companion: None,
+    }];
+    services.extend(COMPANIONS.iter().map(|spec| {
+      let probe = HttpHealthProbe {
+        url: spec.health_url(),
+        host: crate::BACKEND_HOST.to_string(),
+        port: spec.port,
+      };
+      ManagedService {
+        name: spec.name,
+        state: Arc::new(BackendState::new(Box::new(CommandLauncher), Box::new(probe))),
+        companion: Some(spec),
+      }
+    }));
+    Self { services }
+  }
+
+  /// Look up a service by name; None is the backend.
+  pub fn get(&self, name: Option<&str>) -> Result<&ManagedService, AppError> {
+    let name = name.unwrap_or(BACKEND_SERVICE_NAME);
+    self
+      .services
+      .iter()
+      .find(|s| s.name == name)
+      .ok_or_else(|| AppError::ResourceNotFound(format!("unknown service: {}", name)))
+  }
+
+  pub fn all(&self) -> &[ManagedService] {
+    &self.services
+  }
+}
+
+/// Health check, then spawn unless something already answers (or squats) on the companion's port.
+async fn run_companion_flow(state: Arc<BackendState>, generation: u64, spec: &'static CompanionSpec, exe: PathBuf) {
+  if state.probe.healthy().await {
+    if let Some(mut g) = state.lock_current(generation) {
+      g.set_status("READY", None);
+    }
+    crate::app_log(&format!("{} autostart: READY (already running)", spec.name));
+    return;
+  }
+  if state.probe.port_in_use() {
+    let owner = crate::port_owner::find_async(spec.port).await;
+    if let Some(mut g) = state.lock_current(generation) {
+      g.port_owner = owner;
+      g.set_status("NOT_READY", Some(crate::NOT_READY_REASON_PORT_IN_USE.to_string()));
+    }
+    crate::app_log(&format!("{} autostart: NOT_READY (PORT_IN_USE_NO_HEALTH)", spec.name));
+    return;
+  }
+  crate::spawn_and_health(state, generation, spec.launch_spec(exe), spec.name).await;
+}
+
+/// Start a companion's flow, or mark it NOT_INSTALLED when its binary is not bundled.
+fn start_companion(
+  app: &tauri::AppHandle,
+  service: &ManagedService,
+  spec: &'static CompanionSpec,
+) -> Result<tauri::async_runtime::JoinHandle<()>, AppError> {
+  let exe = match spec.exe_path(app) {
+    Ok(exe) => exe,
+    Err(e) => {
+      if let Ok(mut g) = service.state.inner.lock() {
+        g.set_status("NOT_READY", Some(NOT_READY_REASON_NOT_INSTALLED.to_string()));
+      }
+      return Err(AppError::ResourceNotFound(format!("{} is not installed: {}", spec.name, e.message())));
+    }
+  };
+  let state = &service.state;
+  Ok(crate::spawn_flow(state, |gen| run_companion_flow(state.clone(), gen, spec, exe)))
+}
+
+/// Kick off every companion (autostart path; does not wait).
+pub fn autostart_companions(app: &tauri::AppHandle) {
+  let registry = app.state::<ServiceRegistry>();
+  for service in registry.all() {
+    if let Some(spec) = service.companion {
+      if let Err(e) = start_companion(app, service, spec) {
+        crate::app_log(&format!("{} autostart: {}", spec.name, e));
+      }
+    }
+  }
+}
+
+/// Kill the service's previous child and start it again (see restart_backend for the backend).
+pub async fn restart(app: &tauri::AppHandle, service: &ManagedService) -> Result<String, AppError> {
+  let Some(spec) = service.companion else {
+    return crate::restart_backend(app, &service.state).await;
+  };
+  crate::reset_backend(&service.state)?;
+  let handle = start_companion(app, service, spec)?;
+  crate::await_flow(&service.state, handle).await
+}
+
+/// Kill every process with the service's image name (Windows), then run its autostart flow again.
+pub async fn kill_and_retry(app: &tauri::AppHandle, service: &ManagedService) -> Result<String, AppError> {
+  #[cfg(target_os = "windows")]
+  {
+    let _ = std::process::Command::new("taskkill")
+      .args(["/F", "/IM", &service.image_name(app)])
+      .output();
+  }
+  crate::reset_backend(&service.state)?;
+  match service.companion {
+    None => crate::rerun_autostart(app, &service.state).await,
+    Some(spec) => {
+      let handle = start_companion(app, service, spec)?;
+      crate::await_flow(&service.state, handle).await
+    }
+  }
+}
+
+/// Stop the companions this app started (shutdown hook; the backend has its own).
+pub fn stop_companions(registry: &ServiceRegistry) {
+  for service in registry.all().iter().filter(|s| s.companion.is_some()) {
+    let _ = crate::stop_owned_backend(&service.state);
+  }
+}
+
+#[tauri::command]
+pub fn list_services(app: tauri::AppHandle, registry: tauri::State<ServiceRegistry>) -> Vec<ServiceInfo> {
+  registry
+    .all()
+    .iter()
+    .map(|service| match service.companion {
+      None => ServiceInfo {
+        name: service.name,
+        status: crate::backend_status_string(&service.state),
+        port: crate::backend_port(),
+        health_url: crate::health_url(),
+        child_log_path: service.child_log_path(),
+        exe_path: crate::backend_exe_path(&app).ok(),
+      },
+      Some(spec) => ServiceInfo {
+        name: service.name,
+        status: crate::backend_status_string(&service.state),
+        port: spec.port,
+        health_url: spec.health_url(),
+        child_log_path: service.child_log_path(),
+        exe_path: spec.exe_path(&app).ok(),
+      },
+    })
+    .collect()
+}
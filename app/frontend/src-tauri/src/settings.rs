@@ -0,0 +1,104 @@
+// Persisted desktop settings (settings.json by default, see settings_store). Unknown/missing fields fall back to defaults.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::cli::LogLevel;
+use crate::health::HealthSettings;
+use crate::idle::IdleSettings;
+use crate::maintenance::MaintenanceSettings;
+use crate::profiles::BackendProfile;
+use crate::settings_store::{self, SettingsStore};
+
+const SETTINGS_FILE_NAME: &str = "settings.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+  /// Webview zoom factor per window label (1.0 = 100%).
+  pub window_zoom: HashMap<String, f64>,
+  /// Backend is installed as an OS service; autostart must not spawn its own child.
+  pub service_mode: bool,
+  /// Env var name -> keyring secret name, injected into the spawned backend.
+  pub backend_secret_env: HashMap<String, String>,
+  /// Extra plain env vars for the spawned backend (see backend_env).
+  pub backend_env: HashMap<String, String>,
+  /// Leave the backend running on exit and reuse it on the next launch (cold-start fast path).
+  /// When off, the backend this app started is stopped on exit.
+  pub backend_keep_alive: bool,
+  /// Global summon hotkey, e.g. "CmdOrCtrl+Shift+M". None = default, "" = disabled.
+  pub summon_hotkey: Option<String>,
+  /// Per-file cap for attach_files_to_backend in bytes. None = attachments::DEFAULT_ATTACHMENT_MAX_BYTES.
+  pub attachment_max_bytes: Option<u64>,
+  /// Data directory from the next launch; only honoured in the default-location settings.json (see paths).
+  pub data_dir: Option<PathBuf>,
+  /// Named backend targets besides the built-in `local` (see profiles).
+  pub backend_profiles: Vec<BackendProfile>,
+  /// Profile used at launch. None = local.
+  pub active_profile: Option<String>,
+  /// Where submit_crash_report posts reports. None = sending is unsupported; reports stay local.
+  pub crash_report_url: Option<String>,
+  /// Health-check timing and endpoint overrides (see health).
+  pub health: HealthSettings,
+  /// Scheduled backend restarts (see maintenance).
+  pub maintenance_restart: MaintenanceSettings,
+  /// URL answering 204 when the machine is online (see network). None = default, "" = disabled.
+  pub network_check_url: Option<String>,
+  /// Stop the backend after a stretch of inactivity (see idle).
+  pub idle_suspend: IdleSettings,
+  /// Encrypt new log lines at rest with a key in the OS keyring (see log_privacy).
+  pub log_encryption: bool,
+  /// Record local usage events to logs/telemetry.jsonl (see telemetry).
+  pub telemetry_enabled: bool,
+  /// Shell log level; --log-level and AI_MENTOR_LOG_LEVEL win. None = info.
+  pub log_level: Option<LogLevel>,
+}
+
+pub fn settings_path() -> PathBuf {
+  crate::app_base_dir().join(SETTINGS_FILE_NAME)
+}
+
+/// Load settings from the active store; nothing stored or a load error yields defaults (logged to app.log).
+/// A non-JSON store that is still empty is seeded from an existing settings.json.
+pub fn load() -> Settings {
+  let store = settings_store::active_store();
+  match store.load() {
+    Ok(Some(s)) => s,
+    Ok(None) if store.name() != "json" => settings_store::json_store().load().ok().flatten().unwrap_or_default(),
+    Ok(None) => Settings::default(),
+    Err(e) => {
+      crate::app_log(&format!("settings: {} load failed ({}), using defaults", store.name(), e));
+      Settings::default()
+    }
+  }
+}
+
+pub fn save(settings: &Settings) -> Result<(), String> {
+  settings_store::active_store().save(settings)
+}
+
+pub struct SettingsState {
+  pub inner: Mutex<Settings>,
+}
+
+impl SettingsState {
+  pub fn load() -> Self {
+    Self {
+      inner: Mutex::new(load()),
+    }
+  }
+
+  pub fn get(&self) -> Settings {
+    self.inner.lock().map(|g| g.clone()).unwrap_or_default()
+  }
+
+  /// Apply `f` to the in-memory settings and persist the result.
+  pub fn update<F: FnOnce(&mut Settings)>(&self, f: F) -> Result<Settings, String> {
+    let mut g = self.inner.lock().map_err(|e| e.to_string())?;
+    f(&mut g);
+    save(&g)?;
+    Ok(g.clone())
+  }
+}
@@ -0,0 +1,136 @@
+// Persistence backends for Settings. Selected with AI_MENTOR_SETTINGS_STORE=json|sqlite (default json);
+// other backends (roaming, policy-managed) only need to implement SettingsStore.
+
+use crate::settings::Settings;
+use std::fs;
+use std::path::PathBuf;
+
+const SETTINGS_DB_NAME: &str = "settings.db";
+
+pub trait SettingsStore: Send + Sync {
+  fn name(&self) -> &'static str;
+  /// Ok(None) when nothing has been stored yet.
+  fn load(&self) -> Result<Option<Settings>, String>;
+  fn save(&self, settings: &Settings) -> Result<(), String>;
+  /// File to watch for outside edits (see settings_watch); None when there is none.
+  fn watch_path(&self) -> Option<PathBuf> {
+    None
+  }
+}
+
+/// settings.json, written via temp file + rename so a crash never leaves a truncated file.
+pub struct JsonFileStore {
+  pub path: PathBuf,
+}
+
+impl SettingsStore for JsonFileStore {
+  fn name(&self) -> &'static str {
+    "json"
+  }
+
+  fn load(&self) -> Result<Option<Settings>, String> {
+    let text = match fs::read_to_string(&self.path) {
+      Ok(t) => t,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+      Err(e) => return Err(e.to_string()),
+    };
+    serde_json::from_str(&text).map(Some).map_err(|e| e.to_string())
+  }
+
+  fn save(&self, settings: &Settings) -> Result<(), String> {
+    if let Some(parent) = self.path.parent() {
+      fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    let tmp = self.path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &self.path).map_err(|e| e.to_string())
+  }
+
+  fn watch_path(&self) -> Option<PathBuf> {
+    Some(self.path.clone())
+  }
+}
+
+/// settings.db: one row per top-level settings field (key, JSON value), replaced in a transaction.
+pub struct SqliteStore {
+  pub path: PathBuf,
+}
+
+impl SqliteStore {
+  fn open(&self) -> Result<rusqlite::Connection, String> {
+    if let Some(parent) = self.path.parent() {
+      fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let conn = rusqlite::Connection::open(&self.path).map_err(|e| e.to_string())?;
+    conn
+      .execute(
+        "CREATE TABLE IF NOT EXISTS settings (key TEXT PRIMARY KEY, value TEXT NOT NULL)",
+        [],
+      )
+      .map_err(|e| e.to_string())?;
+    Ok(conn)
+  }
+}
+
+impl SettingsStore for SqliteStore {
+  fn name(&self) -> &'static str {
+    "sqlite"
+  }
+
+  fn load(&self) -> Result<Option<Settings>, String> {
+    let conn = self.open()?;
+    let mut stmt = conn.prepare("SELECT key, value FROM settings").map_err(|e| e.to_string())?;
+    let rows = stmt
+      .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))
+      .map_err(|e| e.to_string())?;
+    let mut obj = serde_json::Map::new();
+    for row in rows {
+      let (key, value) = row.map_err(|e| e.to_string())?;
+      let value = serde_json::from_str(&value).map_err(|e| format!("{}: {}", key, e))?;
+      obj.insert(key, value);
+    }
+    if obj.is_empty() {
+      return Ok(None);
+    }
+    serde_json::from_value(serde_json::Value::Object(obj)).map(Some).map_err(|e| e.to_string())
+  }
+
+  fn save(&self, settings: &Settings) -> Result<(), String> {
+    let value = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    let serde_json::Value::Object(obj) = value else {
+      return Err("settings did not serialize to an object".to_string());
+    };
+    let mut conn = self.open()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM settings", []).map_err(|e| e.to_string())?;
+    for (key, value) in obj {
+      tx.execute(
+        "INSERT INTO settings (key, value) VALUES (?1, ?2)",
+        rusqlite::params![key, value.to_string()],
+      )
+      .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+  }
+
+  fn watch_path(&self) -> Option<PathBuf> {
+    Some(self.path.clone())
+  }
+}
+
+pub fn json_store() -> JsonFileStore {
+  JsonFileStore {
+    path: crate::settings::settings_path(),
+  }
+}
+
+/// Store selected by AI_MENTOR_SETTINGS_STORE (unknown values fall back to json).
+pub fn active_store() -> Box<dyn SettingsStore> {
+  match std::env::var("AI_MENTOR_SETTINGS_STORE").as_deref() {
+    Ok("sqlite") => Box::new(SqliteStore {
+      path: crate::app_base_dir().join(SETTINGS_DB_NAME),
+    }),
+    _ => Box::new(json_store()),
+  }
+}
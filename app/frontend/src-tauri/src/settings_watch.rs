@@ -0,0 +1,135 @@
+// Hot reload of settings edited outside the app. The active store's file (settings.json or
+// settings.db) is polled for modification; on a change the settings are re-read, fields that can
+// change at runtime are applied, and `settings-changed` reports which fields changed and which of
+// them only take effect after a backend or app restart. The app's own saves change the file but
+// not the values and are ignored; a file that does not parse (e.g. mid-edit) is skipped.
+
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tauri::{Emitter, Manager};
+
+use crate::settings::{Settings, SettingsState};
+
+pub const SETTINGS_CHANGED_EVENT: &str = "settings-changed";
+const POLL_INTERVAL_MS: u64 = 1_000;
+/// Read once at launch.
+const RESTART_REQUIRED: &[&str] = &["data_dir", "active_profile", "backend_profiles"];
+/// Read when the backend is spawned.
+const BACKEND_RESTART_REQUIRED: &[&str] = &["backend_env", "backend_secret_env", "service_mode"];
+
+/// Payload of `settings-changed`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsChanged {
+  /// Top-level settings fields whose value changed.
+  pub changed: Vec<String>,
+  /// In effect now.
+  pub applied: Vec<String>,
+  pub backend_restart_required: Vec<String>,
+  pub restart_required: Vec<String>,
+}
+
+fn fields(settings: &Settings) -> serde_json::Map<String, serde_json::Value> {
+  match serde_json::to_value(settings) {
+    Ok(serde_json::Value::Object(map)) => map,
+    _ => serde_json::Map::new(),
+  }
+}
+
+/// Names of the top-level fields that differ.
+fn diff(old: &Settings, new: &Settings) -> Vec<String> {
+  let (old, new) = (fields(old), fields(new));
+  let mut changed: Vec<String> = old
+    .keys()
+    .chain(new.keys())
+    .filter(|k| old.get(*k) != new.get(*k))
+    .cloned()
+    .collect();
+  changed.sort();
+  changed.dedup();
+  changed
+}
+
+/// Push a changed field into the module that caches it. Fields not listed here are read from the
+/// settings state whenever they are used.
+fn apply(app: &tauri::AppHandle, field: &str, settings: &Settings) {
+  match field {
+    "log_level" => crate::cli::set_settings_log_level(settings.log_level),
+    "log_encryption" => crate::log_privacy::init(settings),
+    "telemetry_enabled" => crate::telemetry::init(settings),
+    "health" => crate::health::init(&settings.health),
+    "summon_hotkey" => crate::hotkey::register_from_settings(app),
+    "window_zoom" => crate::zoom::apply_saved(app),
+    _ => {}
+  }
+}
+
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+  let md = std::fs::metadata(path).ok()?;
+  Some((md.modified().ok()?, md.len()))
+}
+
+fn reload(app: &tauri::AppHandle) {
+  let loaded = match crate::settings_store::active_store().load() {
+    Ok(Some(s)) => s,
+    Ok(None) => return,
+    Err(e) => {
+      crate::app_log(&format!("settings: changed on disk but not loadable ({}), keeping current", e));
+      return;
+    }
+  };
+  let state = app.state::<SettingsState>();
+  let changed = {
+    let Ok(mut g) = state.inner.lock() else {
+      return;
+    };
+    let changed = diff(&g, &loaded);
+    *g = loaded.clone();
+    changed
+  };
+  if changed.is_empty() {
+    return;
+  }
+  let mut event = SettingsChanged {
+    changed: changed.clone(),
+    applied: Vec::new(),
+    backend_restart_required: Vec::new(),
+    restart_required: Vec::new(),
+  };
+  for field in changed {
+    if RESTART_REQUIRED.contains(&field.as_str()) {
+      event.restart_required.push(field);
+    } else if BACKEND_RESTART_REQUIRED.contains(&field.as_str()) {
+      event.backend_restart_required.push(field);
+    } else {
+      apply(app, &field, &loaded);
+      event.applied.push(field);
+    }
+  }
+  crate::app_log(&format!(
+    "settings: reloaded; applied [{}], backend restart [{}], app restart [{}]",
+    event.applied.join(", "),
+    event.backend_restart_required.join(", "),
+    event.restart_required.join(", ")
+  ));
+  let _ = app.emit(SETTINGS_CHANGED_EVENT, &event);
+}
+
+/// Poll the active store's file every POLL_INTERVAL_MS for the rest of the session.
+pub fn start(app: &tauri::AppHandle) {
+  let Some(path) = crate::settings_store::active_store().watch_path() else {
+    return;
+  };
+  let app = app.clone();
+  std::thread::spawn(move || {
+    let mut last = stamp(&path);
+    loop {
+      std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+      let current = stamp(&path);
+      if current != last {
+        last = current;
+        reload(&app);
+      }
+    }
+  });
+}
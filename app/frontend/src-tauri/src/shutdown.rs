@@ -0,0 +1,124 @@
+// Shutdown hooks: subsystems register teardown work with a priority (lower runs first) and a timeout.
+// Every exit path (last window closed, tray quit, relaunch, OS session end / app exit) goes through
+// run_shutdown, which runs the hooks once, in order; a hook that overruns its timeout is abandoned.
+// Closing the main window while chat windows stay open does not exit and runs nothing.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+use crate::error::AppError;
+
+/// Stop producers first (streams, watchers, downloads) ...
+pub const PRIORITY_PRODUCERS: i32 = 10;
+/// ... then the backend supervisor ...
+pub const PRIORITY_BACKEND: i32 = 50;
+/// ... and process-level cleanup (lock file, log flush) last.
+pub const PRIORITY_FINAL: i32 = 100;
+
+type HookFn = Box<dyn FnOnce() + Send>;
+
+struct Hook {
+  name: &'static str,
+  priority: i32,
+  timeout: Duration,
+  run: HookFn,
+}
+
+#[derive(Default)]
+pub struct ShutdownHooks {
+  hooks: Mutex<Vec<Hook>>,
+  done: AtomicBool,
+}
+
+impl ShutdownHooks {
+  /// Register `run` to be called once on shutdown. Hooks with equal priority run in registration order.
+  pub fn register<F>(&self, name: &'static str, priority: i32, timeout: Duration, run: F)
+  where
+    F: FnOnce() + Send + 'static,
+  {
+    if let Ok(mut g) = self.hooks.lock() {
+      g.push(Hook {
+        name,
+        priority,
+        timeout,
+        run: Box::new(run),
+      });
+    }
+  }
+
+  fn run_all(&self, reason: &str) {
+    if self.done.swap(true, Ordering::SeqCst) {
+      return;
+    }
+    let mut hooks = match self.hooks.lock() {
+      Ok(mut g) => std::mem::take(&mut *g),
+      Err(_) => return,
+    };
+    hooks.sort_by_key(|h| h.priority);
+    crate::app_log(&format!("shutdown: reason={} hooks={}", reason, hooks.len()));
+    for hook in hooks {
+      let started = Instant::now();
+      let (tx, rx) = mpsc::channel();
+      let run = hook.run;
+      std::thread::spawn(move || {
+        run();
+        let _ = tx.send(());
+      });
+      match rx.recv_timeout(hook.timeout) {
+        Ok(()) => crate::app_log(&format!("shutdown: {} done in {} ms", hook.name, started.elapsed().as_millis())),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+          crate::app_log(&format!("shutdown: {} timed out after {} ms", hook.name, hook.timeout.as_millis()))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => crate::app_log(&format!("shutdown: {} panicked", hook.name)),
+      }
+    }
+  }
+}
+
+/// The single exit path. Safe to call more than once; only the first call runs the hooks.
+pub(crate) fn run_shutdown(app: &tauri::AppHandle, reason: &str) {
+  if let Some(hooks) = app.try_state::<ShutdownHooks>() {
+    hooks.run_all(reason);
+  }
+}
+
+/// Run the shutdown hooks, then exit the process (tray quit, relaunch and similar explicit exits).
+pub(crate) fn exit_app(app: &tauri::AppHandle, reason: &str, code: i32) {
+  run_shutdown(app, reason);
+  app.exit(code);
+}
+
+/// Quit from the UI (menu / tray) through the shared shutdown path.
+#[tauri::command]
+pub fn quit_app(app: tauri::AppHandle) {
+  exit_app(&app, "quit", 0);
+}
+
+/// Shut down like quit_app (backend, logs, lock file), then start the shell again with the same
+/// arguments. `skip_autostart` adds --no-autostart for the next boot (troubleshooting); without it a
+/// --no-autostart of this session is dropped, as is --minimized (the user asked for the app).
+#[tauri::command]
+pub fn relaunch_app(app: tauri::AppHandle, skip_autostart: Option<bool>) -> Result<(), AppError> {
+  let exe = std::env::current_exe()?;
+  let mut args: Vec<std::ffi::OsString> = std::env::args_os()
+    .skip(1)
+    .filter(|a| *a != crate::cli::NO_AUTOSTART_FLAG && *a != crate::cli::MINIMIZED_FLAG)
+    .collect();
+  if skip_autostart.unwrap_or(false) {
+    args.push(crate::cli::NO_AUTOSTART_FLAG.into());
+  }
+  crate::app_log(&format!("relaunch: {} {:?}", exe.display(), args));
+  // The lock file goes with the hooks, so the new instance is only started afterwards.
+  run_shutdown(&app, "relaunch");
+  let code = match std::process::Command::new(&exe).args(&args).spawn() {
+    Ok(_) => 0,
+    Err(e) => {
+      crate::app_log(&format!("relaunch: could not start {}: {}", exe.display(), e));
+      1
+    }
+  };
+  exit_app(&app, "relaunch", code);
+  Ok(())
+}
@@ -0,0 +1,214 @@
+// Bundled sidecar binaries (backend, companions). Tauri's externalBin (tauri.<platform>.conf.json)
+// installs <name><exe suffix> next to the app executable, taking it from bin/<name>-<target triple> at
+// build time; bundles that ship bin/ as a resource are still found there. The first that exists
+// wins. Before a spawn the executable header is checked against the arch this
+// app runs as, so an x64-only bundle on ARM64 or a Windows exe in a Linux bundle ends up NOT_READY
+// with reason WRONG_ARCH instead of an opaque spawn error.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::error::AppError;
+
+const TARGET_TRIPLE: &str = std::env!("TARGET_TRIPLE");
+/// Enough for the DOS stub + PE header of any real executable.
+const HEADER_LEN: u64 = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+  Pe,
+  Elf,
+  MachO,
+}
+
+impl Format {
+  fn native() -> Self {
+    if cfg!(windows) {
+      Format::Pe
+    } else if cfg!(target_os = "macos") {
+      Format::MachO
+    } else {
+      Format::Elf
+    }
+  }
+
+  fn label(self) -> &'static str {
+    match self {
+      Format::Pe => "Windows",
+      Format::Elf => "Linux",
+      Format::MachO => "macOS",
+    }
+  }
+}
+
+/// Resource-relative candidates for `name`, most specific first.
+fn candidates(name: &str) -> [String; 2] {
+  let suffix = std::env::consts::EXE_SUFFIX;
+  [format!("bin/{}-{}{}", name, TARGET_TRIPLE, suffix), format!("bin/{}{}", name, suffix)]
+}
+
+/// First bundled binary for `name` that exists: next to the app executable, then under resources.
+pub fn resolve(app: &tauri::AppHandle, name: &str) -> Result<PathBuf, AppError> {
+  let mut paths: Vec<PathBuf> = std::env::current_exe()
+    .ok()
+    .and_then(|exe| exe.parent().map(|dir| dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX))))
+    .into_iter()
+    .collect();
+  for rel in candidates(name) {
+    let path = app
+      .path()
+      .resolve(&rel, tauri::path::BaseDirectory::Resource)
+      .map_err(|e| AppError::ResourceNotFound(format!("{:?}", e)))?;
+    paths.push(path);
+  }
+  match paths.iter().find(|p| p.is_file()) {
+    Some(path) => Ok(path.clone()),
+    None => {
+      let tried: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+      Err(AppError::ResourceNotFound(format!("{} not found (tried {})", name, tried.join(", "))))
+    }
+  }
+}
+
+fn read_u16(h: &[u8], at: usize, big_endian: bool) -> Option<u16> {
+  let b: [u8; 2] = h.get(at..at + 2)?.try_into().ok()?;
+  Some(if big_endian { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) })
+}
+
+fn read_u32_le(h: &[u8], at: usize) -> Option<u32> {
+  Some(u32::from_le_bytes(h.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Format and CPU architecture (std::env::consts::ARCH names) from the executable header. None for
+/// anything else (scripts, universal Mach-O, unknown machines), which is not checked.
+fn binary_target(path: &Path) -> Option<(Format, &'static str)> {
+  let mut h = Vec::new();
+  std::fs::File::open(path).ok()?.take(HEADER_LEN).read_to_end(&mut h).ok()?;
+  if h.starts_with(b"MZ") {
+    let pe = read_u32_le(&h, 0x3c)? as usize;
+    if h.get(pe..pe + 4)? != b"PE\0\0" {
+      return None;
+    }
+    let arch = match read_u16(&h, pe + 4, false)? {
+      0x8664 => "x86_64",
+      0xaa64 => "aarch64",
+      0x014c => "x86",
+      _ => return None,
+    };
+    return Some((Format::Pe, arch));
+  }
+  if h.starts_with(b"\x7fELF") {
+    let arch = match read_u16(&h, 18, *h.get(5)? == 2)? {
+      62 => "x86_64",
+      183 => "aarch64",
+      3 => "x86",
+      _ => return None,
+    };
+    return Some((Format::Elf, arch));
+  }
+  if h.starts_with(&[0xcf, 0xfa, 0xed, 0xfe]) {
+    let arch = match read_u32_le(&h, 4)? {
+      0x0100_0007 => "x86_64",
+      0x0100_000c => "aarch64",
+      _ => return None,
+    };
+    return Some((Format::MachO, arch));
+  }
+  None
+}
+
+/// This process's arch, plus x86_64 on ARM64 Windows and macOS (runs emulated).
+fn runnable_arch(arch: &str) -> bool {
+  let host = std::env::consts::ARCH;
+  arch == host || (host == "aarch64" && arch == "x86_64" && cfg!(any(windows, target_os = "macos")))
+}
+
+/// Err(WrongArch) when `exe` is a binary this host cannot run.
+pub fn check_arch(exe: &Path) -> Result<(), AppError> {
+  let Some((format, arch)) = binary_target(exe) else {
+    return Ok(());
+  };
+  if format == Format::native() && runnable_arch(arch) {
+    return Ok(());
+  }
+  Err(AppError::WrongArch(format!(
+    "{} is a {} {} binary; this app runs as {} {}",
+    exe.display(),
+    format.label(),
+    arch,
+    Format::native().label(),
+    std::env::consts::ARCH
+  )))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Minimal PE: "MZ", e_lfanew at 0x3c pointing at "PE\0\0" + machine.
+  fn pe(machine: u16) -> Vec<u8> {
+    let mut h = vec![0u8; 0x80];
+    h[..2].copy_from_slice(b"MZ");
+    h[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+    h[0x40..0x44].copy_from_slice(b"PE\0\0");
+    h[0x44..0x46].copy_from_slice(&machine.to_le_bytes());
+    h
+  }
+
+  /// Minimal ELF: magic, EI_DATA at 5, e_machine at 18 in that byte order.
+  fn elf(machine: u16, big_endian: bool) -> Vec<u8> {
+    let mut h = vec![0u8; 64];
+    h[..4].copy_from_slice(b"\x7fELF");
+    h[4] = 2;
+    h[5] = if big_endian { 2 } else { 1 };
+    let m = if big_endian { machine.to_be_bytes() } else { machine.to_le_bytes() };
+    h[18..20].copy_from_slice(&m);
+    h
+  }
+
+  fn target_of(name: &str, bytes: &[u8]) -> Option<(Format, &'static str)> {
+    let dir = std::env::temp_dir().join(format!("ai-mentor-sidecar-tests-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, bytes).unwrap();
+    let target = binary_target(&path);
+    let _ = std::fs::remove_file(&path);
+    target
+  }
+
+  #[test]
+  fn pe_machines() {
+    assert_eq!(target_of("x64.exe", &pe(0x8664)), Some((Format::Pe, "x86_64")));
+    assert_eq!(target_of("arm64.exe", &pe(0xaa64)), Some((Format::Pe, "aarch64")));
+    assert_eq!(target_of("x86.exe", &pe(0x014c)), Some((Format::Pe, "x86")));
+    assert_eq!(target_of("ia64.exe", &pe(0x0200)), None);
+  }
+
+  #[test]
+  fn pe_without_signature_is_unknown() {
+    let mut h = pe(0x8664);
+    h[0x40..0x44].copy_from_slice(b"NE\0\0");
+    assert_eq!(target_of("dos.exe", &h), None);
+    // e_lfanew beyond the file.
+    let mut h = pe(0x8664);
+    h[0x3c..0x40].copy_from_slice(&0x1000u32.to_le_bytes());
+    assert_eq!(target_of("short.exe", &h), None);
+  }
+
+  #[test]
+  fn elf_machines_in_both_byte_orders() {
+    assert_eq!(target_of("x64", &elf(62, false)), Some((Format::Elf, "x86_64")));
+    assert_eq!(target_of("arm64", &elf(183, false)), Some((Format::Elf, "aarch64")));
+    assert_eq!(target_of("x86", &elf(3, false)), Some((Format::Elf, "x86")));
+    assert_eq!(target_of("arm64-be", &elf(183, true)), Some((Format::Elf, "aarch64")));
+    assert_eq!(target_of("riscv", &elf(243, false)), None);
+  }
+
+  #[test]
+  fn scripts_and_missing_files_are_unknown() {
+    assert_eq!(target_of("launch_backend.cmd", b"@echo off\r\n"), None);
+    assert_eq!(target_of("empty", b""), None);
+    assert_eq!(binary_target(Path::new("/nonexistent/ai-mentor-backend")), None);
+  }
+}
@@ -0,0 +1,63 @@
+// Splash window shown while the backend boots. The main window starts hidden (tauri.conf.json); the
+// splash (public/splash.html, no IPC) shows the build id and live status pushed in via eval. The first
+// READY, DEGRADED or NOT_READY swaps to the main window, which carries the retry UI. Started with
+// --minimized (launch at login) and a tray icon to come back from, neither is shown.
+
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::StatusChange;
+
+pub const SPLASH_WINDOW_LABEL: &str = "splash";
+const SPLASH_PAGE: &str = "splash.html";
+
+fn start_hidden(app: &tauri::AppHandle) -> bool {
+  crate::cli::args().minimized && crate::tray::exists(app)
+}
+
+/// Open the splash; on failure the main window is shown right away.
+pub(crate) fn show(app: &tauri::AppHandle) {
+  if start_hidden(app) {
+    return;
+  }
+  let init = format!(
+    "window.__SPLASH__ = {};",
+    serde_json::json!({ "build": std::env!("BUILD_ID"), "status": "STARTING" })
+  );
+  let built = WebviewWindowBuilder::new(app, SPLASH_WINDOW_LABEL, WebviewUrl::App(SPLASH_PAGE.into()))
+    .title("AI Μέντορας")
+    .inner_size(420.0, 260.0)
+    .resizable(false)
+    .decorations(false)
+    .center()
+    .initialization_script(&init)
+    .build();
+  if let Err(e) = built {
+    crate::app_log(&format!("splash: could not open ({}), showing main window", e));
+    show_main(app);
+  }
+}
+
+/// Startup only: the tray and hotkey bring the window back via hotkey::summon.
+pub(crate) fn show_main(app: &tauri::AppHandle) {
+  if start_hidden(app) {
+    return;
+  }
+  if let Some(main) = app.get_webview_window(crate::MAIN_WINDOW_LABEL) {
+    let _ = main.show();
+    let _ = main.set_focus();
+  }
+}
+
+/// Status listener: update the splash while STARTING, swap to the main window on the first outcome.
+pub(crate) fn on_status(app: &tauri::AppHandle, change: &StatusChange) {
+  let Some(splash) = app.get_webview_window(SPLASH_WINDOW_LABEL) else {
+    return;
+  };
+  if change.status == "STARTING" {
+    let args = serde_json::json!([change.status, change.reason]);
+    let _ = splash.eval(format!("window.setStatus && window.setStatus.apply(null, {})", args));
+    return;
+  }
+  show_main(app);
+  let _ = splash.close();
+}
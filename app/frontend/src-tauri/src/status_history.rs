@@ -0,0 +1,80 @@
+// Status transition timeline. Every change of a service's status or reason is kept in a bounded ring
+// on its BackendState and appended to logs/status_history.jsonl (tagged with the service) by the
+// status event thread, so flapping can be read off one file instead of three logs.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::AppError;
+
+const STATUS_HISTORY_MAX: usize = 200;
+const HISTORY_FILE_NAME: &str = "status_history.jsonl";
+/// The file is rotated to status_history.jsonl.1 past this size.
+const HISTORY_FILE_MAX_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusTransition {
+  /// Unix milliseconds.
+  pub at_ms: u64,
+  pub from: String,
+  pub to: String,
+  pub reason: Option<String>,
+  /// Attempt generation the change happened in (see BackendStateInner::generation).
+  pub attempt: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PersistedTransition<'a> {
+  service: &'a str,
+  #[serde(flatten)]
+  transition: &'a StatusTransition,
+}
+
+pub(crate) fn history_path() -> PathBuf {
+  crate::logs_dir().join(HISTORY_FILE_NAME)
+}
+
+pub(crate) fn now_ms() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as u64)
+    .unwrap_or(0)
+}
+
+pub(crate) fn push(history: &mut VecDeque<StatusTransition>, transition: StatusTransition) {
+  if history.len() >= STATUS_HISTORY_MAX {
+    history.pop_front();
+  }
+  history.push_back(transition);
+}
+
+/// Append one transition to the JSONL file. Called off the state lock.
+pub(crate) fn persist(service: &str, transition: &StatusTransition) {
+  let path = history_path();
+  if std::fs::metadata(&path).is_ok_and(|m| m.len() >= HISTORY_FILE_MAX_BYTES) {
+    let _ = std::fs::rename(&path, path.with_extension("jsonl.1"));
+  }
+  let Some(mut f) = crate::open_append_log(&path) else {
+    return;
+  };
+  if let Ok(line) = serde_json::to_string(&PersistedTransition { service, transition }) {
+    let _ = writeln!(f, "{}", line);
+  }
+}
+
+/// Most recent transitions of `service` (default: backend) this session, oldest first; `limit`
+/// keeps only the last N.
+#[tauri::command]
+pub fn get_status_history(
+  registry: tauri::State<crate::services::ServiceRegistry>,
+  limit: Option<usize>,
+  service: Option<String>,
+) -> Result<Vec<StatusTransition>, AppError> {
+  let state = &registry.get(service.as_deref())?.state;
+  let g = state.inner.lock().map_err(|e| e.to_string())?;
+  let skip = limit.map(|n| g.status_history.len().saturating_sub(n)).unwrap_or(0);
+  Ok(g.status_history.iter().skip(skip).cloned().collect())
+}
@@ -0,0 +1,267 @@
+// Opt-in local telemetry. With settings.telemetry_enabled on, startup duration, status transitions and
+// command invocations are appended to logs/telemetry.jsonl; nothing leaves the machine unless the user
+// exports the file. get_telemetry_summary answers the usual questions (how often does autostart fail,
+// how long does health take, how often is retry used) from that file.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::error::AppError;
+use crate::settings::{Settings, SettingsState};
+use crate::status_history::StatusTransition;
+
+const TELEMETRY_FILE_NAME: &str = "telemetry.jsonl";
+/// The file is rotated to telemetry.jsonl.1 past this size.
+const TELEMETRY_FILE_MAX_BYTES: u64 = 2 * 1024 * 1024;
+const RETRY_COMMANDS: &[&str] = &["retry_backend_start", "kill_backend_and_retry"];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STARTED: OnceLock<Instant> = OnceLock::new();
+static STARTUP_RECORDED: AtomicBool = AtomicBool::new(false);
+/// When each service last entered STARTING (Unix ms).
+static STARTING_SINCE: Mutex<Option<HashMap<String, u64>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+  /// App setup until the backend's first outcome.
+  Startup { ms: u64, status: String },
+  Status {
+    service: String,
+    from: String,
+    to: String,
+    reason: Option<String>,
+    /// How long the service was STARTING, when this transition leaves STARTING.
+    starting_ms: Option<u64>,
+  },
+  Command { name: String, ms: u64, ok: bool },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Record {
+  at_ms: u64,
+  #[serde(flatten)]
+  event: TelemetryEvent,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DurationStats {
+  pub count: usize,
+  pub avg_ms: u64,
+  pub p50_ms: u64,
+  pub p95_ms: u64,
+  pub max_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CommandSummary {
+  pub calls: u64,
+  pub failures: u64,
+  pub avg_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TelemetrySummary {
+  pub enabled: bool,
+  pub path: PathBuf,
+  pub events: usize,
+  /// Unix ms of the oldest event on file.
+  pub since_ms: Option<u64>,
+  pub startup: Option<DurationStats>,
+  /// Backend start attempts (transitions out of STARTING) and those that did not reach READY.
+  pub start_attempts: u64,
+  pub start_failures: u64,
+  /// Time from STARTING to the first healthy answer, successful attempts only.
+  pub health_wait: Option<DurationStats>,
+  /// retry_backend_start and kill_backend_and_retry calls.
+  pub retries: u64,
+  pub commands: BTreeMap<String, CommandSummary>,
+}
+
+pub(crate) fn telemetry_path() -> PathBuf {
+  crate::logs_dir().join(TELEMETRY_FILE_NAME)
+}
+
+fn rotated_path() -> PathBuf {
+  telemetry_path().with_extension("jsonl.1")
+}
+
+fn append(event: TelemetryEvent) {
+  if !ENABLED.load(Ordering::Relaxed) {
+    return;
+  }
+  let path = telemetry_path();
+  if std::fs::metadata(&path).is_ok_and(|m| m.len() >= TELEMETRY_FILE_MAX_BYTES) {
+    let _ = std::fs::rename(&path, rotated_path());
+  }
+  let Some(mut f) = crate::open_append_log(&path) else {
+    return;
+  };
+  let record = Record {
+    at_ms: crate::status_history::now_ms(),
+    event,
+  };
+  if let Ok(line) = serde_json::to_string(&record) {
+    let _ = writeln!(f, "{}", line);
+  }
+}
+
+/// Called by command_stats for every finished command.
+pub(crate) fn record_command(name: &str, ms: u64, ok: bool) {
+  append(TelemetryEvent::Command {
+    name: name.to_string(),
+    ms,
+    ok,
+  });
+}
+
+/// Called by the status event thread for every transition of `service`.
+pub(crate) fn record_transition(service: &str, t: &StatusTransition) {
+  if !ENABLED.load(Ordering::Relaxed) {
+    return;
+  }
+  let starting_ms = STARTING_SINCE.lock().ok().and_then(|mut g| {
+    let since = g.get_or_insert_with(HashMap::new);
+    if t.to == "STARTING" {
+      since.insert(service.to_string(), t.at_ms);
+    }
+    if t.from == "STARTING" {
+      since.remove(service).map(|s| t.at_ms.saturating_sub(s))
+    } else {
+      None
+    }
+  });
+  append(TelemetryEvent::Status {
+    service: service.to_string(),
+    from: t.from.clone(),
+    to: t.to.clone(),
+    reason: t.reason.clone(),
+    starting_ms,
+  });
+  let outcome = t.to != "STARTING";
+  if service == crate::services::BACKEND_SERVICE_NAME && outcome && !STARTUP_RECORDED.swap(true, Ordering::Relaxed) {
+    if let Some(started) = STARTED.get() {
+      append(TelemetryEvent::Startup {
+        ms: started.elapsed().as_millis() as u64,
+        status: t.to.clone(),
+      });
+    }
+  }
+}
+
+pub fn init(settings: &Settings) {
+  let _ = STARTED.set(Instant::now());
+  ENABLED.store(settings.telemetry_enabled, Ordering::Relaxed);
+}
+
+fn read_records() -> Vec<Record> {
+  [rotated_path(), telemetry_path()]
+    .iter()
+    .filter_map(|p| std::fs::read_to_string(p).ok())
+    .flat_map(|text| {
+      text
+        .lines()
+        .filter_map(|l| serde_json::from_str::<Record>(l).ok())
+        .collect::<Vec<_>>()
+    })
+    .collect()
+}
+
+fn duration_stats(mut samples: Vec<u64>) -> Option<DurationStats> {
+  if samples.is_empty() {
+    return None;
+  }
+  samples.sort_unstable();
+  let pct = |p: usize| samples[(samples.len() - 1) * p / 100];
+  Some(DurationStats {
+    count: samples.len(),
+    avg_ms: samples.iter().sum::<u64>() / samples.len() as u64,
+    p50_ms: pct(50),
+    p95_ms: pct(95),
+    max_ms: samples[samples.len() - 1],
+  })
+}
+
+fn summarize(records: &[Record]) -> TelemetrySummary {
+  let mut summary = TelemetrySummary {
+    enabled: ENABLED.load(Ordering::Relaxed),
+    path: telemetry_path(),
+    events: records.len(),
+    since_ms: records.iter().map(|r| r.at_ms).min(),
+    ..Default::default()
+  };
+  let mut startups = Vec::new();
+  let mut health = Vec::new();
+  let mut command_ms: HashMap<&str, u64> = HashMap::new();
+  for record in records {
+    match &record.event {
+      TelemetryEvent::Startup { ms, .. } => startups.push(*ms),
+      TelemetryEvent::Status {
+        service,
+        from,
+        to,
+        starting_ms,
+        ..
+      } if service == crate::services::BACKEND_SERVICE_NAME && from == "STARTING" => {
+        summary.start_attempts += 1;
+        if crate::is_up(to) {
+          health.extend(*starting_ms);
+        } else {
+          summary.start_failures += 1;
+        }
+      }
+      TelemetryEvent::Status { .. } => {}
+      TelemetryEvent::Command { name, ms, ok } => {
+        if RETRY_COMMANDS.contains(&name.as_str()) {
+          summary.retries += 1;
+        }
+        let c = summary.commands.entry(name.clone()).or_default();
+        c.calls += 1;
+        if !ok {
+          c.failures += 1;
+        }
+        *command_ms.entry(name.as_str()).or_default() += ms;
+      }
+    }
+  }
+  for (name, c) in summary.commands.iter_mut() {
+    c.avg_ms = command_ms.get(name.as_str()).copied().unwrap_or(0) / c.calls.max(1);
+  }
+  summary.startup = duration_stats(startups);
+  summary.health_wait = duration_stats(health);
+  summary
+}
+
+#[tauri::command]
+pub fn get_telemetry_summary() -> TelemetrySummary {
+  summarize(&read_records())
+}
+
+/// Turn local telemetry on or off. Turning it off keeps the file; export or delete it as you like.
+#[tauri::command]
+pub fn set_telemetry_enabled(settings: tauri::State<SettingsState>, enabled: bool) -> Result<bool, AppError> {
+  settings.update(|s| s.telemetry_enabled = enabled)?;
+  ENABLED.store(enabled, Ordering::Relaxed);
+  crate::app_log(&format!("telemetry: {}", if enabled { "enabled" } else { "disabled" }));
+  Ok(enabled)
+}
+
+/// Write all recorded events (oldest first, rotated file included) to `dest` as JSONL.
+/// Returns the number of events written.
+#[tauri::command]
+pub fn export_telemetry(dest: String) -> Result<usize, AppError> {
+  let records = read_records();
+  let mut out = String::new();
+  for record in &records {
+    out.push_str(&serde_json::to_string(record).map_err(|e| e.to_string())?);
+    out.push('\n');
+  }
+  std::fs::write(&dest, out).map_err(|e| format!("{}: {}", dest, e))?;
+  crate::app_log(&format!("telemetry: exported {} events to {}", records.len(), dest));
+  Ok(records.len())
+}
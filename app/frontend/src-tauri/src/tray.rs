@@ -0,0 +1,43 @@
+// Tray icon: left click summons the main window, menu has Show / Quit. Quit goes through the shared
+// shutdown path so hooks run the same way as on window close.
+
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+
+const TRAY_ID: &str = "main";
+const MENU_SHOW: &str = "show";
+const MENU_QUIT: &str = "quit";
+
+pub(crate) fn exists(app: &tauri::AppHandle) -> bool {
+  app.tray_by_id(TRAY_ID).is_some()
+}
+
+pub(crate) fn create(app: &tauri::AppHandle) -> tauri::Result<()> {
+  let show = MenuItem::with_id(app, MENU_SHOW, "Show AI Mentor", true, None::<&str>)?;
+  let quit = MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)?;
+  let menu = Menu::with_items(app, &[&show, &quit])?;
+  let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+    .tooltip("AI Mentor")
+    .menu(&menu)
+    .show_menu_on_left_click(false)
+    .on_menu_event(|app, event| match event.id.as_ref() {
+      MENU_SHOW => crate::hotkey::summon(app),
+      MENU_QUIT => crate::shutdown::exit_app(app, "tray_quit", 0),
+      _ => {}
+    })
+    .on_tray_icon_event(|tray, event| {
+      if let TrayIconEvent::Click {
+        button: MouseButton::Left,
+        button_state: MouseButtonState::Up,
+        ..
+      } = event
+      {
+        crate::hotkey::summon(tray.app_handle());
+      }
+    });
+  if let Some(icon) = app.default_window_icon() {
+    builder = builder.icon(icon.clone());
+  }
+  builder.build(app)?;
+  Ok(())
+}
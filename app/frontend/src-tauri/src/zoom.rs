@@ -0,0 +1,80 @@
+// Webview zoom: factor applied natively (WebView2 zoom on Windows), persisted per window label,
+// and Ctrl+scroll forwarded from the page to set_zoom_level.
+
+use crate::error::AppError;
+use crate::settings::SettingsState;
+use tauri::Manager;
+
+pub const ZOOM_MIN: f64 = 0.5;
+pub const ZOOM_MAX: f64 = 3.0;
+const ZOOM_DEFAULT: f64 = 1.0;
+
+/// Ctrl+wheel listener injected on page load; the built-in zoom hotkeys stay disabled so this is the only path.
+const CTRL_WHEEL_SCRIPT: &str = r#"
+(function () {
+  window.__AI_MENTOR_ZOOM__ = __ZOOM__;
+  if (window.__AI_MENTOR_ZOOM_HOOK__) return;
+  window.__AI_MENTOR_ZOOM_HOOK__ = true;
+  window.addEventListener('wheel', function (e) {
+    if (!e.ctrlKey) return;
+    e.preventDefault();
+    var step = e.deltaY < 0 ? 0.1 : -0.1;
+    var next = Math.round((window.__AI_MENTOR_ZOOM__ + step) * 100) / 100;
+    window.__TAURI_INTERNALS__.invoke('set_zoom_level', { factor: next });
+  }, { passive: false });
+})();
+"#;
+
+fn clamp_zoom(factor: f64) -> f64 {
+  if !factor.is_finite() {
+    return ZOOM_DEFAULT;
+  }
+  factor.clamp(ZOOM_MIN, ZOOM_MAX)
+}
+
+fn saved_zoom(app: &tauri::AppHandle, label: &str) -> f64 {
+  app
+    .try_state::<SettingsState>()
+    .and_then(|s| s.get().window_zoom.get(label).copied())
+    .map(clamp_zoom)
+    .unwrap_or(ZOOM_DEFAULT)
+}
+
+fn apply_zoom(webview: &tauri::Webview, factor: f64) {
+  if let Err(e) = webview.set_zoom(factor) {
+    crate::app_log(&format!("zoom: set_zoom({}) failed for {}: {}", factor, webview.label(), e));
+  }
+  let _ = webview.eval(format!("window.__AI_MENTOR_ZOOM__ = {};", factor));
+}
+
+/// Re-apply each open webview's saved factor, e.g. after settings.json changed on disk.
+pub(crate) fn apply_saved(app: &tauri::AppHandle) {
+  for window in app.webview_windows().values() {
+    apply_zoom(window.as_ref(), saved_zoom(app, window.label()));
+  }
+}
+
+/// Called from the builder's page-load hook: restore the saved factor and (re)install the Ctrl+wheel listener.
+pub fn on_page_load(webview: &tauri::Webview) {
+  let factor = saved_zoom(webview.app_handle(), webview.label());
+  apply_zoom(webview, factor);
+  let _ = webview.eval(CTRL_WHEEL_SCRIPT.replace("__ZOOM__", &factor.to_string()));
+}
+
+/// Set the calling window's zoom factor (clamped to 0.5..=3.0) and persist it. Returns the applied factor.
+#[tauri::command]
+pub fn set_zoom_level(webview: tauri::Webview, settings: tauri::State<SettingsState>, factor: f64) -> Result<f64, AppError> {
+  let factor = clamp_zoom(factor);
+  apply_zoom(&webview, factor);
+  let label = webview.label().to_string();
+  settings.update(|s| {
+    s.window_zoom.insert(label, factor);
+  })?;
+  Ok(factor)
+}
+
+/// Saved zoom factor for the calling window (1.0 when never set).
+#[tauri::command]
+pub fn get_zoom_level(webview: tauri::Webview) -> f64 {
+  saved_zoom(webview.app_handle(), webview.label())
+}
@@ -0,0 +1,40 @@
+/**
+ * Frontend/shell build handshake. The shell compares our __BUILD_ID__ with the bundle it was built with;
+ * on mismatch (stale webview cache, partial update) it clears browsing data and reloads once.
+ * localStorage is handed to the shell before the clear and restored by the next handshake.
+ */
+import { isTauri } from "./api/backendBaseUrl";
+
+type HandshakeResult = {
+  ok: boolean;
+  expected: string;
+  reported: string;
+  reload_required: boolean;
+  restore: Record<string, string> | null;
+};
+
+function snapshotLocalStorage(): Record<string, string> {
+  const out: Record<string, string> = {};
+  for (let i = 0; i < localStorage.length; i++) {
+    const key = localStorage.key(i);
+    if (key !== null) out[key] = localStorage.getItem(key) ?? "";
+  }
+  return out;
+}
+
+/** Resolves false when the shell is reloading the webview (caller should not continue startup). */
+export async function frontendHandshake(): Promise<boolean> {
+  if (!isTauri()) return true;
+  try {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const res = await invoke<HandshakeResult>("frontend_handshake", { buildId: __BUILD_ID__ });
+    if (res.restore) {
+      for (const [key, value] of Object.entries(res.restore)) localStorage.setItem(key, value);
+    }
+    if (!res.reload_required) return true;
+    await invoke("frontend_clear_cache_and_reload", { localStorage: snapshotLocalStorage() });
+    return false;
+  } catch {
+    return true;
+  }
+}
@@ -1,10 +1,11 @@
-import { useState, useEffect } from 'react';
+import { useState, useEffect, useRef } from 'react';
 import { Button } from '@/components/ui/button';
 import { Textarea } from '@/components/ui/textarea';
 import { Send, Mic, Globe } from 'lucide-react';
 import { useSpeechRecognition } from '@/hooks/useSpeechRecognition';
 import { toast } from 'sonner';
 import { cn } from '@/lib/utils';
+import { isTauri } from '@/api/backendBaseUrl';
 
 interface MessageInputProps {
   onSendMessage: (content: string, useOnline: boolean) => void;
@@ -14,6 +15,7 @@ interface MessageInputProps {
 export function MessageInput({ onSendMessage, isLoading }: MessageInputProps) {
   const [message, setMessage] = useState('');
   const [useOnline, setUseOnline] = useState(false);
+  const inputRef = useRef<HTMLTextAreaElement>(null);
   const {
     isListening,
     transcript,
@@ -30,6 +32,23 @@ export function MessageInput({ onSendMessage, isLoading }: MessageInputProps) {
     }
   }, [transcript, resetTranscript]);
 
+  // Global hotkey (see src-tauri/src/hotkey.rs) brings the window forward: put the cursor in the prompt.
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    import('@tauri-apps/api/event').then(({ listen }) =>
+      listen('mentor-summoned', () => inputRef.current?.focus()).then((fn) => {
+        if (cancelled) fn();
+        else unlisten = fn;
+      })
+    );
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
   const handleSubmit = () => {
     if (!message.trim() || isLoading) return;
 
@@ -64,6 +83,7 @@ export function MessageInput({ onSendMessage, isLoading }: MessageInputProps) {
       <div className="flex items-end gap-2">
         <div className="flex-1">
           <Textarea
+            ref={inputRef}
             value={message}
             onChange={(e) => setMessage(e.target.value)}
             onKeyDown={handleKeyDown}
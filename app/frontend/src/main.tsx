@@ -1,7 +1,9 @@
 import { createRoot } from "react-dom/client";
 import { useEffect, useState } from "react";
 import App from "./App";
+import { ChatInterface } from "./components/ChatInterface";
 import { getBackendBaseUrl, isTauri } from "./api/backendBaseUrl";
+import { frontendHandshake } from "./buildCheck";
 import "./index.css";
 
 function Root() {
@@ -11,7 +13,9 @@ function Root() {
       setReady(true);
       return;
     }
-    getBackendBaseUrl().then(() => setReady(true));
+    frontendHandshake().then((ok) => {
+      if (ok) getBackendBaseUrl().then(() => setReady(true));
+    });
   }, []);
   if (!ready) {
     return (
@@ -20,6 +24,10 @@ function Root() {
       </div>
     );
   }
+  // Chat windows opened via open_chat_window render only their conversation.
+  if (window.__CHAT_SESSION__) {
+    return <ChatInterface conversationId={Number(window.__CHAT_SESSION__) || null} />;
+  }
   return <App />;
 }
 
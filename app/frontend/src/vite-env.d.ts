@@ -2,4 +2,10 @@
 
 declare const __BUILD__: string
 declare const __COMMIT__: string
+declare const __BUILD_ID__: string
 declare const __APP_VERSION__: string
+
+interface Window {
+  /** Set by the desktop shell in chat windows (open_chat_window). */
+  __CHAT_SESSION__?: string
+}
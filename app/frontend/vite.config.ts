@@ -2,18 +2,31 @@ import path from "path"
 import { readFileSync } from "fs"
 import { fileURLToPath } from "url"
 import react from "@vitejs/plugin-react"
-import { defineConfig } from "vite"
+import { defineConfig, type Plugin } from "vite"
 
 const __dirname = path.dirname(fileURLToPath(import.meta.url))
 const pkg = JSON.parse(
   readFileSync(path.resolve(__dirname, "package.json"), "utf-8")
 )
+const buildId = process.env.VITE_BUILD_ID ?? "dev"
+
+/** Writes dist/build-id.txt; the Tauri build embeds it as the expected frontend BUILD_ID. */
+function buildIdFile(): Plugin {
+  return {
+    name: "build-id-file",
+    apply: "build",
+    generateBundle() {
+      this.emitFile({ type: "asset", fileName: "build-id.txt", source: buildId })
+    },
+  }
+}
 
 export default defineConfig({
-  plugins: [react()],
+  plugins: [react(), buildIdFile()],
   define: {
     __BUILD__: JSON.stringify(process.env.VITE_BUILD ?? "dev"),
     __COMMIT__: JSON.stringify(process.env.VITE_COMMIT ?? "unknown"),
+    __BUILD_ID__: JSON.stringify(buildId),
     __APP_VERSION__: JSON.stringify(process.env.VITE_APP_VERSION ?? pkg.version ?? "0.0.0"),
   },
   resolve: {
@@ -4,15 +4,38 @@ from functools import lru_cache
 from pathlib import Path
 
 
+def _base_dir() -> Path:
+    """Base dir chosen by the desktop shell (AI_MENTOR_BASE_DIR: --data-dir, portable mode,
+    settings.data_dir), else %LOCALAPPDATA%\\AI_Mentor. Same rule as sidecar_entry._get_base_dir."""
+    base = os.environ.get("AI_MENTOR_BASE_DIR", "")
+    if base:
+        return Path(base)
+    local_app_data = os.environ.get("LOCALAPPDATA", "")
+    if not local_app_data:
+        local_app_data = str(Path.home())
+    return Path(local_app_data) / "AI_Mentor"
+
+
+def data_dir() -> Path:
+    """<base dir>/data: the database (when packaged or given a base dir) and stored attachments."""
+    return _base_dir() / "data"
+
+
+def models_dir() -> Path:
+    """Local model weights: AI_MENTOR_MODELS_DIR from the desktop shell, else <base dir>/models."""
+    directory = os.environ.get("AI_MENTOR_MODELS_DIR", "")
+    if directory:
+        return Path(directory)
+    return _base_dir() / "models"
+
+
 def _default_database_url() -> str:
-    """Default DB path. When packaged (AI_MENTOR_PACKAGED=1), use %LOCALAPPDATA%\\AI_Mentor\\data."""
-    if os.environ.get("AI_MENTOR_PACKAGED"):
-        local_app_data = os.environ.get("LOCALAPPDATA", "")
-        if not local_app_data:
-            local_app_data = str(Path.home())
-        data_dir = Path(local_app_data) / "AI_Mentor" / "data"
-        data_dir.mkdir(parents=True, exist_ok=True)
-        db_path = (data_dir / "ai_mentor.sqlite").resolve()
+    """Default DB path. When packaged (AI_MENTOR_PACKAGED=1) or given AI_MENTOR_BASE_DIR, use
+    <base dir>\\data\\ai_mentor.sqlite."""
+    if os.environ.get("AI_MENTOR_PACKAGED") or os.environ.get("AI_MENTOR_BASE_DIR"):
+        directory = data_dir()
+        directory.mkdir(parents=True, exist_ok=True)
+        db_path = (directory / "ai_mentor.sqlite").resolve()
         return f"sqlite+aiosqlite:///{db_path.as_posix()}"
     return "sqlite+aiosqlite:///./app.db"
 
@@ -1,9 +1,12 @@
-"""API v1: analyze, evaluation, pipeline, runner, reports, and meta endpoints."""
+"""API v1: analyze, attachments, evaluation, pipeline, runner, reports, meta, models, and lifecycle endpoints."""
 
 from fastapi import APIRouter
 
 from .analyze import router as analyze_router
+from .attachments import router as attachments_router
 from .evaluation import router as evaluation_router
+from .lifecycle import router as lifecycle_router
+from .local_models import router as local_models_router
 from .meta import router as meta_router
 from .pipeline import router as pipeline_router
 from .reports import router as reports_router
@@ -11,7 +14,10 @@ from .runner import router as runner_router
 
 router = APIRouter(prefix="/api/v1", tags=["api_v1"])
 router.include_router(analyze_router)
+router.include_router(attachments_router)
 router.include_router(evaluation_router)
+router.include_router(lifecycle_router)
+router.include_router(local_models_router)
 router.include_router(meta_router)
 router.include_router(pipeline_router)
 router.include_router(reports_router)
@@ -0,0 +1,91 @@
+"""PUT /api/v1/sessions/{session_id}/attachments/{upload_id} — chunked file upload from the desktop shell.
+
+Each chunk carries Content-Range: bytes <start>-<end>/<total>, the file's Content-Type and its
+percent-encoded name in X-File-Name. Chunks must arrive in order and are appended to
+<data dir>/attachments/<session_id>/<upload_id>.part; after the last one the file is renamed to
+<upload_id> next to an <upload_id>.json record (name, mime, size). Earlier chunks are answered with
+the bytes received so far, the last one with the stored attachment.
+"""
+
+from __future__ import annotations
+
+import json
+import os
+import re
+from pathlib import Path
+from typing import Optional
+from urllib.parse import unquote
+
+from fastapi import APIRouter, Header, HTTPException, Request
+
+from core.config import data_dir
+
+MAX_BYTES_ENV = "AI_MENTOR_ATTACHMENT_MAX_BYTES"
+DEFAULT_MAX_BYTES = 2 * 1024 * 1024 * 1024
+
+_ID_RE = re.compile(r"^[A-Za-z0-9_-]{1,64}$")
+_RANGE_RE = re.compile(r"^bytes (\d+)-(\d+)/(\d+)$")
+
+router = APIRouter(prefix="/sessions", tags=["attachments"])
+
+
+def attachments_dir() -> Path:
+    return data_dir() / "attachments"
+
+
+def _max_bytes() -> int:
+    try:
+        return max(1, int(os.environ.get(MAX_BYTES_ENV, str(DEFAULT_MAX_BYTES))))
+    except ValueError:
+        return DEFAULT_MAX_BYTES
+
+
+@router.put("/{session_id}/attachments/{upload_id}", summary="Upload one chunk of an attachment")
+async def put_attachment_chunk(
+    session_id: str,
+    upload_id: str,
+    request: Request,
+    content_range: Optional[str] = Header(default=None),
+    content_type: Optional[str] = Header(default=None),
+    x_file_name: Optional[str] = Header(default=None),
+) -> dict:
+    """Append the chunk at its Content-Range offset; store the file once all bytes arrived."""
+    if not _ID_RE.match(session_id) or not _ID_RE.match(upload_id):
+        raise HTTPException(status_code=400, detail="invalid session or upload id")
+    match = _RANGE_RE.match(content_range or "")
+    if match is None:
+        raise HTTPException(status_code=400, detail="Content-Range: bytes <start>-<end>/<total> required")
+    start, end, total = (int(g) for g in match.groups())
+    if total > _max_bytes():
+        raise HTTPException(status_code=413, detail=f"attachment exceeds {_max_bytes()} bytes")
+    body = await request.body()
+    # An empty file arrives once as "bytes 0-0/0" without a body.
+    expected = 0 if total == 0 else end - start + 1
+    if end < start or (total and end >= total) or len(body) != expected:
+        raise HTTPException(status_code=400, detail="chunk does not match its Content-Range")
+
+    directory = attachments_dir() / session_id
+    directory.mkdir(parents=True, exist_ok=True)
+    part = directory / f"{upload_id}.part"
+    stored = directory / upload_id
+    if stored.exists():
+        raise HTTPException(status_code=409, detail="upload already complete")
+    received = part.stat().st_size if part.exists() else 0
+    if start != received:
+        raise HTTPException(status_code=409, detail=f"expected the chunk at byte {received}")
+    with part.open("ab") as f:
+        f.write(body)
+    received += len(body)
+    if received < total:
+        return {"upload_id": upload_id, "received": received, "total": total}
+
+    record = {
+        "upload_id": upload_id,
+        "session_id": session_id,
+        "file_name": unquote(x_file_name or "") or upload_id,
+        "mime": content_type or "application/octet-stream",
+        "size": total,
+    }
+    os.replace(part, stored)
+    (directory / f"{upload_id}.json").write_text(json.dumps(record, ensure_ascii=False), encoding="utf-8")
+    return {"status": "stored", **record}
@@ -0,0 +1,36 @@
+"""POST /api/v1/shutdown — graceful stop requested by the desktop shell that spawned this process.
+
+The shell passes a per-session token in AI_MENTOR_SHUTDOWN_TOKEN and sends it back in the
+X-Shutdown-Token header; without a configured token (e.g. `uvicorn main:app` in development) the
+endpoint refuses. The response is sent first, then SIGINT lets uvicorn run its normal shutdown
+(on_event("shutdown"), database dispose).
+"""
+
+from __future__ import annotations
+
+import asyncio
+import hmac
+import os
+import signal
+from typing import Optional
+
+from fastapi import APIRouter, Header, HTTPException
+
+SHUTDOWN_TOKEN_ENV = "AI_MENTOR_SHUTDOWN_TOKEN"
+SHUTDOWN_DELAY_SECONDS = 0.2
+
+router = APIRouter(tags=["lifecycle"])
+
+
+def _request_exit() -> None:
+    signal.raise_signal(signal.SIGINT)
+
+
+@router.post("/shutdown", summary="Graceful shutdown (desktop shell only)")
+async def shutdown(x_shutdown_token: Optional[str] = Header(default=None)) -> dict:
+    """Stop the server after answering, if the caller knows the shell's token."""
+    expected = os.environ.get(SHUTDOWN_TOKEN_ENV, "")
+    if not expected or not x_shutdown_token or not hmac.compare_digest(x_shutdown_token, expected):
+        raise HTTPException(status_code=403, detail="shutdown not allowed")
+    asyncio.get_running_loop().call_later(SHUTDOWN_DELAY_SECONDS, _request_exit)
+    return {"status": "shutting_down"}
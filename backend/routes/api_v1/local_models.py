@@ -0,0 +1,52 @@
+"""GET /api/v1/models — local model weights installed by the desktop shell.
+
+The shell downloads weights into AI_MENTOR_MODELS_DIR and records each one in manifest.json there
+(name, file, size, sha256, source, installed_at). This lists the manifest entries, marking the ones
+whose file is gone, so the backend sees the same set of models as the app.
+"""
+
+from __future__ import annotations
+
+import json
+from pathlib import Path
+
+from fastapi import APIRouter
+
+from core.config import models_dir
+
+MANIFEST_FILE_NAME = "manifest.json"
+
+router = APIRouter(prefix="/models", tags=["models"])
+
+
+def read_manifest(directory: Path) -> list[dict]:
+    """Manifest entries with a usable name and file; [] when the manifest is missing or invalid."""
+    try:
+        data = json.loads((directory / MANIFEST_FILE_NAME).read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return []
+    entries = data.get("models") if isinstance(data, dict) else None
+    if not isinstance(entries, list):
+        return []
+    return [
+        e for e in entries
+        if isinstance(e, dict) and isinstance(e.get("name"), str) and isinstance(e.get("file"), str)
+    ]
+
+
+@router.get("", summary="List local models from the shell's manifest")
+async def list_models() -> dict:
+    """Manifest entries with `missing` set when the weights file is not on disk."""
+    directory = models_dir()
+    models = []
+    for entry in read_manifest(directory):
+        path = directory / entry["file"]
+        models.append({
+            "name": entry["name"],
+            "file": entry["file"],
+            "path": str(path),
+            "size": entry.get("size"),
+            "sha256": entry.get("sha256"),
+            "missing": not path.is_file(),
+        })
+    return {"models_dir": str(directory), "models": models}
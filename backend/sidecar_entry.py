@@ -1,8 +1,9 @@
 """
-Sidecar entrypoint for desktop build: fixed port 8000, no console.
+Sidecar entrypoint for desktop build: port 8000 unless AI_MENTOR_PORT says otherwise, no console.
 
 - Used only when packaging the backend as Tauri sidecar (PyInstaller).
-- Imports the existing FastAPI app from main; runs uvicorn on 127.0.0.1:8000.
+- Imports the existing FastAPI app from main; runs uvicorn on 127.0.0.1:<port> (the shell sets
+  AI_MENTOR_PORT for --port and loopback profiles on other ports).
 - Writes backend_port.json so Tauri can read base_url for health checks.
 - Logs/markers to %LOCALAPPDATA%\\AI Mentor\\backend\\ (sidecar_started.txt, sidecar_crash.log, backend.log).
 
@@ -32,7 +33,20 @@ if not getattr(sys, "frozen", False):
     if str(_backend_dir) not in sys.path:
         sys.path.insert(0, str(_backend_dir))
 
-SIDECAR_PORT = 8000
+DEFAULT_SIDECAR_PORT = 8000
+
+
+def _get_port() -> int:
+    """Port from AI_MENTOR_PORT if it is a valid TCP port, else DEFAULT_SIDECAR_PORT."""
+    raw = os.environ.get("AI_MENTOR_PORT", "").strip()
+    try:
+        port = int(raw)
+    except ValueError:
+        return DEFAULT_SIDECAR_PORT
+    return port if 0 < port < 65536 else DEFAULT_SIDECAR_PORT
+
+
+SIDECAR_PORT = _get_port()
 
 
 def _get_base_dir() -> Path:
@@ -60,7 +74,7 @@ try:
     base_dir_str = str(BASE_DIR)
     with open(backend_log, "a", encoding="utf-8") as f:
         f.write(
-            f"BACKEND_PROCESS_START pid={os.getpid()} port=8000 base_dir={base_dir_str}\n"
+            f"BACKEND_PROCESS_START pid={os.getpid()} port={SIDECAR_PORT} base_dir={base_dir_str}\n"
         )
         f.flush()
     (LOG_DIR / "sidecar_started.txt").write_text(
@@ -148,7 +162,7 @@ def main() -> int:
         logger = logging.getLogger(__name__)
         backend_log = LOG_DIR / "backend.log"
         with open(backend_log, "a", encoding="utf-8") as f:
-            f.write("Uvicorn running on http://127.0.0.1:8000\n")
+            f.write(f"Uvicorn running on http://127.0.0.1:{SIDECAR_PORT}\n")
             f.flush()
         logger.info(
             "BACKEND_START __file__=%s host=127.0.0.1 port=%s CORS_allow_origins=[http://tauri.localhost,...]",
@@ -160,7 +174,7 @@ def main() -> int:
         uvicorn.run(
             app,
             host="127.0.0.1",
-            port=8000,
+            port=SIDECAR_PORT,
             log_config=custom_plain_log_config(),
             access_log=False,
         )
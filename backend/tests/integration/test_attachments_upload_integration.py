@@ -0,0 +1,88 @@
+"""
+Integration test: chunked attachment upload (PUT /api/v1/sessions/<session>/attachments/<upload_id>)
+as the desktop shell sends it.
+"""
+
+from __future__ import annotations
+
+import json
+import sys
+from pathlib import Path
+
+_backend = Path(__file__).resolve().parent.parent.parent
+if str(_backend) not in sys.path:
+    sys.path.insert(0, str(_backend))
+
+import pytest
+from fastapi.testclient import TestClient
+
+from main import app
+from routes.api_v1 import attachments
+
+URL = "/api/v1/sessions/s-1/attachments/abc123"
+
+
+@pytest.fixture
+def base_dir(monkeypatch: pytest.MonkeyPatch, tmp_path: Path) -> Path:
+    monkeypatch.setenv("AI_MENTOR_BASE_DIR", str(tmp_path))
+    monkeypatch.delenv(attachments.MAX_BYTES_ENV, raising=False)
+    return tmp_path
+
+
+def _put(client: TestClient, body: bytes, start: int, total: int, url: str = URL):
+    end = max(start + len(body) - 1, 0)
+    headers = {
+        "Content-Range": f"bytes {start}-{end}/{total}",
+        "Content-Type": "text/plain",
+        "X-File-Name": "notes%20%CE%B1.txt",
+    }
+    return client.put(url, content=body, headers=headers)
+
+
+def test_chunks_are_assembled_and_recorded(base_dir: Path) -> None:
+    client = TestClient(app)
+    first = _put(client, b"hello ", 0, 11)
+    assert first.status_code == 200
+    assert first.json() == {"upload_id": "abc123", "received": 6, "total": 11}
+    last = _put(client, b"world", 6, 11)
+    assert last.status_code == 200
+    assert last.json()["status"] == "stored"
+    assert last.json()["file_name"] == "notes α.txt"
+    stored = base_dir / "data" / "attachments" / "s-1"
+    assert (stored / "abc123").read_bytes() == b"hello world"
+    assert not (stored / "abc123.part").exists()
+    record = json.loads((stored / "abc123.json").read_text(encoding="utf-8"))
+    assert record == {"upload_id": "abc123", "session_id": "s-1", "file_name": "notes α.txt", "mime": "text/plain", "size": 11}
+
+
+def test_out_of_order_chunk_is_rejected(base_dir: Path) -> None:
+    client = TestClient(app)
+    assert _put(client, b"world", 6, 11).status_code == 409
+    assert _put(client, b"hello ", 0, 11).status_code == 200
+    assert _put(client, b"hello ", 0, 11).status_code == 409
+
+
+def test_empty_file_is_stored(base_dir: Path) -> None:
+    resp = _put(TestClient(app), b"", 0, 0)
+    assert resp.status_code == 200
+    assert resp.json()["size"] == 0
+    assert (base_dir / "data" / "attachments" / "s-1" / "abc123").read_bytes() == b""
+
+
+def test_completed_upload_is_not_reopened(base_dir: Path) -> None:
+    client = TestClient(app)
+    assert _put(client, b"abc", 0, 3).status_code == 200
+    assert _put(client, b"abc", 0, 3).status_code == 409
+
+
+def test_bad_requests(base_dir: Path) -> None:
+    client = TestClient(app)
+    assert _put(client, b"x", 0, 1, url="/api/v1/sessions/s.1/attachments/abc123").status_code == 400
+    assert client.put(URL, content=b"x").status_code == 400
+    # Body shorter than the declared range.
+    assert client.put(URL, content=b"x", headers={"Content-Range": "bytes 0-4/10"}).status_code == 400
+
+
+def test_size_limit(base_dir: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.setenv(attachments.MAX_BYTES_ENV, "4")
+    assert _put(TestClient(app), b"hello", 0, 5).status_code == 413
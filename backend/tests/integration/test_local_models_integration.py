@@ -0,0 +1,76 @@
+"""
+Integration test: GET /api/v1/models lists the manifest the desktop shell writes to
+AI_MENTOR_MODELS_DIR.
+"""
+
+from __future__ import annotations
+
+import json
+import sys
+from pathlib import Path
+
+_backend = Path(__file__).resolve().parent.parent.parent
+if str(_backend) not in sys.path:
+    sys.path.insert(0, str(_backend))
+
+import pytest
+from fastapi.testclient import TestClient
+
+from main import app
+
+URL = "/api/v1/models"
+
+
+@pytest.fixture
+def models_dir(monkeypatch: pytest.MonkeyPatch, tmp_path: Path) -> Path:
+    directory = tmp_path / "weights"
+    directory.mkdir()
+    monkeypatch.setenv("AI_MENTOR_MODELS_DIR", str(directory))
+    return directory
+
+
+def _write_manifest(directory: Path, models: list) -> None:
+    (directory / "manifest.json").write_text(json.dumps({"models": models}), encoding="utf-8")
+
+
+def test_lists_manifest_entries_and_missing_files(models_dir: Path) -> None:
+    (models_dir / "small.gguf").write_bytes(b"weights")
+    _write_manifest(models_dir, [
+        {"name": "small", "file": "small.gguf", "size": 7, "sha256": "a" * 64, "source": None, "installed_at": 1},
+        {"name": "gone", "file": "gone.gguf", "size": 9, "sha256": "b" * 64, "source": None, "installed_at": 2},
+    ])
+    with TestClient(app) as client:
+        r = client.get(URL)
+    assert r.status_code == 200
+    body = r.json()
+    assert body["models_dir"] == str(models_dir)
+    by_name = {m["name"]: m for m in body["models"]}
+    assert by_name["small"]["missing"] is False
+    assert by_name["small"]["size"] == 7
+    assert by_name["small"]["path"] == str(models_dir / "small.gguf")
+    assert by_name["gone"]["missing"] is True
+
+
+def test_missing_manifest_lists_nothing(models_dir: Path) -> None:
+    with TestClient(app) as client:
+        r = client.get(URL)
+    assert r.status_code == 200
+    assert r.json()["models"] == []
+
+
+def test_invalid_manifest_and_entries_are_skipped(models_dir: Path) -> None:
+    (models_dir / "manifest.json").write_text("{not json", encoding="utf-8")
+    with TestClient(app) as client:
+        assert client.get(URL).json()["models"] == []
+    _write_manifest(models_dir, [{"name": "no-file"}, "junk", {"name": "ok", "file": "ok.bin"}])
+    with TestClient(app) as client:
+        names = [m["name"] for m in client.get(URL).json()["models"]]
+    assert names == ["ok"]
+
+
+def test_defaults_to_base_dir_models(monkeypatch: pytest.MonkeyPatch, tmp_path: Path) -> None:
+    monkeypatch.delenv("AI_MENTOR_MODELS_DIR", raising=False)
+    monkeypatch.setenv("AI_MENTOR_BASE_DIR", str(tmp_path))
+    with TestClient(app) as client:
+        r = client.get(URL)
+    assert r.json()["models_dir"] == str(tmp_path / "models")
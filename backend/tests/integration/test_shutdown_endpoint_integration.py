@@ -0,0 +1,46 @@
+"""
+Integration test: POST /api/v1/shutdown only answers the shell that holds the session token.
+"""
+
+from __future__ import annotations
+
+import sys
+from pathlib import Path
+
+_backend = Path(__file__).resolve().parent.parent.parent
+if str(_backend) not in sys.path:
+    sys.path.insert(0, str(_backend))
+
+import pytest
+from fastapi.testclient import TestClient
+
+from main import app
+from routes.api_v1 import lifecycle
+
+
+@pytest.fixture
+def exits(monkeypatch: pytest.MonkeyPatch) -> list:
+    """Record exit requests instead of signalling the test process."""
+    calls: list = []
+    monkeypatch.setattr(lifecycle, "_request_exit", lambda: calls.append(True))
+    return calls
+
+
+def test_shutdown_refused_without_configured_token(monkeypatch: pytest.MonkeyPatch, exits: list) -> None:
+    monkeypatch.delenv(lifecycle.SHUTDOWN_TOKEN_ENV, raising=False)
+    resp = TestClient(app).post("/api/v1/shutdown", headers={"X-Shutdown-Token": "anything"})
+    assert resp.status_code == 403
+
+
+def test_shutdown_refused_with_wrong_token(monkeypatch: pytest.MonkeyPatch, exits: list) -> None:
+    monkeypatch.setenv(lifecycle.SHUTDOWN_TOKEN_ENV, "secret")
+    client = TestClient(app)
+    assert client.post("/api/v1/shutdown").status_code == 403
+    assert client.post("/api/v1/shutdown", headers={"X-Shutdown-Token": "wrong"}).status_code == 403
+
+
+def test_shutdown_accepted_with_token(monkeypatch: pytest.MonkeyPatch, exits: list) -> None:
+    monkeypatch.setenv(lifecycle.SHUTDOWN_TOKEN_ENV, "secret")
+    resp = TestClient(app).post("/api/v1/shutdown", headers={"X-Shutdown-Token": "secret"})
+    assert resp.status_code == 200
+    assert resp.json() == {"status": "shutting_down"}
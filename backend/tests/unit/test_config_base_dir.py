@@ -0,0 +1,38 @@
+"""
+Unit tests for the default database location: AI_MENTOR_BASE_DIR from the desktop shell wins.
+"""
+
+from __future__ import annotations
+
+import sys
+from pathlib import Path
+
+_backend = Path(__file__).resolve().parent.parent.parent
+if str(_backend) not in sys.path:
+    sys.path.insert(0, str(_backend))
+
+import pytest
+
+from core.config import _default_database_url
+
+
+def test_base_dir_override_holds_database(monkeypatch: pytest.MonkeyPatch, tmp_path: Path) -> None:
+    monkeypatch.setenv("AI_MENTOR_BASE_DIR", str(tmp_path))
+    monkeypatch.delenv("AI_MENTOR_PACKAGED", raising=False)
+    expected = (tmp_path / "data" / "ai_mentor.sqlite").resolve().as_posix()
+    assert _default_database_url() == f"sqlite+aiosqlite:///{expected}"
+    assert (tmp_path / "data").is_dir()
+
+
+def test_packaged_without_override_uses_localappdata(monkeypatch: pytest.MonkeyPatch, tmp_path: Path) -> None:
+    monkeypatch.delenv("AI_MENTOR_BASE_DIR", raising=False)
+    monkeypatch.setenv("AI_MENTOR_PACKAGED", "1")
+    monkeypatch.setenv("LOCALAPPDATA", str(tmp_path))
+    expected = (tmp_path / "AI_Mentor" / "data" / "ai_mentor.sqlite").resolve().as_posix()
+    assert _default_database_url() == f"sqlite+aiosqlite:///{expected}"
+
+
+def test_development_default_is_local_file(monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.delenv("AI_MENTOR_BASE_DIR", raising=False)
+    monkeypatch.delenv("AI_MENTOR_PACKAGED", raising=False)
+    assert _default_database_url() == "sqlite+aiosqlite:///./app.db"
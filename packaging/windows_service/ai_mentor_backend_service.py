@@ -2,9 +2,13 @@
 Windows Service host for AI Mentor Backend.
 Runs as service AI_Mentor_Backend; in SvcDoRun launches ai-mentor-backend.exe
 and keeps it running. Logs to %LOCALAPPDATA%\\AI_Mentor\\logs\\backend.log.
+The desktop app sets AI_MENTOR_BASE_DIR in the service's registry Environment and
+writes the rest of the backend env (port, models dir, configured vars, secrets)
+to <base>\\service\\backend_env.json, which is merged in on every start.
 """
 from __future__ import annotations
 
+import json
 import os
 import subprocess
 import sys
@@ -26,6 +30,8 @@ except ImportError:
 SERVICE_NAME = "AI_Mentor_Backend"
 DISPLAY_NAME = "AI Mentor Backend"
 BACKEND_EXE_NAME = "ai-mentor-backend.exe"
+ENV_FILE_NAME = "backend_env.json"
+DEFAULT_PORT = "8000"
 
 
 def _get_local_app_data() -> Path:
@@ -54,6 +60,18 @@ def _get_backend_exe_path() -> Path:
     return _get_service_dir() / BACKEND_EXE_NAME
 
 
+def _load_app_env() -> dict[str, str]:
+    """Env written by the desktop app; empty when the file is missing or unreadable."""
+    path = _get_base_dir() / "service" / ENV_FILE_NAME
+    try:
+        data = json.loads(path.read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return {}
+    if not isinstance(data, dict):
+        return {}
+    return {str(k): str(v) for k, v in data.items()}
+
+
 def _ensure_dirs() -> None:
     base = _get_base_dir()
     (base / "service").mkdir(parents=True, exist_ok=True)
@@ -78,7 +96,7 @@ if win32serviceutil is not None and win32service is not None:
     class AIMentorBackendService(win32serviceutil.ServiceFramework):
         _svc_name_ = SERVICE_NAME
         _svc_display_name_ = DISPLAY_NAME
-        _svc_description_ = "Runs AI Mentor backend API (http://127.0.0.1:8000)."
+        _svc_description_ = "Runs AI Mentor backend API (http://127.0.0.1, port from AI_MENTOR_PORT)."
 
         def __init__(self, args: list[str]) -> None:
             win32serviceutil.ServiceFramework.__init__(self, args)
@@ -90,8 +108,9 @@ if win32serviceutil is not None and win32service is not None:
             exe_path = _get_backend_exe_path()
             log_path = _get_base_dir() / "logs" / "backend.log"
             env = os.environ.copy()
+            env.update(_load_app_env())
             env["AI_MENTOR_BASE_DIR"] = str(_get_base_dir())
-            env["AI_MENTOR_PORT"] = "8000"
+            env.setdefault("AI_MENTOR_PORT", DEFAULT_PORT)
             env["AI_MENTOR_PACKAGED"] = "1"
 
             try:
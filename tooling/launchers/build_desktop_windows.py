@@ -224,7 +224,6 @@ def main() -> int:
     tauri_bin = root / "app" / "frontend" / "src-tauri" / "bin"
     tauri_bin.mkdir(parents=True, exist_ok=True)
     _copy(exe_path, tauri_bin / "ai-mentor-backend-x86_64-pc-windows-msvc.exe")
-    _copy(exe_path, tauri_bin / "ai-mentor-backend.exe")
     _copy(root / "packaging" / "windows_task" / "launch_backend.cmd", tauri_bin / "launch_backend.cmd")
     print()
 